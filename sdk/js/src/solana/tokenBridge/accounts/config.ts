@@ -27,16 +27,27 @@ export async function getTokenBridgeConfig(
 
 export class TokenBridgeConfig {
   wormhole: PublicKey;
+  paused: boolean;
+  governed: boolean;
 
-  constructor(wormholeProgramId: Buffer) {
+  constructor(
+    wormholeProgramId: Buffer,
+    paused: boolean = false,
+    governed: boolean = false
+  ) {
     this.wormhole = new PublicKey(wormholeProgramId);
+    this.paused = paused;
+    this.governed = governed;
   }
 
   static deserialize(data: Buffer): TokenBridgeConfig {
-    if (data.length != 32) {
-      throw new Error("data.length != 32");
+    // The paused and governed flags are only stored once governance has set them.
+    if (data.length < 32 || data.length > 34) {
+      throw new Error("data.length < 32 || data.length > 34");
     }
     const wormholeProgramId = data.subarray(0, 32);
-    return new TokenBridgeConfig(wormholeProgramId);
+    const paused = data.length > 32 && data.readUInt8(32) != 0;
+    const governed = data.length > 33 && data.readUInt8(33) != 0;
+    return new TokenBridgeConfig(wormholeProgramId, paused, governed);
   }
 }
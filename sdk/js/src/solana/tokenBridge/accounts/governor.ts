@@ -0,0 +1,56 @@
+import { AccountMeta, PublicKey, PublicKeyInitData } from "@solana/web3.js";
+import { deriveAddress } from "../../utils";
+
+export function deriveTokenGovernorKey(
+  tokenBridgeProgramId: PublicKeyInitData,
+  mint: PublicKeyInitData
+): PublicKey {
+  return deriveAddress(
+    [Buffer.from("governor"), new PublicKey(mint).toBuffer()],
+    tokenBridgeProgramId
+  );
+}
+
+export function deriveChainGovernorKey(
+  tokenBridgeProgramId: PublicKeyInitData,
+  chain: number
+): PublicKey {
+  return deriveAddress(
+    [
+      Buffer.from("governor"),
+      (() => {
+        const buf = Buffer.alloc(2);
+        buf.writeUInt16BE(chain);
+        return buf;
+      })(),
+    ],
+    tokenBridgeProgramId
+  );
+}
+
+export function derivePendingTransferKey(
+  tokenBridgeProgramId: PublicKeyInitData,
+  message: PublicKeyInitData
+): PublicKey {
+  return deriveAddress(
+    [Buffer.from("pending"), new PublicKey(message).toBuffer()],
+    tokenBridgeProgramId
+  );
+}
+
+/**
+ * Outflow limits of a transfer and the account holding it if it exceeds them, passed after the
+ * accounts of the transfer. The token bridge requires them once governance has set a first limit.
+ */
+export function getTransferGovernorAccounts(
+  tokenBridgeProgramId: PublicKeyInitData,
+  mint: PublicKeyInitData,
+  targetChain: number,
+  message: PublicKeyInitData
+): AccountMeta[] {
+  return [
+    deriveTokenGovernorKey(tokenBridgeProgramId, mint),
+    deriveChainGovernorKey(tokenBridgeProgramId, targetChain),
+    derivePendingTransferKey(tokenBridgeProgramId, message),
+  ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }));
+}
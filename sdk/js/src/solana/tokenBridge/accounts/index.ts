@@ -1,6 +1,7 @@
 export * from "./config";
 export * from "./custody";
 export * from "./endpoint";
+export * from "./governor";
 export * from "./transferWithPayload";
 export * from "./signer";
 export * from "./wrapped";
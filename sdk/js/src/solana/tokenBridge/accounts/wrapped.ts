@@ -68,20 +68,42 @@ export class WrappedMeta {
   chain: number;
   tokenAddress: Buffer;
   originalDecimals: number;
+  lastUpdatedSequence: bigint;
+  lastUpdated: bigint;
 
-  constructor(chain: number, tokenAddress: Buffer, originalDecimals: number) {
+  constructor(
+    chain: number,
+    tokenAddress: Buffer,
+    originalDecimals: number,
+    lastUpdatedSequence: bigint = BigInt(0),
+    lastUpdated: bigint = BigInt(0)
+  ) {
     this.chain = chain;
     this.tokenAddress = tokenAddress;
     this.originalDecimals = originalDecimals;
+    this.lastUpdatedSequence = lastUpdatedSequence;
+    this.lastUpdated = lastUpdated;
   }
 
   static deserialize(data: Buffer): WrappedMeta {
-    if (data.length != 35) {
-      throw new Error("data.length != 35");
+    // The update fields are only stored once the metadata has been updated by an attestation.
+    if (data.length != 35 && data.length != 51) {
+      throw new Error("data.length != 35 && data.length != 51");
     }
     const chain = data.readUInt16LE(0);
     const tokenAddress = data.subarray(2, 34);
     const originalDecimals = data.readUInt8(34);
-    return new WrappedMeta(chain, tokenAddress, originalDecimals);
+    if (data.length == 35) {
+      return new WrappedMeta(chain, tokenAddress, originalDecimals);
+    }
+    const lastUpdatedSequence = data.readBigUInt64LE(35);
+    const lastUpdated = data.readBigInt64LE(43);
+    return new WrappedMeta(
+      chain,
+      tokenAddress,
+      originalDecimals,
+      lastUpdatedSequence,
+      lastUpdated
+    );
   }
 }
@@ -11,6 +11,7 @@ import {
   deriveCustodySignerKey,
   deriveTokenBridgeConfigKey,
   deriveCustodyKey,
+  getTransferGovernorAccounts,
 } from "../accounts";
 
 export function createTransferNativeInstruction(
@@ -47,7 +48,12 @@ export function createTransferNativeInstruction(
       mint
     ) as any,
     signers: undefined,
-    remainingAccounts: undefined,
+    remainingAccounts: getTransferGovernorAccounts(
+      tokenBridgeProgramId,
+      mint,
+      targetChain,
+      message
+    ),
     preInstructions: undefined,
     postInstructions: undefined,
   });
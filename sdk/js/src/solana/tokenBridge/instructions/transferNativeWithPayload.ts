@@ -12,6 +12,7 @@ import {
   deriveTokenBridgeConfigKey,
   deriveCustodyKey,
   deriveSenderAccountKey,
+  getTransferGovernorAccounts,
 } from "../accounts";
 
 export function createTransferNativeWithPayloadInstruction(
@@ -49,7 +50,12 @@ export function createTransferNativeWithPayloadInstruction(
       mint
     ) as any,
     signers: undefined,
-    remainingAccounts: undefined,
+    remainingAccounts: getTransferGovernorAccounts(
+      tokenBridgeProgramId,
+      mint,
+      targetChain,
+      message
+    ),
     preInstructions: undefined,
     postInstructions: undefined,
   });
@@ -11,6 +11,7 @@ import {
   deriveTokenBridgeConfigKey,
   deriveWrappedMetaKey,
   deriveWrappedMintKey,
+  getTransferGovernorAccounts,
 } from "../accounts";
 
 export function createTransferWrappedInstruction(
@@ -51,7 +52,12 @@ export function createTransferWrappedInstruction(
       tokenAddress
     ) as any,
     signers: undefined,
-    remainingAccounts: undefined,
+    remainingAccounts: getTransferGovernorAccounts(
+      tokenBridgeProgramId,
+      deriveWrappedMintKey(tokenBridgeProgramId, tokenChain, tokenAddress),
+      targetChain,
+      message
+    ),
     preInstructions: undefined,
     postInstructions: undefined,
   });
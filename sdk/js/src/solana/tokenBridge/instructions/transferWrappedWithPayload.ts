@@ -12,6 +12,7 @@ import {
   deriveTokenBridgeConfigKey,
   deriveWrappedMetaKey,
   deriveWrappedMintKey,
+  getTransferGovernorAccounts,
 } from "../accounts";
 
 export function createTransferWrappedWithPayloadInstruction(
@@ -53,7 +54,12 @@ export function createTransferWrappedWithPayloadInstruction(
       tokenAddress
     ) as any,
     signers: undefined,
-    remainingAccounts: undefined,
+    remainingAccounts: getTransferGovernorAccounts(
+      tokenBridgeProgramId,
+      deriveWrappedMintKey(tokenBridgeProgramId, tokenChain, tokenAddress),
+      targetChain,
+      message
+    ),
     preInstructions: undefined,
     postInstructions: undefined,
   });
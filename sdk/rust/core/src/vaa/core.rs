@@ -21,6 +21,10 @@ use crate::vaa::{
     parse_fixed,
     GovernanceAction,
 };
+use crate::{
+    Chain,
+    WormholeError,
+};
 
 pub struct GovernanceContractUpgrade {
     pub new_contract: [u8; 32],
@@ -97,3 +101,136 @@ impl GovernanceAction for GovernanceTransferFees {
         ))
     }
 }
+
+pub struct GovernanceSetGuardianSetExpiration {
+    pub guardian_set_expiration_time: u32,
+    pub guardian_set_index:           u32,
+    pub expiration_time:              u32,
+}
+
+impl GovernanceAction for GovernanceSetGuardianSetExpiration {
+    const MODULE: &'static [u8] = b"Core";
+    const ACTION: u8 = 8;
+    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
+        let (i, guardian_set_expiration_time) = u32(Endianness::Big)(input)?;
+        let (i, guardian_set_index) = u32(Endianness::Big)(i)?;
+        let (i, expiration_time) = u32(Endianness::Big)(i)?;
+        Ok((
+            i,
+            Self {
+                guardian_set_expiration_time,
+                guardian_set_index,
+                expiration_time,
+            },
+        ))
+    }
+}
+
+impl GovernanceSetGuardianSetExpiration {
+    /// Serialize to Wormhole wire format, including the governance header targeting `chain`.
+    pub fn serialize(&self, chain: Chain) -> Result<Vec<u8>, WormholeError> {
+        use byteorder::{
+            BigEndian,
+            WriteBytesExt,
+        };
+        use std::io::Write;
+
+        let mut module = [0u8; 32];
+        module[32 - Self::MODULE.len()..].copy_from_slice(Self::MODULE);
+
+        let mut v = Vec::with_capacity(32 + 1 + 2 + 12);
+        let write = |v: &mut Vec<u8>| -> std::io::Result<()> {
+            v.write_all(&module)?;
+            v.write_u8(Self::ACTION)?;
+            v.write_u16::<BigEndian>(chain as u16)?;
+            v.write_u32::<BigEndian>(self.guardian_set_expiration_time)?;
+            v.write_u32::<BigEndian>(self.guardian_set_index)?;
+            v.write_u32::<BigEndian>(self.expiration_time)?;
+            Ok(())
+        };
+        write(&mut v).map_err(|_| WormholeError::DeserializeFailed)?;
+        Ok(v)
+    }
+}
+
+pub struct GovernanceSetSplFee {
+    pub mint: [u8; 32],
+    pub fee:  U256,
+}
+
+impl GovernanceAction for GovernanceSetSplFee {
+    const MODULE: &'static [u8] = b"Core";
+    const ACTION: u8 = 6;
+    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
+        let (i, mint) = parse_fixed(input)?;
+        let mut fee = [0u8; 32];
+        let (i, _) = fill(u8, &mut fee)(i)?;
+        Ok((
+            i,
+            Self {
+                mint,
+                fee: U256::from_big_endian(&fee),
+            },
+        ))
+    }
+}
+
+pub struct GovernanceTransferSplFees {
+    pub mint:   [u8; 32],
+    pub amount: U256,
+    pub to:     [u8; 32],
+}
+
+impl GovernanceAction for GovernanceTransferSplFees {
+    const MODULE: &'static [u8] = b"Core";
+    const ACTION: u8 = 7;
+    fn parse(input: &[u8]) -> IResult<&[u8], Self> {
+        let (i, mint) = parse_fixed(input)?;
+        let mut amount = [0u8; 32];
+        let (i, _) = fill(u8, &mut amount)(i)?;
+        let (i, to) = parse_fixed(i)?;
+        Ok((
+            i,
+            Self {
+                mint,
+                amount: U256::from_big_endian(&amount),
+                to,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod testing {
+    use super::GovernanceSetGuardianSetExpiration;
+    use crate::vaa::GovernanceAction;
+    use crate::Chain;
+
+    #[test]
+    fn test_guardian_set_expiration_roundtrip() {
+        let action = GovernanceSetGuardianSetExpiration {
+            guardian_set_expiration_time: 86400,
+            guardian_set_index:           2,
+            expiration_time:              1,
+        };
+
+        let payload = action.serialize(Chain::Solana).unwrap();
+        assert_eq!(
+            hex::encode(&payload),
+            "00000000000000000000000000000000000000000000000000000000436f7265050001000151800000000200000001"
+        );
+
+        let (header, parsed) =
+            GovernanceSetGuardianSetExpiration::from_bytes(&payload, Some(Chain::Solana)).unwrap();
+        assert_eq!(header.action, 5);
+        assert_eq!(header.chains, Chain::Solana);
+        assert_eq!(parsed.guardian_set_expiration_time, 86400);
+        assert_eq!(parsed.guardian_set_index, 2);
+        assert_eq!(parsed.expiration_time, 1);
+
+        // Actions targeting another chain must be rejected.
+        assert!(
+            GovernanceSetGuardianSetExpiration::from_bytes(&payload, Some(Chain::Terra)).is_err()
+        );
+    }
+}
@@ -0,0 +1,137 @@
+//! Anchor interface for the Wormhole core and token bridges.
+//!
+//! The bridges are Solitaire programs: an instruction is selected by a single byte followed by its
+//! Borsh encoded data, and accounts are told apart by magic prefixes such as `b"vaa"` rather than
+//! Anchor's eight byte discriminators. This crate hides that from Anchor programs by providing,
+//! for each instruction, an accounts struct to build a `CpiContext` with and a function invoking
+//! it, as Anchor generates for its own programs. Bridge accounts that programs commonly read are
+//! wrapped so that they can be loaded with `Account<'info, T>`.
+//!
+//! Accounts structs are generated from the Solitaire accounts struct of each instruction: they
+//! name the same accounts, and their metas are built by the client accounts struct
+//! `#[derive(FromAccounts)]` generates, so the flags follow the layers the program peels them with.
+//! An accounts struct that misses or adds an account doesn't compile. Only the programs and sysvars
+//! the instruction invokes, which the program doesn't peel, are listed by hand after the accounts.
+
+use anchor_lang::prelude::*;
+use borsh::BorshSerialize;
+use solana_program::{
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    program::invoke_signed,
+};
+use solitaire::idl::{
+    IdlAccount,
+    IdlMutability,
+};
+
+pub mod token_bridge;
+pub mod wormhole;
+
+/// Metas of the accounts of an instruction, as built by its client accounts struct. Accounts that
+/// may be either mutable or not are as writable as the caller passed them in `infos`, and
+/// `is_signer` overrides the signer flag as for Anchor's own accounts.
+pub fn account_metas(
+    accounts: &[IdlAccount],
+    mut metas: Vec<AccountMeta>,
+    infos: &[&AccountInfo],
+    is_signer: Option<bool>,
+) -> Vec<AccountMeta> {
+    for (account, meta) in accounts.iter().zip(&mut metas) {
+        if account.mutability == IdlMutability::MaybeMut {
+            meta.is_writable = infos
+                .iter()
+                .any(|info| *info.key == meta.pubkey && info.is_writable);
+        }
+        if let Some(is_signer) = is_signer {
+            meta.is_signer = is_signer;
+        }
+    }
+    metas
+}
+
+/// Declares the accounts struct of an instruction from its Solitaire accounts struct, the client
+/// accounts struct generated for it and the program it belongs to. The accounts are followed by
+/// the `dependencies` the instruction only invokes, which are always passed read-only.
+macro_rules! cpi_accounts {
+    (
+        $(#[$attr:meta])*
+        pub struct $name:ident {
+            $($(#[$field_attr:meta])* $field:ident,)*
+        }
+        solitaire {
+            accounts: $accounts:path,
+            client: $client:ty,
+            program: $program:ty,
+        }
+        dependencies {
+            $($(#[$dependency_attr:meta])* $dependency:ident,)*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone)]
+        pub struct $name<'info> {
+            $($(#[$field_attr])* pub $field: solana_program::account_info::AccountInfo<'info>,)*
+            $($(#[$dependency_attr])* pub $dependency: solana_program::account_info::AccountInfo<'info>,)*
+        }
+
+        impl anchor_lang::ToAccountMetas for $name<'_> {
+            fn to_account_metas(
+                &self,
+                is_signer: Option<bool>,
+            ) -> Vec<solana_program::instruction::AccountMeta> {
+                type Client = $client;
+                let client = Client {
+                    $($field: solitaire::client::ClientKey::from_pubkey(*self.$field.key),)*
+                };
+                let mut metas = $crate::account_metas(
+                    &<$accounts as solitaire::idl::IdlAccounts>::accounts(),
+                    client.to_account_metas(&<$program as anchor_lang::Id>::id()),
+                    &[$(&self.$field,)*],
+                    is_signer,
+                );
+                $(metas.push(solana_program::instruction::AccountMeta::new_readonly(
+                    *self.$dependency.key,
+                    false,
+                ));)*
+                metas
+            }
+        }
+
+        impl<'info> anchor_lang::ToAccountInfos<'info> for $name<'info> {
+            fn to_account_infos(&self) -> Vec<solana_program::account_info::AccountInfo<'info>> {
+                vec![$(self.$field.clone(),)* $(self.$dependency.clone(),)*]
+            }
+        }
+    };
+}
+
+pub(crate) use cpi_accounts;
+
+/// Invoke the program of `ctx` with `data`, which starts with the Solitaire instruction
+/// discriminant, signing with the seeds of the context. Remaining accounts are passed after the
+/// accounts of the instruction as the caller has them.
+pub(crate) fn invoke<'info, T>(
+    ctx: CpiContext<'_, '_, '_, 'info, T>,
+    data: impl BorshSerialize,
+) -> Result<()>
+where
+    T: ToAccountMetas + ToAccountInfos<'info>,
+{
+    let mut accounts = ctx.accounts.to_account_metas(None);
+    accounts.extend(ctx.remaining_accounts.iter().map(|info| AccountMeta {
+        pubkey: *info.key,
+        is_signer: info.is_signer,
+        is_writable: info.is_writable,
+    }));
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts,
+        data: data
+            .try_to_vec()
+            .map_err(|_| error!(ErrorCode::InstructionDidNotSerialize))?,
+    };
+    invoke_signed(&ix, &ctx.to_account_infos(), ctx.signer_seeds).map_err(Into::into)
+}
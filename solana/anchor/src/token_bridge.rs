@@ -0,0 +1,452 @@
+//! Token bridge.
+//!
+//! Transfers post their message through the core bridge, so the message fee has to be paid to
+//! the fee collector of the core bridge beforehand, and the token bridge has to be allowed to
+//! move the tokens: transfers burn or lock from `from` through an approval of `authority_signer`.
+//! Once governance has set an outflow limit, transfers pass the accounts of
+//! [`token_bridge::api::TransferGovernor`] as writable remaining accounts of the context.
+//! Transfers exceeding the limits are held in `pending` instead of posted, and can be released by
+//! anyone a day later, posting the message to an account derived from `pending`.
+//! Native transfers of Token-2022 mints pass the Token-2022 program as `token_program`.
+
+use std::{
+    ops::Deref,
+    str::FromStr,
+};
+
+use anchor_lang::prelude::*;
+use borsh::BorshDeserialize;
+use token_bridge::{
+    instruction::Instruction,
+    types::{
+        VersionedConfig,
+        VersionedWrappedMeta,
+    },
+};
+
+use crate::{
+    cpi_accounts,
+    invoke,
+};
+
+pub use token_bridge::{
+    CompleteNativeData,
+    CompleteNativeWithPayloadData,
+    CompleteWrappedData,
+    CompleteWrappedWithPayloadData,
+    TransferNativeData,
+    TransferNativeWithPayloadData,
+    TransferWrappedData,
+    TransferWrappedWithPayloadData,
+};
+
+/// The token bridge program, for `Program<'info, TokenBridge>`.
+#[derive(Clone)]
+pub struct TokenBridge;
+
+impl Id for TokenBridge {
+    fn id() -> Pubkey {
+        Pubkey::from_str(env!("TOKEN_BRIDGE_ADDRESS")).unwrap()
+    }
+}
+
+cpi_accounts! {
+    /// Accounts of [`token_bridge::TransferNative`].
+    pub struct TransferNative {
+        payer,
+        config,
+        from,
+        mint,
+        custody,
+        authority_signer,
+        custody_signer,
+        bridge,
+        message,
+        emitter,
+        sequence,
+        fee_collector,
+        clock,
+    }
+    solitaire {
+        accounts: token_bridge::TransferNative,
+        client: token_bridge::api::TransferNativeAccounts,
+        program: TokenBridge,
+    }
+    dependencies {
+        rent,
+        system_program,
+        wormhole_program,
+        token_program,
+    }
+}
+
+cpi_accounts! {
+    /// Accounts of [`token_bridge::TransferWrapped`].
+    pub struct TransferWrapped {
+        payer,
+        config,
+        from,
+        from_owner,
+        mint,
+        wrapped_meta,
+        authority_signer,
+        bridge,
+        message,
+        emitter,
+        sequence,
+        fee_collector,
+        clock,
+    }
+    solitaire {
+        accounts: token_bridge::TransferWrapped,
+        client: token_bridge::api::TransferWrappedAccounts,
+        program: TokenBridge,
+    }
+    dependencies {
+        rent,
+        system_program,
+        wormhole_program,
+        token_program,
+    }
+}
+
+cpi_accounts! {
+    /// Accounts of [`token_bridge::TransferNativeWithPayload`]. The sender signs with the seeds
+    /// `[b"sender"]` of the program named in the instruction data, or is the payer.
+    pub struct TransferNativeWithPayload {
+        payer,
+        config,
+        from,
+        mint,
+        custody,
+        authority_signer,
+        custody_signer,
+        bridge,
+        message,
+        emitter,
+        sequence,
+        fee_collector,
+        clock,
+        sender,
+    }
+    solitaire {
+        accounts: token_bridge::TransferNativeWithPayload,
+        client: token_bridge::api::TransferNativeWithPayloadAccounts,
+        program: TokenBridge,
+    }
+    dependencies {
+        rent,
+        system_program,
+        wormhole_program,
+        token_program,
+    }
+}
+
+cpi_accounts! {
+    /// Accounts of [`token_bridge::TransferWrappedWithPayload`], see
+    /// [`TransferNativeWithPayload`] for the sender.
+    pub struct TransferWrappedWithPayload {
+        payer,
+        config,
+        from,
+        from_owner,
+        mint,
+        wrapped_meta,
+        authority_signer,
+        bridge,
+        message,
+        emitter,
+        sequence,
+        fee_collector,
+        clock,
+        sender,
+    }
+    solitaire {
+        accounts: token_bridge::TransferWrappedWithPayload,
+        client: token_bridge::api::TransferWrappedWithPayloadAccounts,
+        program: TokenBridge,
+    }
+    dependencies {
+        rent,
+        system_program,
+        wormhole_program,
+        token_program,
+    }
+}
+
+cpi_accounts! {
+    /// Accounts of [`token_bridge::CompleteNative`].
+    pub struct CompleteNative {
+        payer,
+        config,
+        vaa,
+        claim,
+        chain_registration,
+        to,
+        to_fees,
+        custody,
+        mint,
+        custody_signer,
+        /// Wallet of `to` if it is an associated token account the instruction creates, else
+        /// `to` again.
+        to_owner,
+    }
+    solitaire {
+        accounts: token_bridge::CompleteNative,
+        client: token_bridge::api::CompleteNativeAccounts,
+        program: TokenBridge,
+    }
+    dependencies {
+        rent,
+        system_program,
+        wormhole_program,
+        token_program,
+        associated_token_program,
+    }
+}
+
+cpi_accounts! {
+    /// Accounts of [`token_bridge::CompleteWrapped`].
+    pub struct CompleteWrapped {
+        payer,
+        config,
+        vaa,
+        claim,
+        chain_registration,
+        to,
+        to_fees,
+        mint,
+        wrapped_meta,
+        mint_authority,
+        /// Wallet of `to` if it is an associated token account the instruction creates, else
+        /// `to` again.
+        to_owner,
+    }
+    solitaire {
+        accounts: token_bridge::CompleteWrapped,
+        client: token_bridge::api::CompleteWrappedAccounts,
+        program: TokenBridge,
+    }
+    dependencies {
+        rent,
+        system_program,
+        wormhole_program,
+        token_program,
+        associated_token_program,
+    }
+}
+
+cpi_accounts! {
+    /// Accounts of [`token_bridge::CompleteNativeWithPayload`]. The redeemer is the recipient
+    /// of the transfer and signs, usually through the seeds of the calling program.
+    pub struct CompleteNativeWithPayload {
+        payer,
+        config,
+        vaa,
+        claim,
+        chain_registration,
+        to,
+        redeemer,
+        to_fees,
+        custody,
+        mint,
+        custody_signer,
+    }
+    solitaire {
+        accounts: token_bridge::CompleteNativeWithPayload,
+        client: token_bridge::api::CompleteNativeWithPayloadAccounts,
+        program: TokenBridge,
+    }
+    dependencies {
+        rent,
+        system_program,
+        wormhole_program,
+        token_program,
+    }
+}
+
+cpi_accounts! {
+    /// Accounts of [`token_bridge::CompleteWrappedWithPayload`], see
+    /// [`CompleteNativeWithPayload`] for the redeemer.
+    pub struct CompleteWrappedWithPayload {
+        payer,
+        config,
+        vaa,
+        claim,
+        chain_registration,
+        to,
+        redeemer,
+        to_fees,
+        mint,
+        wrapped_meta,
+        mint_authority,
+    }
+    solitaire {
+        accounts: token_bridge::CompleteWrappedWithPayload,
+        client: token_bridge::api::CompleteWrappedWithPayloadAccounts,
+        program: TokenBridge,
+    }
+    dependencies {
+        rent,
+        system_program,
+        wormhole_program,
+        token_program,
+    }
+}
+
+pub fn transfer_native<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferNative<'info>>,
+    data: TransferNativeData,
+) -> Result<()> {
+    invoke(ctx, (Instruction::TransferNative, data))
+}
+
+pub fn transfer_wrapped<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferWrapped<'info>>,
+    data: TransferWrappedData,
+) -> Result<()> {
+    invoke(ctx, (Instruction::TransferWrapped, data))
+}
+
+pub fn transfer_native_with_payload<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferNativeWithPayload<'info>>,
+    data: TransferNativeWithPayloadData,
+) -> Result<()> {
+    invoke(ctx, (Instruction::TransferNativeWithPayload, data))
+}
+
+pub fn transfer_wrapped_with_payload<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferWrappedWithPayload<'info>>,
+    data: TransferWrappedWithPayloadData,
+) -> Result<()> {
+    invoke(ctx, (Instruction::TransferWrappedWithPayload, data))
+}
+
+pub fn complete_native<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, CompleteNative<'info>>,
+) -> Result<()> {
+    invoke(ctx, (Instruction::CompleteNative, CompleteNativeData {}))
+}
+
+pub fn complete_wrapped<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, CompleteWrapped<'info>>,
+) -> Result<()> {
+    invoke(ctx, (Instruction::CompleteWrapped, CompleteWrappedData {}))
+}
+
+pub fn complete_native_with_payload<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, CompleteNativeWithPayload<'info>>,
+) -> Result<()> {
+    invoke(
+        ctx,
+        (
+            Instruction::CompleteNativeWithPayload,
+            CompleteNativeWithPayloadData {},
+        ),
+    )
+}
+
+pub fn complete_wrapped_with_payload<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, CompleteWrappedWithPayload<'info>>,
+) -> Result<()> {
+    invoke(
+        ctx,
+        (
+            Instruction::CompleteWrappedWithPayload,
+            CompleteWrappedWithPayloadData {},
+        ),
+    )
+}
+
+/// Origin of a wrapped mint, for `Account<'info, WrappedMeta>`. Accounts written at any version
+/// are read as the current layout.
+///
+/// Wrapped meta has no discriminator, only the length of the data is checked, which other token
+/// bridge accounts may share. Constrain the address to the wrapped meta of the mint, with
+/// `seeds = [b"meta", mint.key().as_ref()]` and `seeds::program = TokenBridge::id()`.
+#[derive(Clone)]
+pub struct WrappedMeta(pub token_bridge::types::WrappedMeta);
+
+impl WrappedMeta {
+    /// Serialized length of each layout wrapped meta is stored at, oldest first.
+    pub const LAYOUT_LENGTHS: [usize; 2] = [35, 51];
+}
+
+impl AccountDeserialize for WrappedMeta {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        if !Self::LAYOUT_LENGTHS.contains(&buf.len()) {
+            return Err(error!(ErrorCode::AccountDidNotDeserialize));
+        }
+        Self::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        VersionedWrappedMeta::deserialize(buf)
+            .map(|meta| WrappedMeta(meta.data))
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+}
+
+/// Owned by the token bridge, so never written back by the calling program.
+impl AccountSerialize for WrappedMeta {
+}
+
+impl Owner for WrappedMeta {
+    fn owner() -> Pubkey {
+        TokenBridge::id()
+    }
+}
+
+impl Deref for WrappedMeta {
+    type Target = token_bridge::types::WrappedMeta;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Token bridge configuration, for `Account<'info, Config>`. Accounts written at any version are
+/// read as the current layout.
+///
+/// Like [`WrappedMeta`], only the length of the data is checked. Constrain the address with
+/// `seeds = [b"config"]` and `seeds::program = TokenBridge::id()`.
+#[derive(Clone)]
+pub struct Config(pub token_bridge::types::Config);
+
+impl Config {
+    /// Serialized length of each layout the configuration is stored at, oldest first.
+    pub const LAYOUT_LENGTHS: [usize; 3] = [32, 33, 34];
+}
+
+impl AccountDeserialize for Config {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        if !Self::LAYOUT_LENGTHS.contains(&buf.len()) {
+            return Err(error!(ErrorCode::AccountDidNotDeserialize));
+        }
+        Self::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        VersionedConfig::deserialize(buf)
+            .map(|config| Config(config.data))
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+}
+
+/// Owned by the token bridge, so never written back by the calling program.
+impl AccountSerialize for Config {
+}
+
+impl Owner for Config {
+    fn owner() -> Pubkey {
+        TokenBridge::id()
+    }
+}
+
+impl Deref for Config {
+    type Target = token_bridge::types::Config;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
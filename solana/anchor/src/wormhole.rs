@@ -0,0 +1,154 @@
+//! Core bridge.
+
+use std::{
+    ops::Deref,
+    str::FromStr,
+};
+
+use crate::{
+    cpi_accounts,
+    invoke,
+};
+use anchor_lang::prelude::*;
+use borsh::BorshDeserialize;
+use bridge::{
+    instruction::Instruction,
+    PostMessageData,
+    PostedVAAData,
+};
+
+pub use bridge::types::ConsistencyLevel;
+
+/// The core bridge program, for `Program<'info, Wormhole>`.
+#[derive(Clone)]
+pub struct Wormhole;
+
+impl Id for Wormhole {
+    fn id() -> Pubkey {
+        Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap()
+    }
+}
+
+cpi_accounts! {
+    /// Accounts of [`bridge::PostMessage`]. The message fee has to be paid to the fee collector
+    /// before posting, and the emitter usually signs through the seeds of the calling program.
+    pub struct PostMessage {
+        bridge,
+        message,
+        emitter,
+        sequence,
+        payer,
+        fee_collector,
+        clock,
+    }
+    solitaire {
+        accounts: bridge::PostMessage,
+        client: bridge::api::PostMessageAccounts,
+        program: Wormhole,
+    }
+    dependencies {
+        rent,
+        system_program,
+    }
+}
+
+cpi_accounts! {
+    /// Accounts of [`bridge::PostMessageUnreliable`], see [`PostMessage`].
+    pub struct PostMessageUnreliable {
+        bridge,
+        message,
+        emitter,
+        sequence,
+        payer,
+        fee_collector,
+        clock,
+    }
+    solitaire {
+        accounts: bridge::PostMessageUnreliable,
+        client: bridge::api::PostMessageUnreliableAccounts,
+        program: Wormhole,
+    }
+    dependencies {
+        rent,
+        system_program,
+    }
+}
+
+/// Post a message through the core bridge, see [`bridge::post_message`].
+pub fn post_message<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, PostMessage<'info>>,
+    nonce: u32,
+    payload: Vec<u8>,
+    consistency_level: ConsistencyLevel,
+) -> Result<()> {
+    invoke(
+        ctx,
+        (
+            Instruction::PostMessage,
+            PostMessageData {
+                nonce,
+                payload,
+                consistency_level,
+            },
+        ),
+    )
+}
+
+/// Post a message whose account can be reused by the next message of the emitter, see
+/// [`bridge::post_message_unreliable`].
+pub fn post_message_unreliable<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, PostMessageUnreliable<'info>>,
+    nonce: u32,
+    payload: Vec<u8>,
+    consistency_level: ConsistencyLevel,
+) -> Result<()> {
+    invoke(
+        ctx,
+        (
+            Instruction::PostMessageUnreliable,
+            PostMessageData {
+                nonce,
+                payload,
+                consistency_level,
+            },
+        ),
+    )
+}
+
+/// A verified VAA posted by the core bridge, for `Account<'info, PostedVaa>`.
+#[derive(Clone)]
+pub struct PostedVaa(pub PostedVAAData);
+
+impl AccountDeserialize for PostedVaa {
+    /// Posted messages share the layout of VAAs, only accounts with the `b"vaa"` magic are VAAs.
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        if !buf.starts_with(b"vaa") {
+            return Err(error!(ErrorCode::AccountDiscriminatorMismatch));
+        }
+        Self::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        PostedVAAData::deserialize(buf)
+            .map(PostedVaa)
+            .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+}
+
+/// Owned by the core bridge, so never written back by the calling program.
+impl AccountSerialize for PostedVaa {
+}
+
+impl Owner for PostedVaa {
+    fn owner() -> Pubkey {
+        Wormhole::id()
+    }
+}
+
+impl Deref for PostedVaa {
+    type Target = PostedVAAData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
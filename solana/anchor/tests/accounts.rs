@@ -0,0 +1,166 @@
+//! The Anchor accounts structs are built from the client accounts structs of the programs, check
+//! that the metas they produce follow the accounts the programs describe in their IDL.
+
+use ::token_bridge::types::{
+    ConfigV0,
+    ConfigV1,
+    WrappedMetaV0,
+};
+use anchor_lang::prelude::*;
+use borsh::BorshSerialize;
+use solana_program::{
+    system_program,
+    sysvar,
+};
+use solitaire::idl::{
+    IdlAccounts,
+    IdlMutability,
+};
+use wormhole_anchor::{
+    token_bridge,
+    wormhole,
+};
+
+fn info(key: Pubkey, is_writable: bool) -> AccountInfo<'static> {
+    AccountInfo::new(
+        Box::leak(Box::new(key)),
+        false,
+        is_writable,
+        Box::leak(Box::new(0)),
+        Box::leak(Box::new([])),
+        Box::leak(Box::new(Pubkey::default())),
+        false,
+        0,
+    )
+}
+
+#[test]
+fn post_message_metas() {
+    let program = wormhole::Wormhole::id();
+    let bridge = Pubkey::find_program_address(&[b"Bridge"], &program).0;
+    let fee_collector = Pubkey::find_program_address(&[b"fee_collector"], &program).0;
+    let message = Pubkey::new_unique();
+    let emitter = Pubkey::new_unique();
+    let sequence = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let accounts = |emitter_writable| wormhole::PostMessage {
+        bridge: info(bridge, true),
+        message: info(message, true),
+        emitter: info(emitter, emitter_writable),
+        sequence: info(sequence, true),
+        payer: info(payer, true),
+        fee_collector: info(fee_collector, true),
+        clock: info(sysvar::clock::id(), false),
+        rent: info(sysvar::rent::id(), false),
+        system_program: info(system_program::id(), false),
+    };
+
+    assert_eq!(
+        accounts(false).to_account_metas(None),
+        vec![
+            AccountMeta::new(bridge, false),
+            AccountMeta::new(message, true),
+            AccountMeta::new_readonly(emitter, true),
+            AccountMeta::new(sequence, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new(fee_collector, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ]
+    );
+
+    // The emitter may be mutable, and is passed as the caller has it.
+    assert!(accounts(true).to_account_metas(None)[2].is_writable);
+}
+
+#[test]
+fn complete_native_metas_follow_idl() {
+    let accounts = token_bridge::CompleteNative {
+        payer: info(Pubkey::new_unique(), false),
+        config: info(Pubkey::new_unique(), false),
+        vaa: info(Pubkey::new_unique(), false),
+        claim: info(Pubkey::new_unique(), false),
+        chain_registration: info(Pubkey::new_unique(), false),
+        to: info(Pubkey::new_unique(), false),
+        to_fees: info(Pubkey::new_unique(), false),
+        custody: info(Pubkey::new_unique(), false),
+        mint: info(Pubkey::new_unique(), false),
+        custody_signer: info(Pubkey::new_unique(), false),
+        to_owner: info(Pubkey::new_unique(), false),
+        rent: info(sysvar::rent::id(), false),
+        system_program: info(system_program::id(), false),
+        wormhole_program: info(wormhole::Wormhole::id(), false),
+        token_program: info(Pubkey::new_unique(), false),
+        associated_token_program: info(Pubkey::new_unique(), false),
+    };
+    let idl = ::token_bridge::CompleteNative::accounts();
+    let metas = accounts.to_account_metas(None);
+
+    assert_eq!(metas.len(), idl.len() + 5);
+    for (account, meta) in idl.iter().zip(&metas) {
+        assert_eq!(meta.is_signer, account.signer, "{}", account.name);
+        assert_eq!(
+            meta.is_writable,
+            account.mutability == IdlMutability::Mut,
+            "{}",
+            account.name
+        );
+    }
+    for (meta, dependency) in metas[idl.len()..].iter().zip([
+        &accounts.rent,
+        &accounts.system_program,
+        &accounts.wormhole_program,
+        &accounts.token_program,
+        &accounts.associated_token_program,
+    ]) {
+        assert_eq!(meta, &AccountMeta::new_readonly(*dependency.key, false));
+    }
+}
+
+#[test]
+fn token_bridge_accounts_are_checked_by_length() {
+    let meta_v0 = WrappedMetaV0 {
+        chain: 2,
+        token_address: [1; 32],
+        original_decimals: 18,
+    }
+    .try_to_vec()
+    .unwrap();
+    let meta = ::token_bridge::types::WrappedMeta::default()
+        .try_to_vec()
+        .unwrap();
+    assert_eq!(
+        token_bridge::WrappedMeta::LAYOUT_LENGTHS,
+        [meta_v0.len(), meta.len()]
+    );
+    for data in [&meta_v0, &meta] {
+        assert!(token_bridge::WrappedMeta::try_deserialize(&mut data.as_slice()).is_ok());
+    }
+    let mut long = meta_v0.clone();
+    long.push(0);
+    assert!(token_bridge::WrappedMeta::try_deserialize(&mut long.as_slice()).is_err());
+
+    let config_v0 = ConfigV0 {
+        wormhole_bridge: Pubkey::new_unique(),
+    }
+    .try_to_vec()
+    .unwrap();
+    let config_v1 = ConfigV1 {
+        wormhole_bridge: Pubkey::new_unique(),
+        paused: true,
+    }
+    .try_to_vec()
+    .unwrap();
+    let config = ::token_bridge::types::Config::default()
+        .try_to_vec()
+        .unwrap();
+    assert_eq!(
+        token_bridge::Config::LAYOUT_LENGTHS,
+        [config_v0.len(), config_v1.len(), config.len()]
+    );
+    for data in [&config_v0, &config_v1, &config] {
+        assert!(token_bridge::Config::try_deserialize(&mut data.as_slice()).is_ok());
+    }
+    assert!(token_bridge::Config::try_deserialize(&mut &meta_v0[..]).is_err());
+}
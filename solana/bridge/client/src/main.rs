@@ -3,15 +3,44 @@
 
 use std::{
     fmt::Display,
+    fs,
     mem::size_of,
+    path::Path,
     process::exit,
+    rc::Rc,
 };
 
 use borsh::BorshDeserialize;
-use bridge::accounts::{
-    Bridge,
-    BridgeData,
-    FeeCollector,
+use bridge::{
+    accounts::{
+        Bridge,
+        BridgeData,
+        FeeCollector,
+        GuardianSet,
+        GuardianSetData,
+        GuardianSetDerivationData,
+        MessageData,
+        PostedVAA,
+        PostedVAAData,
+        PostedVAADerivationData,
+        Sequence,
+        SequenceDerivationData,
+        SequenceTracker,
+        VersionedBridgeData,
+    },
+    instructions::hash_vaa,
+    types::{
+        GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetGuardianSetExpiration,
+        GovernancePayloadSetMessageFee,
+        GovernancePayloadSetSplFee,
+        GovernancePayloadTransferFees,
+        GovernancePayloadTransferSplFees,
+        GovernancePayloadUpgrade,
+    },
+    vaa::VAA,
+    DeserializePayload,
+    SerializeGovernancePayload,
 };
 use clap::{
     crate_description,
@@ -27,16 +56,18 @@ use solana_clap_utils::{
     input_parsers::{
         keypair_of,
         pubkey_of,
+        pubkeys_of,
         value_of,
         values_of,
     },
     input_validators::{
-        is_keypair,
+        is_pubkey,
         is_pubkey_or_keypair,
         is_url,
     },
 };
 use solana_client::{
+    nonce_utils,
     rpc_client::RpcClient,
     rpc_config::RpcSendTransactionConfig,
 };
@@ -45,6 +76,9 @@ use solana_sdk::{
         CommitmentConfig,
         CommitmentLevel,
     },
+    hash::Hash,
+    instruction::Instruction,
+    message::Message,
     native_token::*,
     pubkey::Pubkey,
     signature::{
@@ -52,7 +86,10 @@ use solana_sdk::{
         Keypair,
         Signer,
     },
-    system_instruction::transfer,
+    system_instruction::{
+        advance_nonce_account,
+        transfer,
+    },
     transaction::Transaction,
 };
 use solitaire::{
@@ -64,13 +101,31 @@ use solitaire::{
 
 struct Config {
     rpc_client: RpcClient,
-    owner: Keypair,
-    fee_payer: Keypair,
+    owner: Pubkey,
+    fee_payer: Pubkey,
+    /// Keypairs available to sign for the owner and fee payer. These may be missing when the
+    /// transactions are only written out via `--output-tx`.
+    signers: Vec<Keypair>,
+    /// File the prepared transactions are written to instead of being submitted.
+    output_tx: Option<String>,
+    /// Durable nonce accounts, one per transaction in order, used instead of a recent blockhash
+    /// so that transactions can be signed offline without expiring.
+    nonce_accounts: Vec<Pubkey>,
+    /// Authority of all of the nonce accounts.
+    nonce_authority: Pubkey,
     commitment_config: CommitmentConfig,
 }
 
+/// Instructions that have to be executed together in a single transaction, along with the
+/// single-use keypairs (such as new message or signature set accounts) that must sign it next to
+/// the owner and fee payer.
+struct PendingTransaction {
+    instructions: Vec<Instruction>,
+    ephemeral_signers: Vec<Rc<Keypair>>,
+}
+
 type Error = Box<dyn std::error::Error>;
-type CommmandResult = Result<Option<Transaction>, Error>;
+type CommmandResult = Result<Vec<PendingTransaction>, Error>;
 
 // [`get_recent_blockhash`] is deprecated, but devnet deployment hangs using the
 // recommended method, so allowing deprecated here. This is only the client, so
@@ -91,22 +146,24 @@ fn command_deploy_bridge(
 
     let ix = bridge::instructions::initialize(
         *bridge,
-        config.owner.pubkey(),
+        config.owner,
         message_fee,
         guardian_expiration,
         initial_guardians.as_slice(),
     )
     .unwrap();
     println!("config account: {}, ", ix.accounts[0].pubkey);
-    let mut transaction = Transaction::new_with_payer(&[ix], Some(&config.fee_payer.pubkey()));
 
-    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    let (_, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
     check_fee_payer_balance(
         config,
-        minimum_balance_for_rent_exemption + fee_calculator.calculate_fee(transaction.message()),
+        minimum_balance_for_rent_exemption
+            + fee_calculator.calculate_fee(&Message::new(&[ix.clone()], Some(&config.fee_payer))),
     )?;
-    transaction.sign(&[&config.fee_payer, &config.owner], recent_blockhash);
-    Ok(Some(transaction))
+    Ok(vec![PendingTransaction {
+        instructions: vec![ix],
+        ephemeral_signers: vec![],
+    }])
 }
 
 // [`get_recent_blockhash`] is deprecated, but devnet deployment hangs using the
@@ -124,28 +181,22 @@ fn command_post_message(
     println!("Posting a message to the wormhole");
 
     // Fetch the message fee
-    let bridge_config_account = config
-        .rpc_client
-        .get_account(&Bridge::<'_, { AccountState::Initialized }>::key(
-            None, bridge,
-        ))?;
-    let bridge_config = BridgeData::try_from_slice(bridge_config_account.data.as_slice())?;
+    let bridge_config: VersionedBridgeData = get_account_data(
+        config,
+        &Bridge::<'_, { AccountState::Initialized }>::key(None, bridge),
+    )?;
     let fee = bridge_config.config.fee;
     println!("Message fee: {} lamports", fee);
 
-    let transfer_ix = transfer(
-        &config.owner.pubkey(),
-        &FeeCollector::key(None, bridge),
-        fee,
-    );
+    let transfer_ix = transfer(&config.owner, &FeeCollector::key(None, bridge), fee);
 
     let message = Keypair::new();
     let ix = match proxy {
         Some(p) => cpi_poster::instructions::post_message(
             p,
             *bridge,
-            config.owner.pubkey(),
-            config.owner.pubkey(),
+            config.owner,
+            config.owner,
             message.pubkey(),
             nonce,
             payload,
@@ -154,8 +205,8 @@ fn command_post_message(
         .unwrap(),
         None => bridge::instructions::post_message(
             *bridge,
-            config.owner.pubkey(),
-            config.owner.pubkey(),
+            config.owner,
+            config.owner,
             message.pubkey(),
             nonce,
             payload,
@@ -163,16 +214,337 @@ fn command_post_message(
         )
         .unwrap(),
     };
-    let mut transaction =
-        Transaction::new_with_payer(&[transfer_ix, ix], Some(&config.fee_payer.pubkey()));
-
-    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
-    check_fee_payer_balance(config, fee_calculator.calculate_fee(transaction.message()))?;
-    transaction.sign(
-        &[&config.fee_payer, &config.owner, &message],
-        recent_blockhash,
+    println!("Message account: {}", message.pubkey());
+
+    let instructions = vec![transfer_ix, ix];
+    let (_, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(
+        config,
+        fee_calculator.calculate_fee(&Message::new(&instructions, Some(&config.fee_payer))),
+    )?;
+    Ok(vec![PendingTransaction {
+        instructions,
+        ephemeral_signers: vec![Rc::new(message)],
+    }])
+}
+
+/// Verifies the signatures of a VAA and posts it to the bridge. Signatures are verified in
+/// batches, so this results in several transactions that have to be executed in order.
+fn command_post_vaa(config: &Config, bridge: &Pubkey, vaa: &VAA) -> CommmandResult {
+    let posted_vaa = posted_vaa_key(bridge, vaa);
+    if get_account_data::<PostedVAAData>(config, &posted_vaa).is_ok() {
+        println!("VAA already posted at {}", posted_vaa);
+        return Ok(vec![]);
+    }
+    println!("Posting VAA to {}", posted_vaa);
+
+    let guardian_set: GuardianSetData = get_account_data(
+        config,
+        &GuardianSet::<'_, { AccountState::Initialized }>::key(
+            &GuardianSetDerivationData {
+                index: vaa.guardian_set_index,
+            },
+            bridge,
+        ),
+    )?;
+
+    let signature_set = Rc::new(Keypair::new());
+    println!("Signature set account: {}", signature_set.pubkey());
+
+    let batches = bridge::instructions::verify_signatures_batches(
+        *bridge,
+        config.owner,
+        vaa.guardian_set_index,
+        &guardian_set.keys,
+        signature_set.pubkey(),
+        vaa,
+    )
+    .map_err(|e| format!("Failed to build signature verification: {:?}", e))?;
+
+    let mut transactions: Vec<PendingTransaction> = batches
+        .into_iter()
+        .map(|instructions| PendingTransaction {
+            instructions,
+            ephemeral_signers: vec![signature_set.clone()],
+        })
+        .collect();
+    transactions.push(PendingTransaction {
+        instructions: vec![bridge::instructions::post_vaa(
+            *bridge,
+            config.owner,
+            signature_set.pubkey(),
+            vaa.clone().into(),
+        )],
+        ephemeral_signers: vec![],
+    });
+    Ok(transactions)
+}
+
+/// Posts a Core governance VAA (if not yet posted) and executes the action it carries. The action
+/// is taken from the governance header of the payload.
+fn command_submit_governance_vaa(
+    config: &Config,
+    bridge: &Pubkey,
+    vaa: &VAA,
+    spill: Option<Pubkey>,
+) -> CommmandResult {
+    let mut transactions = command_post_vaa(config, bridge, vaa)?;
+
+    let message = posted_vaa_key(bridge, vaa);
+    let emitter = Pubkey::new(&vaa.emitter_address);
+    let payload = &mut vaa.payload.as_slice();
+    let action = *vaa
+        .payload
+        .get(32)
+        .ok_or("Payload too short to be a governance message")?;
+    let governance_error =
+        |e: solitaire::SolitaireError| format!("Invalid governance payload: {:?}", e);
+
+    let ix = match action {
+        a if a == GovernancePayloadUpgrade::ACTION => {
+            let upgrade =
+                GovernancePayloadUpgrade::deserialize(payload).map_err(governance_error)?;
+            println!("Upgrading contract to {}", upgrade.new_contract);
+            bridge::instructions::upgrade_contract(
+                *bridge,
+                config.owner,
+                message,
+                emitter,
+                upgrade.new_contract,
+                spill.unwrap_or(config.owner),
+                vaa.sequence,
+            )
+        }
+        a if a == GovernancePayloadGuardianSetChange::ACTION => {
+            let change = GovernancePayloadGuardianSetChange::deserialize(payload)
+                .map_err(governance_error)?;
+            println!(
+                "Upgrading guardian set to index {} with {} guardians",
+                change.new_guardian_set_index,
+                change.new_guardian_set.len()
+            );
+            bridge::instructions::upgrade_guardian_set(
+                *bridge,
+                config.owner,
+                message,
+                emitter,
+                change.new_guardian_set_index.saturating_sub(1),
+                change.new_guardian_set_index,
+                vaa.sequence,
+            )
+        }
+        a if a == GovernancePayloadSetMessageFee::ACTION => {
+            let fee =
+                GovernancePayloadSetMessageFee::deserialize(payload).map_err(governance_error)?;
+            println!("Setting message fee to {} lamports", fee.fee);
+            bridge::instructions::set_fees(*bridge, config.owner, message, emitter, vaa.sequence)
+        }
+        a if a == GovernancePayloadTransferFees::ACTION => {
+            let transfer =
+                GovernancePayloadTransferFees::deserialize(payload).map_err(governance_error)?;
+            let recipient = Pubkey::new(&transfer.to);
+            println!(
+                "Transferring {} lamports of fees to {}",
+                transfer.amount, recipient
+            );
+            bridge::instructions::transfer_fees(
+                *bridge,
+                config.owner,
+                message,
+                emitter,
+                vaa.sequence,
+                recipient,
+            )
+        }
+        a if a == GovernancePayloadSetGuardianSetExpiration::ACTION => {
+            let expiration = GovernancePayloadSetGuardianSetExpiration::deserialize(payload)
+                .map_err(governance_error)?;
+            println!(
+                "Setting guardian set expiration time to {} seconds",
+                expiration.guardian_set_expiration_time
+            );
+            bridge::instructions::set_guardian_set_expiration(
+                *bridge,
+                config.owner,
+                message,
+                emitter,
+                vaa.sequence,
+                (expiration.expiration_time != 0).then(|| expiration.guardian_set_index),
+            )
+        }
+        a if a == GovernancePayloadSetSplFee::ACTION => {
+            let fee = GovernancePayloadSetSplFee::deserialize(payload).map_err(governance_error)?;
+            println!("Setting message fee to {} of token {}", fee.fee, fee.mint);
+            bridge::instructions::set_spl_fee(
+                *bridge,
+                config.owner,
+                message,
+                emitter,
+                vaa.sequence,
+                fee.mint,
+            )
+        }
+        a if a == GovernancePayloadTransferSplFees::ACTION => {
+            let transfer =
+                GovernancePayloadTransferSplFees::deserialize(payload).map_err(governance_error)?;
+            let recipient = Pubkey::new(&transfer.to);
+            println!(
+                "Transferring {} of token {} in fees to {}",
+                transfer.amount, transfer.mint, recipient
+            );
+            bridge::instructions::transfer_spl_fees(
+                *bridge,
+                config.owner,
+                message,
+                emitter,
+                vaa.sequence,
+                transfer.mint,
+                recipient,
+            )
+        }
+        _ => return Err(format!("Unknown governance action: {}", action).into()),
+    };
+
+    transactions.push(PendingTransaction {
+        instructions: vec![ix],
+        ephemeral_signers: vec![],
+    });
+    Ok(transactions)
+}
+
+fn command_get_bridge(config: &Config, bridge: &Pubkey) -> CommmandResult {
+    let bridge_key = Bridge::<'_, { AccountState::Initialized }>::key(None, bridge);
+    let bridge_data: VersionedBridgeData = get_account_data(config, &bridge_key)?;
+    let fee_collector = FeeCollector::key(None, bridge);
+    let fee_collector_balance = config.rpc_client.get_balance(&fee_collector)?;
+
+    println!("Bridge config: {}", bridge_key);
+    println!("  Guardian set index: {}", bridge_data.guardian_set_index);
+    println!(
+        "  Guardian set expiration time: {} seconds",
+        bridge_data.config.guardian_set_expiration_time
+    );
+    println!("  Message fee: {} lamports", bridge_data.config.fee);
+    println!("  Last lamports: {}", bridge_data.last_lamports);
+    println!(
+        "Fee collector: {} ({} SOL)",
+        fee_collector,
+        lamports_to_sol(fee_collector_balance)
+    );
+    Ok(vec![])
+}
+
+fn command_get_guardian_set(
+    config: &Config,
+    bridge: &Pubkey,
+    index: Option<u32>,
+) -> CommmandResult {
+    let index = match index {
+        Some(index) => index,
+        None => {
+            let bridge_data: VersionedBridgeData = get_account_data(
+                config,
+                &Bridge::<'_, { AccountState::Initialized }>::key(None, bridge),
+            )?;
+            bridge_data.guardian_set_index
+        }
+    };
+
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData { index },
+        bridge,
     );
-    Ok(Some(transaction))
+    let guardian_set: GuardianSetData = get_account_data(config, &guardian_set_key)?;
+
+    println!("Guardian set {}: {}", guardian_set.index, guardian_set_key);
+    println!("  Creation time: {}", guardian_set.creation_time);
+    println!("  Expiration time: {}", guardian_set.expiration_time);
+    for (i, key) in guardian_set.keys.iter().enumerate() {
+        println!("  Guardian {}: {}", i, hex::encode(key));
+    }
+    Ok(vec![])
+}
+
+fn command_get_sequence(config: &Config, bridge: &Pubkey, emitter: &Pubkey) -> CommmandResult {
+    let sequence_key = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: emitter,
+        },
+        bridge,
+    );
+
+    // The tracker is only created once the emitter posts its first message.
+    let sequence = match config
+        .rpc_client
+        .get_account_with_commitment(&sequence_key, config.commitment_config)?
+        .value
+    {
+        Some(account) => SequenceTracker::try_from_slice(&account.data)?.sequence,
+        None => 0,
+    };
+
+    println!("Sequence tracker: {}", sequence_key);
+    println!("  Next sequence: {}", sequence);
+    Ok(vec![])
+}
+
+fn command_get_message(config: &Config, account: &Pubkey) -> CommmandResult {
+    let posted: PostedVAAData = get_account_data(config, account)?;
+    println!("Message account: {}", account);
+    print_message(&posted.message);
+    Ok(vec![])
+}
+
+fn command_get_posted_vaa(config: &Config, bridge: &Pubkey, vaa: &VAA) -> CommmandResult {
+    command_get_message(config, &posted_vaa_key(bridge, vaa))
+}
+
+fn print_message(message: &MessageData) {
+    println!("  VAA version: {}", message.vaa_version);
+    println!("  Consistency level: {}", message.consistency_level);
+    println!("  VAA time: {}", message.vaa_time);
+    println!("  VAA signature account: {}", message.vaa_signature_account);
+    println!("  Submission time: {}", message.submission_time);
+    println!("  Nonce: {}", message.nonce);
+    println!("  Sequence: {}", message.sequence);
+    println!("  Emitter chain: {}", message.emitter_chain);
+    println!(
+        "  Emitter address: {}",
+        hex::encode(message.emitter_address)
+    );
+    println!("  Payload: {}", hex::encode(&message.payload));
+}
+
+fn posted_vaa_key(bridge: &Pubkey, vaa: &VAA) -> Pubkey {
+    PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        bridge,
+    )
+}
+
+fn get_account_data<T: BorshDeserialize>(config: &Config, account: &Pubkey) -> Result<T, Error> {
+    let account = config.rpc_client.get_account(account)?;
+    Ok(T::try_from_slice(account.data.as_slice())?)
+}
+
+/// Reads a VAA given either as a hex string or as a path to a file containing the VAA in binary
+/// or hex encoding.
+fn read_vaa(value: &str) -> Result<VAA, Error> {
+    let data = if Path::new(value).is_file() {
+        let contents = fs::read(value)?;
+        match std::str::from_utf8(&contents)
+            .ok()
+            .and_then(|s| hex::decode(s.trim()).ok())
+        {
+            Some(decoded) => decoded,
+            None => contents,
+        }
+    } else {
+        hex::decode(value.trim())?
+    };
+    Ok(VAA::deserialize(&data)?)
 }
 
 fn main() {
@@ -206,11 +578,12 @@ fn main() {
             Arg::with_name("owner")
                 .long("owner")
                 .value_name("KEYPAIR")
-                .validator(is_keypair)
+                .validator(is_pubkey_or_keypair)
                 .takes_value(true)
                 .help(
                     "Specify the contract payer account. \
-                     This may be a keypair file, the ASK keyword. \
+                     This may be a keypair file, the ASK keyword, \
+                     or a pubkey when used with --output-tx. \
                      Defaults to the client keypair.",
                 ),
         )
@@ -218,14 +591,57 @@ fn main() {
             Arg::with_name("fee_payer")
                 .long("fee-payer")
                 .value_name("KEYPAIR")
-                .validator(is_keypair)
+                .validator(is_pubkey_or_keypair)
                 .takes_value(true)
                 .help(
                     "Specify the fee-payer account. \
-                     This may be a keypair file, the ASK keyword. \
+                     This may be a keypair file, the ASK keyword, \
+                     or a pubkey when used with --output-tx. \
                      Defaults to the client keypair.",
                 ),
         )
+        .arg(
+            Arg::with_name("output_tx")
+                .long("output-tx")
+                .value_name("FILE")
+                .takes_value(true)
+                .global(true)
+                .help(
+                    "Write the transactions to FILE instead of submitting them, one base64 \
+                     encoded transaction per line. Transactions are only signed by single-use \
+                     accounts created by the command and have to be signed by the owner and \
+                     fee payer before the included blockhash expires, unless --nonce is used.",
+                ),
+        )
+        .arg(
+            Arg::with_name("nonce_account")
+                .long("nonce")
+                .value_name("PUBKEY")
+                .validator(is_pubkey)
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .help(
+                    "Use the durable nonce stored in this account instead of a recent blockhash, \
+                     advancing it as the first instruction of the transaction. Commands \
+                     producing several transactions need a nonce account for each, given by \
+                     repeating --nonce in the order of the transactions.",
+                ),
+        )
+        .arg(
+            Arg::with_name("nonce_authority")
+                .long("nonce-authority")
+                .value_name("KEYPAIR")
+                .validator(is_pubkey_or_keypair)
+                .takes_value(true)
+                .requires("nonce_account")
+                .help(
+                    "Specify the authority of the nonce accounts. \
+                     This may be a keypair file, the ASK keyword, \
+                     or a pubkey when used with --output-tx. \
+                     Defaults to the fee payer.",
+                ),
+        )
         .subcommand(
             SubCommand::with_name("upgrade-authority")
                 .about("Get the derived signer used for contract upgrades")
@@ -330,6 +746,77 @@ fn main() {
                         .help("CPI Proxy to use"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("post-vaa")
+                .about("Verify the signatures of a VAA and post it to the bridge")
+                .arg(bridge_arg())
+                .arg(vaa_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("submit-governance-vaa")
+                .about("Post a Core governance VAA and execute its action (contract upgrade, guardian set upgrade, fee change, fee transfer or guardian set expiration)")
+                .arg(bridge_arg())
+                .arg(vaa_arg())
+                .arg(
+                    Arg::with_name("spill")
+                        .long("spill")
+                        .value_name("SPILL")
+                        .validator(is_pubkey_or_keypair)
+                        .takes_value(true)
+                        .help("Account receiving the buffer lamports of a contract upgrade. Defaults to the owner."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("get-bridge")
+                .about("Print the bridge config")
+                .arg(bridge_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("get-guardian-set")
+                .about("Print a guardian set")
+                .arg(bridge_arg())
+                .arg(
+                    Arg::with_name("index")
+                        .validator(is_u32)
+                        .value_name("INDEX")
+                        .takes_value(true)
+                        .index(2)
+                        .help("Index of the guardian set. Defaults to the active guardian set."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("get-sequence")
+                .about("Print the next sequence number of an emitter")
+                .arg(bridge_arg())
+                .arg(
+                    Arg::with_name("emitter")
+                        .validator(is_pubkey_or_keypair)
+                        .value_name("EMITTER")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Address of the emitter"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("get-message")
+                .about("Print a posted message or VAA account")
+                .arg(
+                    Arg::with_name("account")
+                        .validator(is_pubkey_or_keypair)
+                        .value_name("ACCOUNT")
+                        .takes_value(true)
+                        .index(1)
+                        .required(true)
+                        .help("Address of the message or VAA account"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("get-posted-vaa")
+                .about("Print the account a VAA was posted to")
+                .arg(bridge_arg())
+                .arg(vaa_arg()),
+        )
         .get_matches();
 
     let config = {
@@ -348,13 +835,51 @@ fn main() {
             })
         };
 
-        let owner = keypair_of(&matches, "owner").unwrap_or_else(client_keypair);
-        let fee_payer = keypair_of(&matches, "fee_payer").unwrap_or_else(client_keypair);
+        let output_tx = value_t!(matches, "output_tx", String).ok();
+
+        // Keypairs are only required when the transactions are signed and sent by the client.
+        let mut signers = Vec::new();
+        let mut signer_or_pubkey = |name: &str| match keypair_of(&matches, name) {
+            Some(keypair) => {
+                let pubkey = keypair.pubkey();
+                signers.push(keypair);
+                pubkey
+            }
+            None => match pubkey_of(&matches, name) {
+                Some(pubkey) if output_tx.is_some() => pubkey,
+                Some(_) => {
+                    eprintln!(
+                        "A keypair is required for --{} unless --output-tx is used",
+                        name.replace('_', "-")
+                    );
+                    exit(1)
+                }
+                None => {
+                    let keypair = client_keypair();
+                    let pubkey = keypair.pubkey();
+                    signers.push(keypair);
+                    pubkey
+                }
+            },
+        };
+
+        let owner = signer_or_pubkey("owner");
+        let fee_payer = signer_or_pubkey("fee_payer");
+        let nonce_accounts = pubkeys_of(&matches, "nonce_account").unwrap_or_default();
+        let nonce_authority = if matches.is_present("nonce_authority") {
+            signer_or_pubkey("nonce_authority")
+        } else {
+            fee_payer
+        };
 
         Config {
             rpc_client: RpcClient::new(json_rpc_url),
             owner,
             fee_payer,
+            signers,
+            output_tx,
+            nonce_accounts,
+            nonce_authority,
             commitment_config: CommitmentConfig::processed(),
         }
     };
@@ -385,7 +910,7 @@ fn main() {
             let upgrade_auth = <Derive<Info<'_>, "upgrade">>::key(None, &bridge);
             println!("Upgrade Key: {}", upgrade_auth);
 
-            Ok(None)
+            Ok(vec![])
         }
         ("post-message", Some(arg_matches)) => {
             let bridge = pubkey_of(arg_matches, "bridge").unwrap();
@@ -411,27 +936,46 @@ fn main() {
                 proxy,
             )
         }
+        ("post-vaa", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            read_vaa(arg_matches.value_of("vaa").unwrap())
+                .and_then(|vaa| command_post_vaa(&config, &bridge, &vaa))
+        }
+        ("submit-governance-vaa", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let spill = pubkey_of(arg_matches, "spill");
+            read_vaa(arg_matches.value_of("vaa").unwrap())
+                .and_then(|vaa| command_submit_governance_vaa(&config, &bridge, &vaa, spill))
+        }
+        ("get-bridge", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            command_get_bridge(&config, &bridge)
+        }
+        ("get-guardian-set", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let index: Option<u32> = value_of(arg_matches, "index");
+            command_get_guardian_set(&config, &bridge, index)
+        }
+        ("get-sequence", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let emitter = pubkey_of(arg_matches, "emitter").unwrap();
+            command_get_sequence(&config, &bridge, &emitter)
+        }
+        ("get-message", Some(arg_matches)) => {
+            let account = pubkey_of(arg_matches, "account").unwrap();
+            command_get_message(&config, &account)
+        }
+        ("get-posted-vaa", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            read_vaa(arg_matches.value_of("vaa").unwrap())
+                .and_then(|vaa| command_get_posted_vaa(&config, &bridge, &vaa))
+        }
 
         _ => unreachable!(),
     }
-    .and_then(|transaction| {
-        if let Some(transaction) = transaction {
-            let signature = config
-                .rpc_client
-                .send_and_confirm_transaction_with_spinner_and_config(
-                    &transaction,
-                    config.commitment_config,
-                    RpcSendTransactionConfig {
-                        skip_preflight: true,
-                        preflight_commitment: None,
-                        encoding: None,
-                        max_retries: None,
-                        min_context_slot: None,
-                    },
-                )?;
-            println!("Signature: {}", signature);
-        }
-        Ok(())
+    .and_then(|transactions| match config.output_tx {
+        Some(ref path) => write_transactions(&config, path, transactions),
+        None => send_transactions(&config, transactions),
     })
     .map_err(|err| {
         eprintln!("{}", err);
@@ -490,11 +1034,165 @@ where
         .map_err(|e| format!("{}", e))
 }
 
+fn bridge_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("bridge")
+        .long("bridge")
+        .value_name("BRIDGE_KEY")
+        .validator(is_pubkey_or_keypair)
+        .takes_value(true)
+        .index(1)
+        .required(true)
+        .help("Specify the bridge program address")
+}
+
+fn vaa_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("vaa")
+        .value_name("VAA")
+        .takes_value(true)
+        .index(2)
+        .required(true)
+        .help("Hex encoded VAA, or a file containing the VAA in binary or hex encoding")
+}
+
+fn send_transactions(config: &Config, transactions: Vec<PendingTransaction>) -> Result<(), Error> {
+    check_nonce_usage(config, &transactions)?;
+    for (index, pending) in transactions.iter().enumerate() {
+        let nonce_account = config.nonce_accounts.get(index);
+        let mut transaction = new_transaction(config, pending, nonce_account);
+        let recent_blockhash = transaction_blockhash(config, nonce_account)?;
+        sign_transaction(config, &mut transaction, pending, recent_blockhash)?;
+        if !transaction.is_signed() {
+            return Err("Transaction is missing a signature from the owner or fee payer".into());
+        }
+
+        let signature = config
+            .rpc_client
+            .send_and_confirm_transaction_with_spinner_and_config(
+                &transaction,
+                config.commitment_config,
+                RpcSendTransactionConfig {
+                    skip_preflight: true,
+                    preflight_commitment: None,
+                    encoding: None,
+                    max_retries: None,
+                    min_context_slot: None,
+                },
+            )?;
+        println!("Signature: {}", signature);
+    }
+    Ok(())
+}
+
+fn write_transactions(
+    config: &Config,
+    path: &str,
+    transactions: Vec<PendingTransaction>,
+) -> Result<(), Error> {
+    check_nonce_usage(config, &transactions)?;
+    let recent_blockhash = transaction_blockhash(config, None)?;
+
+    let mut lines = Vec::with_capacity(transactions.len());
+    for (index, pending) in transactions.iter().enumerate() {
+        let nonce_account = config.nonce_accounts.get(index);
+        let mut transaction = new_transaction(config, pending, nonce_account);
+        let blockhash = match nonce_account {
+            Some(_) => transaction_blockhash(config, nonce_account)?,
+            None => recent_blockhash,
+        };
+        sign_transaction(config, &mut transaction, pending, blockhash)?;
+        lines.push(base64::encode(bincode::serialize(&transaction)?));
+    }
+
+    fs::write(path, lines.join("\n"))?;
+    println!("Wrote {} transaction(s) to {}", transactions.len(), path);
+    Ok(())
+}
+
+/// A durable nonce is advanced by the first transaction using it, which would invalidate any
+/// further transactions built against the same nonce, so each transaction needs its own.
+fn check_nonce_usage(config: &Config, transactions: &[PendingTransaction]) -> Result<(), Error> {
+    let nonces = config.nonce_accounts.len();
+    if nonces > 0 && nonces < transactions.len() {
+        return Err(format!(
+            "This command produces {} transactions, each needs its own --nonce but {} were given",
+            transactions.len(),
+            nonces
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Builds the transaction paid for by the fee payer, advancing `nonce_account` first if one is
+/// used.
+fn new_transaction(
+    config: &Config,
+    pending: &PendingTransaction,
+    nonce_account: Option<&Pubkey>,
+) -> Transaction {
+    let mut instructions = Vec::with_capacity(pending.instructions.len() + 1);
+    if let Some(nonce_account) = nonce_account {
+        instructions.push(advance_nonce_account(
+            nonce_account,
+            &config.nonce_authority,
+        ));
+    }
+    instructions.extend(pending.instructions.iter().cloned());
+    Transaction::new_with_payer(&instructions, Some(&config.fee_payer))
+}
+
+/// The blockhash a transaction is built against, either the durable nonce stored in
+/// `nonce_account` or a recent blockhash.
+// [`get_recent_blockhash`] is deprecated, but devnet deployment hangs using the
+// recommended method, so allowing deprecated here. This is only the client, so
+// no risk.
+#[allow(deprecated)]
+fn transaction_blockhash(config: &Config, nonce_account: Option<&Pubkey>) -> Result<Hash, Error> {
+    match nonce_account {
+        Some(nonce_account) => {
+            let account = nonce_utils::get_account_with_commitment(
+                &config.rpc_client,
+                nonce_account,
+                config.commitment_config,
+            )?;
+            Ok(nonce_utils::data_from_account(&account)?.blockhash())
+        }
+        None => Ok(config.rpc_client.get_recent_blockhash()?.0),
+    }
+}
+
+/// Signs with every available keypair that the transaction requires, leaving the remaining
+/// signatures empty.
+fn sign_transaction(
+    config: &Config,
+    transaction: &mut Transaction,
+    pending: &PendingTransaction,
+    recent_blockhash: Hash,
+) -> Result<(), Error> {
+    let required_signers = &transaction.message.account_keys
+        [..transaction.message.header.num_required_signatures as usize];
+
+    let mut keypairs: Vec<&Keypair> = Vec::new();
+    let available = config
+        .signers
+        .iter()
+        .chain(pending.ephemeral_signers.iter().map(|k| k.as_ref()));
+    for keypair in available {
+        let pubkey = keypair.pubkey();
+        if required_signers.contains(&pubkey) && !keypairs.iter().any(|k| k.pubkey() == pubkey) {
+            keypairs.push(keypair);
+        }
+    }
+
+    transaction.try_partial_sign(&keypairs, recent_blockhash)?;
+    Ok(())
+}
+
 fn check_fee_payer_balance(config: &Config, required_balance: u64) -> Result<(), Error> {
     let balance = config
         .rpc_client
         .get_balance_with_commitment(
-            &config.fee_payer.pubkey(),
+            &config.fee_payer,
             CommitmentConfig {
                 commitment: CommitmentLevel::Processed,
             },
@@ -503,7 +1201,7 @@ fn check_fee_payer_balance(config: &Config, required_balance: u64) -> Result<(),
     if balance < required_balance {
         Err(format!(
             "Fee payer, {}, has insufficient balance: {} required, {} available",
-            config.fee_payer.pubkey(),
+            config.fee_payer,
             lamports_to_sol(required_balance),
             lamports_to_sol(balance)
         )
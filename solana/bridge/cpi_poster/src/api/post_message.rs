@@ -30,7 +30,7 @@ pub struct PostMessage<'b> {
     pub bridge_program: Info<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize)]
 pub struct PostMessageData {
     /// Unique nonce for this message
     pub nonce: u32,
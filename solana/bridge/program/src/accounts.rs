@@ -6,6 +6,7 @@ pub mod posted_message;
 pub mod posted_vaa;
 pub mod sequence;
 pub mod signature_set;
+pub mod spl_fee;
 
 pub use self::{
     bridge::*,
@@ -16,4 +17,5 @@ pub use self::{
     posted_vaa::*,
     sequence::*,
     signature_set::*,
+    spl_fee::*,
 };
@@ -14,10 +14,17 @@ use solitaire::{
     AccountState,
     Data,
     Derive,
+    Migrate,
     Owned,
+    Versioned,
 };
 
-pub type Bridge<'a, const State: AccountState> = Derive<Data<'a, BridgeData, { State }>, "Bridge">;
+pub type Bridge<'a, const State: AccountState> =
+    Derive<Data<'a, VersionedBridgeData, { State }>, "Bridge">;
+
+/// Bridge state as stored in its account, at the layout it was last migrated to. Fields are only
+/// ever appended to `BridgeData`, so the account keeps the layout other programs and clients read.
+pub type VersionedBridgeData = Versioned<BridgeData>;
 
 #[derive(Clone, Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct BridgeData {
@@ -31,6 +38,15 @@ pub struct BridgeData {
     pub config: BridgeConfig,
 }
 
+impl Migrate for BridgeData {
+    const VERSION: u8 = 0;
+    type Previous = Self;
+
+    fn migrate(previous: Self) -> Self {
+        previous
+    }
+}
+
 #[cfg(not(feature = "cpi"))]
 impl Owned for BridgeData {
     fn owner(&self) -> AccountOwner {
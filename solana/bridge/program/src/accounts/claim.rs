@@ -66,18 +66,18 @@ where
     claim.verify_derivation(
         ctx.program_id,
         &ClaimDerivationData {
-            emitter_address: message.meta().emitter_address,
-            emitter_chain: message.meta().emitter_chain,
-            sequence: message.meta().sequence,
+            emitter_address: message.meta().emitter_address(),
+            emitter_chain: message.meta().emitter_chain(),
+            sequence: message.meta().sequence(),
         },
     )?;
 
     // Claim the account by initializing it with a value.
     claim.create(
         &ClaimDerivationData {
-            emitter_address: message.meta().emitter_address,
-            emitter_chain: message.meta().emitter_chain,
-            sequence: message.meta().sequence,
+            emitter_address: message.meta().emitter_address(),
+            emitter_chain: message.meta().emitter_chain(),
+            sequence: message.meta().sequence(),
         },
         ctx,
         payer,
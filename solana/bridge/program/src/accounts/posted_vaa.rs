@@ -3,14 +3,22 @@ use borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
+use solana_program::{
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
 use solitaire::{
     processors::seeded::Seeded,
     AccountOwner,
     AccountState,
     Data,
     Owned,
+    Result,
+    ZeroCopy,
 };
 use std::{
+    cell::Ref,
+    convert::TryInto,
     io::{
         Error,
         ErrorKind::InvalidData,
@@ -102,3 +110,86 @@ impl Owned for PostedVAAData {
         AccountOwner::Other(Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap())
     }
 }
+
+/// Offsets of the fixed size fields of a Borsh serialized `PostedVAAData`, following the magic.
+mod offset {
+    pub const VAA_VERSION: usize = 3;
+    pub const CONSISTENCY_LEVEL: usize = 4;
+    pub const VAA_TIME: usize = 5;
+    pub const VAA_SIGNATURE_ACCOUNT: usize = 9;
+    pub const SUBMISSION_TIME: usize = 41;
+    pub const NONCE: usize = 45;
+    pub const SEQUENCE: usize = 49;
+    pub const EMITTER_CHAIN: usize = 57;
+    pub const EMITTER_ADDRESS: usize = 59;
+    pub const PAYLOAD_LEN: usize = 91;
+    pub const PAYLOAD: usize = 95;
+}
+
+impl ZeroCopy for PostedVAAData {
+    fn validate(data: &[u8]) -> Result<()> {
+        if data.len() < offset::PAYLOAD || ![&b"vaa"[..], b"msg", b"msu"].contains(&&data[..3]) {
+            return Err(ProgramError::InvalidAccountData.into());
+        }
+
+        // Like deserializing, refuse trailing bytes after the payload.
+        let len = u32::from_le_bytes(
+            data[offset::PAYLOAD_LEN..offset::PAYLOAD]
+                .try_into()
+                .unwrap(),
+        );
+        if data.len() - offset::PAYLOAD != len as usize {
+            return Err(ProgramError::InvalidAccountData.into());
+        }
+        Ok(())
+    }
+}
+
+/// `PostedVAAData` read in place from a validated account, see [`ZeroCopy`].
+pub struct PostedVAARef<'a>(pub Ref<'a, [u8]>);
+
+impl<'a> PostedVAARef<'a> {
+    fn bytes<const N: usize>(&self, at: usize) -> [u8; N] {
+        self.0[at..at + N].try_into().unwrap()
+    }
+
+    pub fn vaa_version(&self) -> u8 {
+        self.0[offset::VAA_VERSION]
+    }
+
+    pub fn consistency_level(&self) -> u8 {
+        self.0[offset::CONSISTENCY_LEVEL]
+    }
+
+    pub fn vaa_time(&self) -> u32 {
+        u32::from_le_bytes(self.bytes(offset::VAA_TIME))
+    }
+
+    pub fn vaa_signature_account(&self) -> Pubkey {
+        Pubkey::new_from_array(self.bytes(offset::VAA_SIGNATURE_ACCOUNT))
+    }
+
+    pub fn submission_time(&self) -> u32 {
+        u32::from_le_bytes(self.bytes(offset::SUBMISSION_TIME))
+    }
+
+    pub fn nonce(&self) -> u32 {
+        u32::from_le_bytes(self.bytes(offset::NONCE))
+    }
+
+    pub fn sequence(&self) -> u64 {
+        u64::from_le_bytes(self.bytes(offset::SEQUENCE))
+    }
+
+    pub fn emitter_chain(&self) -> u16 {
+        u16::from_le_bytes(self.bytes(offset::EMITTER_CHAIN))
+    }
+
+    pub fn emitter_address(&self) -> [u8; 32] {
+        self.bytes(offset::EMITTER_ADDRESS)
+    }
+
+    pub fn payload(&self) -> &[u8] {
+        &self.0[offset::PAYLOAD..]
+    }
+}
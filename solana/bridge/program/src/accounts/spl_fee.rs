@@ -0,0 +1,65 @@
+//! Message fees can alternatively be paid in an SPL token chosen by governance. Token fees are
+//! collected in a custody account per mint, owned by the FeeCollector.
+
+use crate::types::SplAccount;
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Derive,
+    Owned,
+};
+
+pub type SplFeeConfig<'a, const State: AccountState> =
+    Derive<Data<'a, SplFeeConfigData, { State }>, "SplFeeConfig">;
+
+#[derive(Clone, Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct SplFeeConfigData {
+    /// Mint of the token message fees can be paid in.
+    pub mint: Pubkey,
+
+    /// Amount of tokens that needs to be paid to post a message, 0 disables token fees.
+    pub fee: u64,
+
+    /// Tokens in the custody account of `mint`, the token equivalent of `last_lamports`.
+    pub last_balance: u64,
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for SplFeeConfigData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for SplFeeConfigData {
+    fn owner(&self) -> AccountOwner {
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap())
+    }
+}
+
+pub type SplFeeCustody<'a, const State: AccountState> = Data<'a, SplAccount, { State }>;
+
+pub struct SplFeeCustodyDerivationData {
+    pub mint: Pubkey,
+}
+
+impl<'a, const State: AccountState> Seeded<&SplFeeCustodyDerivationData>
+    for SplFeeCustody<'a, { State }>
+{
+    fn seeds(data: &SplFeeCustodyDerivationData) -> Vec<Vec<u8>> {
+        vec![b"fee_custody".to_vec(), data.mint.to_bytes().to_vec()]
+    }
+}
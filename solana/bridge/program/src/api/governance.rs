@@ -4,6 +4,7 @@ use solana_program::{
     sysvar::{
         clock::Clock,
         rent::Rent,
+        Sysvar as _,
     },
 };
 use solitaire::{
@@ -19,24 +20,36 @@ use crate::{
             Claim,
         },
         Bridge,
+        FeeCollector,
         GuardianSet,
         GuardianSetDerivationData,
+        SplFeeConfig,
+        SplFeeCustody,
+        SplFeeCustodyDerivationData,
     },
     error::Error::{
         InvalidFeeRecipient,
         InvalidGovernanceKey,
         InvalidGovernanceWithdrawal,
+        InvalidGuardianSetExpiration,
         InvalidGuardianSetUpgrade,
+        InvalidSplFeeMint,
+        TooManyGuardians,
     },
     types::{
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetGuardianSetExpiration,
         GovernancePayloadSetMessageFee,
+        GovernancePayloadSetSplFee,
         GovernancePayloadTransferFees,
+        GovernancePayloadTransferSplFees,
         GovernancePayloadUpgrade,
+        SplMint,
     },
     DeserializePayload,
     PayloadMessage,
     CHAIN_ID_GOVERANCE,
+    MAX_LEN_GUARDIAN_KEYS,
 };
 
 /// Fail if the emitter is not the known governance key, or the emitting chain is not Solana.
@@ -45,8 +58,8 @@ where
     T: DeserializePayload,
 {
     let expected_emitter = std::env!("EMITTER_ADDRESS");
-    let current_emitter = format!("{}", Pubkey::new_from_array(vaa.meta().emitter_address));
-    if expected_emitter != current_emitter || vaa.meta().emitter_chain != CHAIN_ID_GOVERANCE {
+    let current_emitter = format!("{}", Pubkey::new_from_array(vaa.meta().emitter_address()));
+    if expected_emitter != current_emitter || vaa.meta().emitter_chain() != CHAIN_ID_GOVERANCE {
         Err(InvalidGovernanceKey.into())
     } else {
         Ok(())
@@ -89,7 +102,7 @@ pub struct UpgradeContract<'b> {
     pub system: Info<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct UpgradeContractData {}
 
 pub fn upgrade_contract(
@@ -138,7 +151,7 @@ pub struct UpgradeGuardianSet<'b> {
     pub guardian_set_new: Mut<GuardianSet<'b, { AccountState::Uninitialized }>>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct UpgradeGuardianSetData {}
 
 pub fn upgrade_guardian_set(
@@ -149,6 +162,10 @@ pub fn upgrade_guardian_set(
     verify_governance(&accs.vaa)?;
     claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
 
+    if accs.vaa.new_guardian_set.len() > MAX_LEN_GUARDIAN_KEYS {
+        return Err(TooManyGuardians.into());
+    }
+
     // Enforce single increments when upgrading.
     if accs.guardian_set_old.index != accs.vaa.new_guardian_set_index - 1 {
         return Err(InvalidGuardianSetUpgrade.into());
@@ -174,11 +191,11 @@ pub fn upgrade_guardian_set(
 
     // Set expiration time for the old set
     accs.guardian_set_old.expiration_time =
-        accs.vaa.meta().vaa_time + accs.bridge.config.guardian_set_expiration_time;
+        accs.vaa.meta().vaa_time() + accs.bridge.config.guardian_set_expiration_time;
 
     // Initialize new guardian Set
     accs.guardian_set_new.index = accs.vaa.new_guardian_set_index;
-    accs.guardian_set_new.creation_time = accs.vaa.meta().vaa_time;
+    accs.guardian_set_new.creation_time = accs.vaa.meta().vaa_time();
     accs.guardian_set_new.keys = accs.vaa.new_guardian_set.clone();
 
     // Create new guardian set
@@ -213,7 +230,7 @@ pub struct SetFees<'b> {
     pub claim: Mut<Claim<'b>>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct SetFeesData {}
 
 pub fn set_fees(ctx: &ExecutionContext, accs: &mut SetFees, _data: SetFeesData) -> Result<()> {
@@ -247,7 +264,7 @@ pub struct TransferFees<'b> {
     pub rent: Sysvar<'b, Rent>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct TransferFeesData {}
 
 pub fn transfer_fees(
@@ -287,3 +304,212 @@ pub fn transfer_fees(
 
     Ok(())
 }
+
+#[derive(FromAccounts)]
+pub struct SetGuardianSetExpiration<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetGuardianSetExpiration>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Guardian set referenced by the VAA, only read when the VAA overrides its expiration and
+    /// left out (the zero key) otherwise
+    pub guardian_set: Option<Mut<GuardianSet<'b, { AccountState::Initialized }>>>,
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct SetGuardianSetExpirationData {}
+
+pub fn set_guardian_set_expiration(
+    ctx: &ExecutionContext,
+    accs: &mut SetGuardianSetExpiration,
+    _data: SetGuardianSetExpirationData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    // Applies to guardian sets replaced by future upgrades.
+    accs.bridge.config.guardian_set_expiration_time = accs.vaa.guardian_set_expiration_time;
+
+    // Override the expiration of an already replaced set, a timestamp in the past expires it
+    // immediately. The active guardian set can never be expired this way.
+    if accs.vaa.expiration_time != 0 {
+        if accs.vaa.guardian_set_index >= accs.bridge.guardian_set_index {
+            return Err(InvalidGuardianSetExpiration.into());
+        }
+
+        let guardian_set = accs
+            .guardian_set
+            .as_mut()
+            .ok_or(InvalidGuardianSetExpiration)?;
+        guardian_set.verify_derivation(
+            ctx.program_id,
+            &GuardianSetDerivationData {
+                index: accs.vaa.guardian_set_index,
+            },
+        )?;
+
+        // Sets can be cut short but never extended, which would revive a set that was rotated
+        // out, possibly because it was compromised.
+        let now = Clock::get()?.unix_timestamp as u32;
+        if accs.vaa.expiration_time > now.max(guardian_set.expiration_time) {
+            return Err(InvalidGuardianSetExpiration.into());
+        }
+        guardian_set.expiration_time = accs.vaa.expiration_time;
+    }
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetSplFee<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetSplFee>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Token fee configuration, created by the first SetSplFee VAA.
+    pub spl_fee_config: Mut<SplFeeConfig<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Mint of the token fees are paid in.
+    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+
+    /// Token account collecting fees for `mint`, owned by the fee collector.
+    pub fee_custody: Mut<SplFeeCustody<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Account collecting tx fees, the authority of all fee custody accounts.
+    pub fee_collector: FeeCollector<'b>,
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct SetSplFeeData {}
+
+pub fn set_spl_fee(
+    ctx: &ExecutionContext,
+    accs: &mut SetSplFee,
+    _data: SetSplFeeData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    if accs.vaa.mint != *accs.mint.info().key {
+        return Err(InvalidSplFeeMint.into());
+    }
+
+    let derivation_data = SplFeeCustodyDerivationData {
+        mint: accs.vaa.mint,
+    };
+    accs.fee_custody
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if !accs.fee_custody.is_initialized() {
+        accs.fee_custody
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+
+        let init_ix = spl_token::instruction::initialize_account(
+            &spl_token::id(),
+            accs.fee_custody.info().key,
+            accs.mint.info().key,
+            accs.fee_collector.key,
+        )?;
+        invoke_signed(&init_ix, ctx.accounts, &[])?;
+    }
+
+    if !accs.spl_fee_config.is_initialized() {
+        accs.spl_fee_config.create(ctx, accs.payer.key, Exempt)?;
+    }
+
+    // Tokens sitting in a newly selected custody account were not paid as fees.
+    if accs.spl_fee_config.mint != accs.vaa.mint {
+        accs.spl_fee_config.mint = accs.vaa.mint;
+        accs.spl_fee_config.last_balance = accs.fee_custody.amount;
+    }
+    accs.spl_fee_config.fee = accs.vaa.fee.as_u64();
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct TransferSplFees<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadTransferSplFees>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Token fee configuration
+    pub spl_fee_config: Mut<SplFeeConfig<'b, { AccountState::Initialized }>>,
+
+    /// Token account holding the fees of the mint in the VAA.
+    pub fee_custody: Mut<SplFeeCustody<'b, { AccountState::Initialized }>>,
+
+    /// Account collecting tx fees, the authority of all fee custody accounts.
+    pub fee_collector: FeeCollector<'b>,
+
+    /// Fee recipient token account
+    pub recipient: Mut<Info<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct TransferSplFeesData {}
+
+pub fn transfer_spl_fees(
+    ctx: &ExecutionContext,
+    accs: &mut TransferSplFees,
+    _data: TransferSplFeesData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    // Make sure the account loaded to receive funds is equal to the one the VAA requested.
+    if accs.vaa.to != accs.recipient.key.to_bytes() {
+        return Err(InvalidFeeRecipient.into());
+    }
+
+    accs.fee_custody.verify_derivation(
+        ctx.program_id,
+        &SplFeeCustodyDerivationData {
+            mint: accs.vaa.mint,
+        },
+    )?;
+
+    let amount = accs.vaa.amount.as_u64();
+    if amount > accs.fee_custody.amount {
+        return Err(InvalidGovernanceWithdrawal.into());
+    }
+
+    // Custody accounts of previously configured mints are not tracked anymore.
+    if accs.spl_fee_config.mint == accs.vaa.mint {
+        accs.spl_fee_config.last_balance = accs.fee_custody.amount - amount;
+    }
+
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        accs.fee_custody.info().key,
+        accs.recipient.key,
+        accs.fee_collector.key,
+        &[],
+        amount,
+    )?;
+
+    let seeds = accs.fee_collector.self_bumped_seeds(None, ctx.program_id);
+    let seeds: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+    let seeds = seeds.as_slice();
+    invoke_signed(&transfer_ix, ctx.accounts, &[seeds])?;
+
+    Ok(())
+}
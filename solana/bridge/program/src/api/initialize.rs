@@ -35,7 +35,7 @@ pub struct Initialize<'b> {
     pub clock: Sysvar<'b, Clock>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct InitializeData {
     /// Period for how long a guardian set is valid after it has been replaced by a new one.  This
     /// guarantees that VAAs issued by that set can still be submitted for a certain period.  In
@@ -6,12 +6,16 @@ use crate::{
         PostedMessageUnreliable,
         Sequence,
         SequenceDerivationData,
+        SplFeeConfig,
+        SplFeeCustody,
+        SplFeeCustodyDerivationData,
     },
     error::Error::{
         EmitterChanged,
         InsufficientFees,
         InvalidPayloadLength,
         MathOverflow,
+        SplFeeNotEnabled,
     },
     types::ConsistencyLevel,
     IsSigned::*,
@@ -78,7 +82,30 @@ pub struct PostMessageUnreliable<'b> {
     pub clock: Sysvar<'b, Clock>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(FromAccounts)]
+pub struct PostMessageSplFee<'b> {
+    /// Token fee configuration, tracking the custody balance.
+    pub spl_fee_config: Mut<SplFeeConfig<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted message
+    pub message: Signer<Mut<UninitializedMessage<'b>>>,
+
+    /// Emitter of the VAA
+    pub emitter: Signer<MaybeMut<Info<'b>>>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Sequence<'b>>,
+
+    /// Payer for account creation
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Token account collecting the fee, derived from the configured mint.
+    pub fee_custody: SplFeeCustody<'b, { AccountState::Initialized }>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize)]
 pub struct PostMessageData {
     /// Unique nonce for this message
     pub nonce: u32,
@@ -95,15 +122,14 @@ pub fn post_message(
     accs: &mut PostMessage,
     data: PostMessageData,
 ) -> Result<()> {
+    collect_fee(&mut accs.bridge, &accs.fee_collector)?;
     post_message_internal(
         ctx,
-        &mut accs.bridge,
         accs.message.info().key,
         &mut accs.message,
         &mut accs.emitter,
         &mut accs.sequence,
         &mut accs.payer,
-        &mut accs.fee_collector,
         &mut accs.clock,
         data,
     )?;
@@ -144,15 +170,14 @@ pub fn post_message_unreliable(
         return Err(EmitterChanged.into());
     }
 
+    collect_fee(&mut accs.bridge, &accs.fee_collector)?;
     post_message_internal(
         ctx,
-        &mut accs.bridge,
         accs.message.info().key,
         &mut accs.message,
         &mut accs.emitter,
         &mut accs.sequence,
         &mut accs.payer,
-        &mut accs.fee_collector,
         &mut accs.clock,
         data,
     )?;
@@ -174,28 +199,75 @@ pub fn post_message_unreliable(
     Ok(())
 }
 
-#[allow(clippy::too_many_arguments)]
-fn post_message_internal<'b>(
+/// Post a message paying the fee in the SPL token configured by governance instead of lamports.
+/// The fee has to be transferred into the custody account of that token beforehand, usually
+/// earlier in the same transaction.
+pub fn post_message_spl_fee(
     ctx: &ExecutionContext,
-    bridge: &mut Mut<Bridge<'b, { AccountState::Initialized }>>,
-    #[cfg_attr(not(feature = "trace"), allow(unused_variables))] message_key: &Pubkey,
-    message: &mut MessageData,
-    emitter: &mut Signer<MaybeMut<Info<'b>>>,
-    sequence: &mut Mut<Sequence<'b>>,
-    payer: &mut Mut<Signer<Info<'b>>>,
-    fee_collector: &mut Mut<FeeCollector<'b>>,
-    clock: &mut Sysvar<'b, Clock>,
+    accs: &mut PostMessageSplFee,
     data: PostMessageData,
 ) -> Result<()> {
-    trace!("Message Address: {}", message_key);
-    trace!("Emitter Address: {}", emitter.info().key);
-    trace!("Nonce: {}", data.nonce);
+    accs.fee_custody.verify_derivation(
+        ctx.program_id,
+        &SplFeeCustodyDerivationData {
+            mint: accs.spl_fee_config.mint,
+        },
+    )?;
 
-    let sequence_derivation = SequenceDerivationData {
-        emitter_key: emitter.key,
-    };
-    sequence.verify_derivation(ctx.program_id, &sequence_derivation)?;
+    // A zero fee means paying in tokens is disabled rather than free.
+    let fee = accs.spl_fee_config.fee;
+    if fee == 0 {
+        return Err(SplFeeNotEnabled.into());
+    }
 
+    // Same as for lamports, only the balance change since the last message is checked.
+    if accs
+        .fee_custody
+        .amount
+        .checked_sub(accs.spl_fee_config.last_balance)
+        .ok_or(MathOverflow)?
+        < fee
+    {
+        trace!(
+            "Expected fee not found: fee, last_balance, custody: {} {} {}",
+            fee,
+            accs.spl_fee_config.last_balance,
+            accs.fee_custody.amount,
+        );
+        return Err(InsufficientFees.into());
+    }
+    accs.spl_fee_config.last_balance = accs.fee_custody.amount;
+
+    post_message_internal(
+        ctx,
+        accs.message.info().key,
+        &mut accs.message,
+        &mut accs.emitter,
+        &mut accs.sequence,
+        &mut accs.payer,
+        &mut accs.clock,
+        data,
+    )?;
+
+    // Create message account
+    let size = accs.message.size();
+    create_account(
+        ctx,
+        accs.message.info(),
+        accs.payer.key,
+        Exempt,
+        size,
+        ctx.program_id,
+        NotSigned,
+    )?;
+
+    Ok(())
+}
+
+fn collect_fee<'b>(
+    bridge: &mut Mut<Bridge<'b, { AccountState::Initialized }>>,
+    fee_collector: &Mut<FeeCollector<'b>>,
+) -> Result<()> {
     let fee = bridge.config.fee;
     // Fee handling, checking previously known balance allows us to not care who is the payer of
     // this submission.
@@ -215,6 +287,29 @@ fn post_message_internal<'b>(
     }
     bridge.last_lamports = fee_collector.lamports();
 
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn post_message_internal<'b>(
+    ctx: &ExecutionContext,
+    #[cfg_attr(not(feature = "trace"), allow(unused_variables))] message_key: &Pubkey,
+    message: &mut MessageData,
+    emitter: &mut Signer<MaybeMut<Info<'b>>>,
+    sequence: &mut Mut<Sequence<'b>>,
+    payer: &mut Mut<Signer<Info<'b>>>,
+    clock: &mut Sysvar<'b, Clock>,
+    data: PostMessageData,
+) -> Result<()> {
+    trace!("Message Address: {}", message_key);
+    trace!("Emitter Address: {}", emitter.info().key);
+    trace!("Nonce: {}", data.nonce);
+
+    let sequence_derivation = SequenceDerivationData {
+        emitter_key: emitter.key,
+    };
+    sequence.verify_derivation(ctx.program_id, &sequence_derivation)?;
+
     // Init sequence tracker if it does not exist yet.
     if !sequence.is_initialized() {
         trace!("Initializing Sequence account to 0.");
@@ -2,6 +2,7 @@ use solitaire::*;
 
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 use solana_program::{
@@ -83,7 +84,7 @@ pub struct Signature {
 
 pub type ForeignAddress = [u8; 32];
 
-#[derive(Default, BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize)]
+#[derive(Default, BorshSchema, BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize)]
 pub struct PostVAAData {
     // Header part
     pub version: u8,
@@ -7,12 +7,13 @@ use crate::{
         InstructionAtWrongIndex,
         InvalidHash,
         InvalidSecpInstruction,
+        TooManyGuardians,
     },
     GuardianSet,
     GuardianSetDerivationData,
     IsSigned::*,
     SignatureSet,
-    MAX_LEN_GUARDIAN_KEYS,
+    MAX_LEN_GUARDIAN_KEYS_V1,
 };
 use byteorder::ByteOrder;
 use solana_program::program_error::ProgramError;
@@ -44,10 +45,19 @@ impl From<&VerifySignatures<'_>> for GuardianSetDerivationData {
     }
 }
 
-#[derive(Default, BorshSerialize, BorshDeserialize)]
+#[derive(Default, BorshSchema, BorshSerialize, BorshDeserialize)]
 pub struct VerifySignaturesData {
     /// instruction indices of signers (-1 for missing)
-    pub signers: [i8; MAX_LEN_GUARDIAN_KEYS],
+    pub signers: [i8; MAX_LEN_GUARDIAN_KEYS_V1],
+}
+
+/// Variable length version of `VerifySignaturesData`, required for guardian sets larger than
+/// `MAX_LEN_GUARDIAN_KEYS_V1`.
+#[derive(Default, BorshSchema, BorshSerialize, BorshDeserialize)]
+pub struct VerifySignaturesV2Data {
+    /// instruction indices of signers (-1 for missing), indexed by guardian. May be shorter than
+    /// the guardian set, missing trailing entries are treated as -1.
+    pub signers: Vec<i8>,
 }
 
 /// SigInfo contains metadata about signers in a VerifySignature ix
@@ -68,12 +78,32 @@ pub fn verify_signatures(
     ctx: &ExecutionContext,
     accs: &mut VerifySignatures,
     data: VerifySignaturesData,
+) -> Result<()> {
+    verify_signatures_internal(ctx, accs, &data.signers)
+}
+
+pub fn verify_signatures_v2(
+    ctx: &ExecutionContext,
+    accs: &mut VerifySignatures,
+    data: VerifySignaturesV2Data,
+) -> Result<()> {
+    verify_signatures_internal(ctx, accs, &data.signers)
+}
+
+fn verify_signatures_internal(
+    ctx: &ExecutionContext,
+    accs: &mut VerifySignatures,
+    signers: &[i8],
 ) -> Result<()> {
     accs.guardian_set
         .verify_derivation(ctx.program_id, &(&*accs).into())?;
 
-    let sig_infos: Vec<SigInfo> = data
-        .signers
+    // The signer map is indexed by guardian, so it can never be longer than the set itself.
+    if signers.len() > accs.guardian_set.keys.len().max(MAX_LEN_GUARDIAN_KEYS_V1) {
+        return Err(TooManyGuardians.into());
+    }
+
+    let sig_infos: Vec<SigInfo> = signers
         .iter()
         .enumerate()
         .filter_map(|(i, p)| {
@@ -196,7 +226,7 @@ pub fn verify_signatures(
 
     // Write sigs of checked addresses into sig_state
     for s in sig_infos {
-        if s.signer_index > accs.guardian_set.num_guardians() {
+        if s.signer_index as usize >= accs.guardian_set.keys.len() {
             return Err(ProgramError::InvalidArgument.into());
         }
 
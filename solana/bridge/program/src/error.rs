@@ -1,10 +1,10 @@
 //! Define application level errors that can be returned by the various instruction handlers that
 //! make up the wormhole bridge.
 
-use crate::trace;
-use solitaire::SolitaireError;
+use solitaire::ErrorCode;
 
-#[derive(Debug)]
+#[derive(Debug, ErrorCode)]
+#[error_code(offset = 1000)]
 pub enum Error {
     GuardianSetMismatch,
     InstructionAtWrongIndex,
@@ -26,13 +26,7 @@ pub enum Error {
     VAAInvalid,
     InvalidPayloadLength,
     EmitterChanged,
-}
-
-/// Errors thrown by the program will bubble up to the solitaire wrapper, which needs a way to
-/// translate these errors into something Solitaire can log and handle.
-impl From<Error> for SolitaireError {
-    fn from(e: Error) -> SolitaireError {
-        trace!("ProgramError: {:?}", e);
-        SolitaireError::Custom(e as u64)
-    }
+    InvalidGuardianSetExpiration,
+    SplFeeNotEnabled,
+    InvalidSplFeeMint,
 }
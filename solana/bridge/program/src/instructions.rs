@@ -1,15 +1,16 @@
-use borsh::BorshSerialize;
 use solana_program::{
     instruction::{
         AccountMeta,
         Instruction,
     },
+    program_error::ProgramError,
     pubkey::Pubkey,
     sysvar,
 };
 
 use byteorder::{
     BigEndian,
+    LittleEndian,
     WriteBytesExt,
 };
 use sha3::Digest;
@@ -24,27 +25,51 @@ use std::io::{
 
 use crate::{
     accounts::{
-        Bridge,
         Claim,
         ClaimDerivationData,
-        FeeCollector,
         GuardianSet,
         GuardianSetDerivationData,
         PostedVAA,
         PostedVAADerivationData,
         Sequence,
         SequenceDerivationData,
+        SplFeeCustody,
+        SplFeeCustodyDerivationData,
+    },
+    api::{
+        InitializeAccounts,
+        PostMessageAccounts,
+        PostMessageSplFeeAccounts,
+        PostMessageUnreliableAccounts,
+        PostVAAAccounts,
+        SetFeesAccounts,
+        SetGuardianSetExpirationAccounts,
+        SetSplFeeAccounts,
+        TransferFeesAccounts,
+        TransferSplFeesAccounts,
+        UpgradeContractAccounts,
+        UpgradeGuardianSetAccounts,
+        VerifySignaturesAccounts,
     },
     types::ConsistencyLevel,
+    vaa::{
+        SignatureItem,
+        VAA,
+    },
     InitializeData,
     PostMessageData,
     PostVAAData,
     SetFeesData,
+    SetGuardianSetExpirationData,
+    SetSplFeeData,
     TransferFeesData,
+    TransferSplFeesData,
     UpgradeContractData,
     UpgradeGuardianSetData,
     VerifySignaturesData,
+    VerifySignaturesV2Data,
     CHAIN_ID_GOVERANCE,
+    MAX_LEN_GUARDIAN_KEYS_V1,
 };
 
 pub fn initialize(
@@ -54,34 +79,33 @@ pub fn initialize(
     guardian_set_expiration_time: u32,
     initial_guardians: &[[u8; 20]],
 ) -> solitaire::Result<Instruction> {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
     let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
         &GuardianSetDerivationData { index: 0 },
         &program_id,
     );
-    let fee_collector = FeeCollector::key(None, &program_id);
 
-    Ok(Instruction {
+    InitializeAccounts {
+        bridge: (),
+        guardian_set,
+        fee_collector: (),
+        payer,
+        clock: (),
+    }
+    .instruction(
         program_id,
-        accounts: vec![
-            AccountMeta::new(bridge, false),
-            AccountMeta::new(guardian_set, false),
-            AccountMeta::new(fee_collector, false),
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
-        data: (
+        (
             crate::instruction::Instruction::Initialize,
             InitializeData {
                 initial_guardians: initial_guardians.to_vec(),
                 fee,
                 guardian_set_expiration_time,
             },
-        )
-            .try_to_vec()?,
-    })
+        ),
+        &[
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+    )
 }
 
 pub fn post_message(
@@ -93,8 +117,6 @@ pub fn post_message(
     payload: Vec<u8>,
     commitment: ConsistencyLevel,
 ) -> solitaire::Result<Instruction> {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let fee_collector = FeeCollector::<'_>::key(None, &program_id);
     let sequence = Sequence::<'_>::key(
         &SequenceDerivationData {
             emitter_key: &emitter,
@@ -102,29 +124,30 @@ pub fn post_message(
         &program_id,
     );
 
-    Ok(Instruction {
+    PostMessageAccounts {
+        bridge: (),
+        message,
+        emitter,
+        sequence,
+        payer,
+        fee_collector: (),
+        clock: (),
+    }
+    .instruction(
         program_id,
-        accounts: vec![
-            AccountMeta::new(bridge, false),
-            AccountMeta::new(message, true),
-            AccountMeta::new_readonly(emitter, true),
-            AccountMeta::new(sequence, false),
-            AccountMeta::new(payer, true),
-            AccountMeta::new(fee_collector, false),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
-        data: (
+        (
             crate::instruction::Instruction::PostMessage,
             PostMessageData {
                 nonce,
                 payload,
                 consistency_level: commitment,
             },
-        )
-            .try_to_vec()?,
-    })
+        ),
+        &[
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+    )
 }
 
 pub fn post_message_unreliable(
@@ -136,8 +159,6 @@ pub fn post_message_unreliable(
     payload: Vec<u8>,
     commitment: ConsistencyLevel,
 ) -> solitaire::Result<Instruction> {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let fee_collector = FeeCollector::<'_>::key(None, &program_id);
     let sequence = Sequence::<'_>::key(
         &SequenceDerivationData {
             emitter_key: &emitter,
@@ -145,29 +166,78 @@ pub fn post_message_unreliable(
         &program_id,
     );
 
-    Ok(Instruction {
+    PostMessageUnreliableAccounts {
+        bridge: (),
+        message,
+        emitter,
+        sequence,
+        payer,
+        fee_collector: (),
+        clock: (),
+    }
+    .instruction(
         program_id,
-        accounts: vec![
-            AccountMeta::new(bridge, false),
-            AccountMeta::new(message, true),
-            AccountMeta::new_readonly(emitter, true),
-            AccountMeta::new(sequence, false),
-            AccountMeta::new(payer, true),
-            AccountMeta::new(fee_collector, false),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        (
+            crate::instruction::Instruction::PostMessageUnreliable,
+            PostMessageData {
+                nonce,
+                payload,
+                consistency_level: commitment,
+            },
+        ),
+        &[
             AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
         ],
-        data: (
-            crate::instruction::Instruction::PostMessageUnreliable,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn post_message_spl_fee(
+    program_id: Pubkey,
+    payer: Pubkey,
+    emitter: Pubkey,
+    message: Pubkey,
+    mint: Pubkey,
+    nonce: u32,
+    payload: Vec<u8>,
+    commitment: ConsistencyLevel,
+) -> solitaire::Result<Instruction> {
+    let fee_custody = SplFeeCustody::<'_, { AccountState::Initialized }>::key(
+        &SplFeeCustodyDerivationData { mint },
+        &program_id,
+    );
+    let sequence = Sequence::<'_>::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter,
+        },
+        &program_id,
+    );
+
+    PostMessageSplFeeAccounts {
+        spl_fee_config: (),
+        message,
+        emitter,
+        sequence,
+        payer,
+        fee_custody,
+        clock: (),
+    }
+    .instruction(
+        program_id,
+        (
+            crate::instruction::Instruction::PostMessageSplFee,
             PostMessageData {
                 nonce,
                 payload,
                 consistency_level: commitment,
             },
-        )
-            .try_to_vec()?,
-    })
+        ),
+        &[
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+    )
 }
 
 pub fn verify_signatures(
@@ -177,6 +247,40 @@ pub fn verify_signatures(
     signature_set: Pubkey,
     data: VerifySignaturesData,
 ) -> solitaire::Result<Instruction> {
+    verify_signatures_accounts(program_id, payer, guardian_set_index, signature_set).instruction(
+        program_id,
+        (crate::instruction::Instruction::VerifySignatures, data),
+        &[
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+    )
+}
+
+pub fn verify_signatures_v2(
+    program_id: Pubkey,
+    payer: Pubkey,
+    guardian_set_index: u32,
+    signature_set: Pubkey,
+    data: VerifySignaturesV2Data,
+) -> solitaire::Result<Instruction> {
+    verify_signatures_accounts(program_id, payer, guardian_set_index, signature_set).instruction(
+        program_id,
+        (crate::instruction::Instruction::VerifySignaturesV2, data),
+        &[
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+    )
+}
+
+// Both versions of verify_signatures take the same accounts and only differ in their data.
+fn verify_signatures_accounts(
+    program_id: Pubkey,
+    payer: Pubkey,
+    guardian_set_index: u32,
+    signature_set: Pubkey,
+) -> VerifySignaturesAccounts {
     let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
         &GuardianSetDerivationData {
             index: guardian_set_index,
@@ -184,20 +288,105 @@ pub fn verify_signatures(
         &program_id,
     );
 
-    Ok(Instruction {
-        program_id,
+    VerifySignaturesAccounts {
+        payer,
+        guardian_set,
+        signature_set,
+        instruction_acc: sysvar::instructions::id(),
+    }
+}
 
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(guardian_set, false),
-            AccountMeta::new(signature_set, true),
-            AccountMeta::new_readonly(sysvar::instructions::id(), false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
+// Build the secp256k1 and verify_signatures instruction pairs needed to verify every signature of
+// a VAA. Signatures are verified in chunks of 7 so that each pair fits into a single transaction.
+pub fn verify_signatures_batches(
+    program_id: Pubkey,
+    payer: Pubkey,
+    guardian_set_index: u32,
+    guardian_keys: &[[u8; 20]],
+    signature_set: Pubkey,
+    vaa: &VAA,
+) -> solitaire::Result<Vec<Vec<Instruction>>> {
+    // Map signatures to guardian set
+    let mut signature_items: Vec<SignatureItem> = Vec::new();
+    for s in vaa.signatures.iter() {
+        let key = guardian_keys
+            .get(s.guardian_index as usize)
+            .ok_or(ProgramError::InvalidArgument)?;
+        signature_items.push(SignatureItem {
+            signature: s.signature.clone(),
+            key: *key,
+            index: s.guardian_index,
+        });
+    }
 
-        data: (crate::instruction::Instruction::VerifySignatures, data).try_to_vec()?,
-    })
+    let body_hash = hash_vaa(&vaa.clone().into());
+
+    let mut verify_txs: Vec<Vec<Instruction>> = Vec::new();
+    for chunk in signature_items.chunks(7) {
+        let mut secp_payload = Vec::new();
+        let mut signature_status = vec![-1i8; guardian_keys.len()];
+
+        let data_offset = 1 + chunk.len() * 11;
+        let message_offset = data_offset + chunk.len() * 85;
+
+        // 1 number of signatures
+        secp_payload.write_u8(chunk.len() as u8)?;
+
+        // Secp signature info description (11 bytes * n)
+        for (i, s) in chunk.iter().enumerate() {
+            secp_payload.write_u16::<LittleEndian>((data_offset + 85 * i) as u16)?;
+            secp_payload.write_u8(0)?;
+            secp_payload.write_u16::<LittleEndian>((data_offset + 85 * i + 65) as u16)?;
+            secp_payload.write_u8(0)?;
+            secp_payload.write_u16::<LittleEndian>(message_offset as u16)?;
+            secp_payload.write_u16::<LittleEndian>(body_hash.len() as u16)?;
+            secp_payload.write_u8(0)?;
+            signature_status[s.index as usize] = i as i8;
+        }
+
+        // Write signatures and addresses
+        for s in chunk.iter() {
+            secp_payload.write_all(&s.signature)?;
+            secp_payload.write_all(&s.key)?;
+        }
+
+        // Write body
+        secp_payload.write_all(&body_hash)?;
+
+        let secp_ix = Instruction {
+            program_id: solana_program::secp256k1_program::id(),
+            data: secp_payload,
+            accounts: vec![],
+        };
+
+        // Prefer the original fixed size layout so that older deployments keep working, the
+        // variable length layout is only needed once the guardian set outgrows it.
+        let verify_ix = if guardian_keys.len() <= MAX_LEN_GUARDIAN_KEYS_V1 {
+            let mut signers = [-1i8; MAX_LEN_GUARDIAN_KEYS_V1];
+            signers[..signature_status.len()].copy_from_slice(&signature_status);
+            verify_signatures(
+                program_id,
+                payer,
+                guardian_set_index,
+                signature_set,
+                VerifySignaturesData { signers },
+            )?
+        } else {
+            verify_signatures_v2(
+                program_id,
+                payer,
+                guardian_set_index,
+                signature_set,
+                VerifySignaturesV2Data {
+                    signers: signature_status,
+                },
+            )?
+        };
+
+        verify_txs.push(vec![secp_ix, verify_ix])
+    }
+
+    Ok(verify_txs)
 }
 
 pub fn post_vaa(
@@ -206,7 +395,6 @@ pub fn post_vaa(
     signature_set: Pubkey,
     vaa: PostVAAData,
 ) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
     let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
         &GuardianSetDerivationData {
             index: vaa.guardian_set_index,
@@ -221,24 +409,35 @@ pub fn post_vaa(
     let message =
         PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &program_id);
 
-    Instruction {
+    PostVAAAccounts {
+        guardian_set,
+        bridge_info: (),
+        signature_set,
+        message,
+        payer,
+        clock: (),
+    }
+    .instruction(
         program_id,
-
-        accounts: vec![
-            AccountMeta::new_readonly(guardian_set, false),
-            AccountMeta::new_readonly(bridge, false),
-            AccountMeta::new_readonly(signature_set, false),
-            AccountMeta::new(message, false),
-            AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        (crate::instruction::Instruction::PostVAA, vaa),
+        &[
             AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
         ],
+    )
+    .unwrap()
+}
 
-        data: (crate::instruction::Instruction::PostVAA, vaa)
-            .try_to_vec()
-            .unwrap(),
-    }
+// Claim of a governance VAA, which marks it as consumed.
+fn governance_claim(program_id: &Pubkey, emitter: Pubkey, sequence: u64) -> Pubkey {
+    Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        program_id,
+    )
 }
 
 pub fn upgrade_contract(
@@ -250,49 +449,35 @@ pub fn upgrade_contract(
     spill: Pubkey,
     sequence: u64,
 ) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
-    let claim = Claim::<'_>::key(
-        &ClaimDerivationData {
-            emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_GOVERANCE,
-            sequence,
-        },
-        &program_id,
-    );
-
-    let (upgrade_authority, _) = Pubkey::find_program_address(&["upgrade".as_bytes()], &program_id);
-
     let (program_data, _) = Pubkey::find_program_address(
         &[program_id.as_ref()],
         &solana_program::bpf_loader_upgradeable::id(),
     );
 
-    Instruction {
+    UpgradeContractAccounts {
+        payer,
+        bridge: (),
+        vaa: payload_message,
+        claim: governance_claim(&program_id, emitter, sequence),
+        upgrade_authority: (),
+        spill,
+        buffer: new_contract,
+        program_data,
+        own_address: program_id,
+        rent: (),
+        clock: (),
+        bpf_loader: solana_program::bpf_loader_upgradeable::id(),
+        system: solana_program::system_program::id(),
+    }
+    .instruction(
         program_id,
-
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new(bridge, false),
-            AccountMeta::new_readonly(payload_message, false),
-            AccountMeta::new(claim, false),
-            AccountMeta::new_readonly(upgrade_authority, false),
-            AccountMeta::new(spill, false),
-            AccountMeta::new(new_contract, false),
-            AccountMeta::new(program_data, false),
-            AccountMeta::new(program_id, false),
-            AccountMeta::new_readonly(sysvar::rent::id(), false),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
-            AccountMeta::new_readonly(solana_program::bpf_loader_upgradeable::id(), false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
-
-        data: (
+        (
             crate::instruction::Instruction::UpgradeContract,
             UpgradeContractData {},
-        )
-            .try_to_vec()
-            .unwrap(),
-    }
+        ),
+        &[],
+    )
+    .unwrap()
 }
 
 pub fn upgrade_guardian_set(
@@ -304,16 +489,6 @@ pub fn upgrade_guardian_set(
     new_index: u32,
     sequence: u64,
 ) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let claim = Claim::<'_>::key(
-        &ClaimDerivationData {
-            emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_GOVERANCE,
-            sequence,
-        },
-        &program_id,
-    );
-
     let guardian_set_old = GuardianSet::<'_, { AccountState::Initialized }>::key(
         &GuardianSetDerivationData { index: old_index },
         &program_id,
@@ -324,26 +499,26 @@ pub fn upgrade_guardian_set(
         &program_id,
     );
 
-    Instruction {
+    UpgradeGuardianSetAccounts {
+        payer,
+        bridge: (),
+        vaa: payload_message,
+        claim: governance_claim(&program_id, emitter, sequence),
+        guardian_set_old,
+        guardian_set_new,
+    }
+    .instruction(
         program_id,
-
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new(bridge, false),
-            AccountMeta::new_readonly(payload_message, false),
-            AccountMeta::new(claim, false),
-            AccountMeta::new(guardian_set_old, false),
-            AccountMeta::new(guardian_set_new, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
-
-        data: (
+        (
             crate::instruction::Instruction::UpgradeGuardianSet,
             UpgradeGuardianSetData {},
-        )
-            .try_to_vec()
-            .unwrap(),
-    }
+        ),
+        &[AccountMeta::new_readonly(
+            solana_program::system_program::id(),
+            false,
+        )],
+    )
+    .unwrap()
 }
 
 pub fn set_fees(
@@ -353,74 +528,160 @@ pub fn set_fees(
     emitter: Pubkey,
     sequence: u64,
 ) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let claim = Claim::<'_>::key(
-        &ClaimDerivationData {
-            emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_GOVERANCE,
-            sequence,
-        },
-        &program_id,
-    );
+    SetFeesAccounts {
+        payer,
+        bridge: (),
+        vaa: message,
+        claim: governance_claim(&program_id, emitter, sequence),
+    }
+    .instruction(
+        program_id,
+        (crate::instruction::Instruction::SetFees, SetFeesData {}),
+        &[AccountMeta::new_readonly(
+            solana_program::system_program::id(),
+            false,
+        )],
+    )
+    .unwrap()
+}
 
-    Instruction {
+pub fn transfer_fees(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    recipient: Pubkey,
+) -> Instruction {
+    TransferFeesAccounts {
+        payer,
+        bridge: (),
+        vaa: message,
+        claim: governance_claim(&program_id, emitter, sequence),
+        fee_collector: (),
+        recipient,
+        rent: (),
+    }
+    .instruction(
         program_id,
+        (
+            crate::instruction::Instruction::TransferFees,
+            TransferFeesData {},
+        ),
+        &[AccountMeta::new_readonly(
+            solana_program::system_program::id(),
+            false,
+        )],
+    )
+    .unwrap()
+}
 
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new(bridge, false),
-            AccountMeta::new_readonly(message, false),
-            AccountMeta::new(claim, false),
+pub fn set_guardian_set_expiration(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    guardian_set_index: Option<u32>,
+) -> Instruction {
+    let guardian_set = guardian_set_index.map(|index| {
+        GuardianSet::<'_, { AccountState::Initialized }>::key(
+            &GuardianSetDerivationData { index },
+            &program_id,
+        )
+    });
+
+    SetGuardianSetExpirationAccounts {
+        payer,
+        bridge: (),
+        vaa: message,
+        claim: governance_claim(&program_id, emitter, sequence),
+        guardian_set,
+    }
+    .instruction(
+        program_id,
+        (
+            crate::instruction::Instruction::SetGuardianSetExpiration,
+            SetGuardianSetExpirationData {},
+        ),
+        &[
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
         ],
+    )
+    .unwrap()
+}
+
+pub fn set_spl_fee(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    mint: Pubkey,
+) -> Instruction {
+    let fee_custody = SplFeeCustody::<'_, { AccountState::MaybeInitialized }>::key(
+        &SplFeeCustodyDerivationData { mint },
+        &program_id,
+    );
 
-        data: (crate::instruction::Instruction::SetFees, SetFeesData {})
-            .try_to_vec()
-            .unwrap(),
+    SetSplFeeAccounts {
+        payer,
+        vaa: message,
+        claim: governance_claim(&program_id, emitter, sequence),
+        spl_fee_config: (),
+        mint,
+        fee_custody,
+        fee_collector: (),
     }
+    .instruction(
+        program_id,
+        (crate::instruction::Instruction::SetSplFee, SetSplFeeData {}),
+        &[
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+    )
+    .unwrap()
 }
 
-pub fn transfer_fees(
+pub fn transfer_spl_fees(
     program_id: Pubkey,
     payer: Pubkey,
     message: Pubkey,
     emitter: Pubkey,
     sequence: u64,
+    mint: Pubkey,
     recipient: Pubkey,
 ) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
-    let claim = Claim::<'_>::key(
-        &ClaimDerivationData {
-            emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_GOVERANCE,
-            sequence,
-        },
+    let fee_custody = SplFeeCustody::<'_, { AccountState::Initialized }>::key(
+        &SplFeeCustodyDerivationData { mint },
         &program_id,
     );
 
-    let fee_collector = FeeCollector::key(None, &program_id);
-
-    Instruction {
+    TransferSplFeesAccounts {
+        payer,
+        vaa: message,
+        claim: governance_claim(&program_id, emitter, sequence),
+        spl_fee_config: (),
+        fee_custody,
+        fee_collector: (),
+        recipient,
+    }
+    .instruction(
         program_id,
-
-        accounts: vec![
-            AccountMeta::new(payer, true),
-            AccountMeta::new(bridge, false),
-            AccountMeta::new_readonly(message, false),
-            AccountMeta::new(claim, false),
-            AccountMeta::new(fee_collector, false),
-            AccountMeta::new(recipient, false),
+        (
+            crate::instruction::Instruction::TransferSplFees,
+            TransferSplFeesData {},
+        ),
+        &[
             AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
         ],
-
-        data: (
-            crate::instruction::Instruction::TransferFees,
-            TransferFeesData {},
-        )
-            .try_to_vec()
-            .unwrap(),
-    }
+    )
+    .unwrap()
 }
 
 // Convert a full VAA structure into the serialization of its unique components, this structure is
@@ -4,7 +4,12 @@
 
 use solitaire::*;
 
-pub const MAX_LEN_GUARDIAN_KEYS: usize = 19;
+/// Upper bound on guardian set size. Upgrades are posted with `post_vaa`, which carries the whole
+/// upgrade payload in one transaction; 36 keys is the most that fits into a 1232 byte packet.
+pub const MAX_LEN_GUARDIAN_KEYS: usize = 36;
+
+/// Size of the fixed signer map used by the original `VerifySignatures` instruction layout.
+pub const MAX_LEN_GUARDIAN_KEYS_V1: usize = 19;
 pub const CHAIN_ID_SOLANA: u16 = 1;
 pub const CHAIN_ID_GOVERANCE: u16 = 1;
 
@@ -38,11 +43,16 @@ pub use accounts::{
     PostedMessageUnreliableData,
     PostedVAA,
     PostedVAAData,
+    PostedVAARef,
     Sequence,
     SequenceDerivationData,
     SequenceTracker,
     SignatureSet,
     SignatureSetData,
+    SplFeeConfig,
+    SplFeeConfigData,
+    SplFeeCustody,
+    SplFeeCustodyDerivationData,
 };
 
 pub mod api;
@@ -50,25 +60,37 @@ pub mod api;
 pub use api::{
     initialize,
     post_message,
+    post_message_spl_fee,
     post_message_unreliable,
     post_vaa,
     set_fees,
+    set_guardian_set_expiration,
+    set_spl_fee,
     transfer_fees,
+    transfer_spl_fees,
     upgrade_contract,
     upgrade_guardian_set,
     verify_signatures,
+    verify_signatures_v2,
     Initialize,
     InitializeData,
     PostMessage,
     PostMessageData,
+    PostMessageSplFee,
     PostMessageUnreliable,
     PostVAA,
     PostVAAData,
     SetFees,
     SetFeesData,
+    SetGuardianSetExpiration,
+    SetGuardianSetExpirationData,
+    SetSplFee,
+    SetSplFeeData,
     Signature,
     TransferFees,
     TransferFeesData,
+    TransferSplFees,
+    TransferSplFeesData,
     UninitializedMessage,
     UpgradeContract,
     UpgradeContractData,
@@ -76,6 +98,7 @@ pub use api::{
     UpgradeGuardianSetData,
     VerifySignatures,
     VerifySignaturesData,
+    VerifySignaturesV2Data,
 };
 
 pub mod error;
@@ -100,4 +123,9 @@ solitaire! {
     UpgradeGuardianSet => upgrade_guardian_set,
     VerifySignatures   => verify_signatures,
     PostMessageUnreliable        => post_message_unreliable,
+    SetGuardianSetExpiration     => set_guardian_set_expiration,
+    VerifySignaturesV2           => verify_signatures_v2,
+    SetSplFee                    => set_spl_fee,
+    PostMessageSplFee            => post_message_spl_fee,
+    TransferSplFees              => transfer_spl_fees,
 }
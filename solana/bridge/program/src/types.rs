@@ -9,6 +9,7 @@ use crate::{
 };
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 use byteorder::{
@@ -24,7 +25,15 @@ use solana_program::{
     program_error::ProgramError::InvalidAccountData,
     pubkey::Pubkey,
 };
-use solitaire::SolitaireError;
+use solitaire::{
+    pack_type,
+    processors::seeded::AccountOwner,
+    SolitaireError,
+};
+use spl_token::state::{
+    Account,
+    Mint,
+};
 use std::{
     self,
     io::{
@@ -38,7 +47,7 @@ use std::{
 pub type GuardianPublicKey = [u8; 20];
 
 #[repr(u8)]
-#[derive(BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize)]
+#[derive(BorshSchema, BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize)]
 pub enum ConsistencyLevel {
     Confirmed,
     Finalized,
@@ -233,3 +242,173 @@ impl SerializeGovernancePayload for GovernancePayloadTransferFees {
 
 impl DeserializeGovernancePayload for GovernancePayloadTransferFees {
 }
+
+pub struct GovernancePayloadSetGuardianSetExpiration {
+    // New period in seconds that replaced guardian sets remain valid for
+    pub guardian_set_expiration_time: u32,
+
+    // Index of a replaced guardian set whose expiration should be overridden
+    pub guardian_set_index: u32,
+
+    // New expiration timestamp for that guardian set, 0 leaves it untouched. It may be no later
+    // than the set's current expiration or the current time, so a set is never extended.
+    pub expiration_time: u32,
+}
+
+impl SerializePayload for GovernancePayloadSetGuardianSetExpiration {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        use byteorder::WriteBytesExt;
+        v.write_u32::<BigEndian>(self.guardian_set_expiration_time)?;
+        v.write_u32::<BigEndian>(self.guardian_set_index)?;
+        v.write_u32::<BigEndian>(self.expiration_time)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetGuardianSetExpiration
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let guardian_set_expiration_time = c.read_u32::<BigEndian>()?;
+        let guardian_set_index = c.read_u32::<BigEndian>()?;
+        let expiration_time = c.read_u32::<BigEndian>()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetGuardianSetExpiration {
+            guardian_set_expiration_time,
+            guardian_set_index,
+            expiration_time,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetGuardianSetExpiration {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 8;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetGuardianSetExpiration {
+}
+
+pub struct GovernancePayloadSetSplFee {
+    // Mint of the token message fees can be paid in
+    pub mint: Pubkey,
+
+    // New fee in the smallest unit of the mint, 0 disables paying fees in tokens
+    pub fee: U256,
+}
+
+impl SerializePayload for GovernancePayloadSetSplFee {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.mint.to_bytes())?;
+        let mut fee_data = [0u8; 32];
+        self.fee.to_big_endian(&mut fee_data);
+        v.write_all(&fee_data[..])?;
+
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetSplFee
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut mint = [0u8; 32];
+        c.read_exact(&mut mint)?;
+
+        let mut fee_data: [u8; 32] = [0; 32];
+        c.read_exact(&mut fee_data)?;
+        let fee = U256::from_big_endian(&fee_data);
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetSplFee {
+            mint: Pubkey::new(&mint[..]),
+            fee,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetSplFee {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 6;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetSplFee {
+}
+
+pub struct GovernancePayloadTransferSplFees {
+    // Mint of the fees to be transferred
+    pub mint: Pubkey,
+
+    // Amount to be transferred
+    pub amount: U256,
+
+    // Recipient token account
+    pub to: ForeignAddress,
+}
+
+impl SerializePayload for GovernancePayloadTransferSplFees {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        v.write_all(&self.mint.to_bytes())?;
+        let mut amount_data = [0u8; 32];
+        self.amount.to_big_endian(&mut amount_data);
+        v.write_all(&amount_data)?;
+        v.write_all(&self.to)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadTransferSplFees
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut mint = [0u8; 32];
+        c.read_exact(&mut mint)?;
+
+        let mut amount_data: [u8; 32] = [0; 32];
+        c.read_exact(&mut amount_data)?;
+        let amount = U256::from_big_endian(&amount_data);
+
+        let mut to = ForeignAddress::default();
+        c.read_exact(&mut to)?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadTransferSplFees {
+            mint: Pubkey::new(&mint[..]),
+            amount,
+            to,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadTransferSplFees {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 7;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadTransferSplFees {
+}
+
+pack_type!(SplMint, Mint, AccountOwner::Other(spl_token::id()));
+pack_type!(SplAccount, Account, AccountOwner::Other(spl_token::id()));
@@ -9,6 +9,7 @@ use crate::{
         InvalidGovernanceModule,
     },
     PostedVAAData,
+    PostedVAARef,
     Result,
     CHAIN_ID_SOLANA,
 };
@@ -25,8 +26,10 @@ use solana_program::{
     pubkey::Pubkey,
 };
 use solitaire::{
+    client::ClientAccount,
+    idl::IdlAccount,
     Context,
-    Data,
+    DataRef,
     Peel,
     SolitaireError,
     *,
@@ -103,10 +106,9 @@ pub trait DeserializeGovernancePayload: DeserializePayload + SerializeGovernance
     }
 }
 
-pub struct PayloadMessage<'b, T: DeserializePayload>(
-    Data<'b, PostedVAAData, { AccountState::Initialized }>,
-    T,
-);
+/// A posted VAA along with its deserialized payload. The account itself is read in place rather
+/// than deserialized, so that large payloads are only parsed once and never written back.
+pub struct PayloadMessage<'b, T: DeserializePayload>(DataRef<'b, PostedVAAData>, T);
 
 impl<'a, 'b: 'a, T: DeserializePayload> Peel<'a, 'b> for PayloadMessage<'b, T> {
     fn peel<I>(ctx: &mut Context<'a, 'b, I>) -> Result<Self>
@@ -114,13 +116,25 @@ impl<'a, 'b: 'a, T: DeserializePayload> Peel<'a, 'b> for PayloadMessage<'b, T> {
         Self: Sized,
     {
         // Deserialize wrapped payload
-        let data: Data<'b, PostedVAAData, { AccountState::Initialized }> = Data::peel(ctx)?;
-        let payload = DeserializePayload::deserialize(&mut &data.payload[..])?;
+        let data: DataRef<'b, PostedVAAData> = DataRef::peel(ctx)?;
+        let payload = DeserializePayload::deserialize(&mut PostedVAARef(data.data()).payload())?;
         Ok(PayloadMessage(data, payload))
     }
 
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
-        Data::persist(&self.0, program_id)
+        DataRef::persist(&self.0, program_id)
+    }
+
+    fn describe(account: &mut IdlAccount) {
+        DataRef::<'b, PostedVAAData>::describe(account)
+    }
+}
+
+impl<'b, T: DeserializePayload> ClientAccount for PayloadMessage<'b, T> {
+    type Key = Pubkey;
+
+    fn pubkey(key: &Self::Key, _program_id: &Pubkey) -> Pubkey {
+        *key
     }
 }
 
@@ -132,8 +146,8 @@ impl<'b, T: DeserializePayload> Deref for PayloadMessage<'b, T> {
 }
 
 impl<'b, T: DeserializePayload> PayloadMessage<'b, T> {
-    pub fn meta(&self) -> &PostedVAAData {
-        &self.0
+    pub fn meta(&self) -> PostedVAARef<'_> {
+        PostedVAARef(self.0.data())
     }
 
     pub fn info(&self) -> AccountInfo<'b> {
@@ -1,27 +1,19 @@
-use solana_program::{
-    instruction::Instruction,
-    pubkey::Pubkey,
-};
+use solana_program::pubkey::Pubkey;
 use std::str::FromStr;
 
 use crate::vaa::{
     DeserializePayload,
-    SignatureItem,
     VAA,
 };
 use borsh::BorshDeserialize;
-use byteorder::WriteBytesExt;
-use sha3::Digest;
 use solitaire::{
     processors::seeded::Seeded,
     AccountState,
 };
-use std::io::Write;
 
 use crate::{
     accounts::{
         Bridge,
-        BridgeData,
         FeeCollector,
         GuardianSet,
         GuardianSetData,
@@ -29,6 +21,7 @@ use crate::{
         PostedVAA,
         PostedVAAData,
         PostedVAADerivationData,
+        VersionedBridgeData,
     },
     instructions::{
         hash_vaa,
@@ -36,23 +29,27 @@ use crate::{
         post_message_unreliable,
         post_vaa,
         set_fees,
+        set_guardian_set_expiration,
+        set_spl_fee,
         transfer_fees,
+        transfer_spl_fees,
         upgrade_contract,
         upgrade_guardian_set,
-        verify_signatures,
+        verify_signatures_batches,
     },
     types::{
         ConsistencyLevel,
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetGuardianSetExpiration,
+        GovernancePayloadSetSplFee,
         GovernancePayloadTransferFees,
+        GovernancePayloadTransferSplFees,
         GovernancePayloadUpgrade,
     },
     Claim,
     ClaimDerivationData,
     PostVAAData,
-    VerifySignaturesData,
 };
-use byteorder::LittleEndian;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -205,6 +202,76 @@ pub fn transfer_fees_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsVa
     return JsValue::from_serde(&ix).unwrap();
 }
 
+#[wasm_bindgen]
+pub fn set_guardian_set_expiration_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload =
+        GovernancePayloadSetGuardianSetExpiration::deserialize(&mut vaa.payload.as_slice())
+            .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = set_guardian_set_expiration(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.sequence,
+        (payload.expiration_time != 0).then(|| payload.guardian_set_index),
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn set_spl_fee_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload = GovernancePayloadSetSplFee::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = set_spl_fee(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.sequence,
+        payload.mint,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn transfer_spl_fees_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload =
+        GovernancePayloadTransferSplFees::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = transfer_spl_fees(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.sequence,
+        payload.mint,
+        Pubkey::new(&payload.to[..]),
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
 #[wasm_bindgen]
 pub fn upgrade_contract_ix(
     program_id: String,
@@ -250,90 +317,18 @@ pub fn verify_signatures_ix(
     let guardian_set: GuardianSetData = guardian_set.into_serde().unwrap();
     let vaa = VAA::deserialize(vaa_data.as_slice()).unwrap();
 
-    // Map signatures to guardian set
-    let mut signature_items: Vec<SignatureItem> = Vec::new();
-    for s in vaa.signatures.iter() {
-        let mut item = SignatureItem {
-            signature: s.signature.clone(),
-            key: [0; 20],
-            index: s.guardian_index as u8,
-        };
-        item.key = guardian_set.keys[s.guardian_index as usize];
-
-        signature_items.push(item);
-    }
-
-    let vaa_body = &vaa_data[VAA::HEADER_LEN + VAA::SIGNATURE_LEN * vaa.signatures.len()..];
-    let body_hash: [u8; 32] = {
-        let mut h = sha3::Keccak256::default();
-        h.write(vaa_body).unwrap();
-        h.finalize().into()
+    let verify_txs = match verify_signatures_batches(
+        program_id,
+        payer,
+        guardian_set_index,
+        &guardian_set.keys,
+        signature_set,
+        &vaa,
+    ) {
+        Ok(v) => v,
+        Err(e) => panic!("{:?}", e),
     };
 
-    let mut verify_txs: Vec<Vec<Instruction>> = Vec::new();
-    for (_tx_index, chunk) in signature_items.chunks(7).enumerate() {
-        let mut secp_payload = Vec::new();
-        let mut signature_status = [-1i8; 19];
-
-        let data_offset = 1 + chunk.len() * 11;
-        let message_offset = data_offset + chunk.len() * 85;
-
-        // 1 number of signatures
-        secp_payload.write_u8(chunk.len() as u8).unwrap();
-
-        // Secp signature info description (11 bytes * n)
-        for (i, s) in chunk.iter().enumerate() {
-            secp_payload
-                .write_u16::<LittleEndian>((data_offset + 85 * i) as u16)
-                .unwrap();
-            secp_payload.write_u8(0).unwrap();
-            secp_payload
-                .write_u16::<LittleEndian>((data_offset + 85 * i + 65) as u16)
-                .unwrap();
-            secp_payload.write_u8(0).unwrap();
-            secp_payload
-                .write_u16::<LittleEndian>(message_offset as u16)
-                .unwrap();
-            secp_payload
-                .write_u16::<LittleEndian>(body_hash.len() as u16)
-                .unwrap();
-            secp_payload.write_u8(0).unwrap();
-            signature_status[s.index as usize] = i as i8;
-        }
-
-        // Write signatures and addresses
-        for s in chunk.iter() {
-            secp_payload.write(&s.signature).unwrap();
-            secp_payload.write(&s.key).unwrap();
-        }
-
-        // Write body
-        secp_payload.write(&body_hash).unwrap();
-
-        let secp_ix = Instruction {
-            program_id: solana_program::secp256k1_program::id(),
-            data: secp_payload,
-            accounts: vec![],
-        };
-
-        let payload = VerifySignaturesData {
-            signers: signature_status,
-        };
-
-        let verify_ix = match verify_signatures(
-            program_id,
-            payer,
-            guardian_set_index,
-            signature_set,
-            payload,
-        ) {
-            Ok(v) => v,
-            Err(e) => panic!("{:?}", e),
-        };
-
-        verify_txs.push(vec![secp_ix, verify_ix])
-    }
-
     JsValue::from_serde(&verify_txs).unwrap()
 }
 
@@ -363,7 +358,12 @@ pub fn state_address(bridge: String) -> Vec<u8> {
 
 #[wasm_bindgen]
 pub fn parse_state(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&BridgeData::try_from_slice(data.as_slice()).unwrap()).unwrap()
+    JsValue::from_serde(
+        &VersionedBridgeData::try_from_slice(data.as_slice())
+            .unwrap()
+            .data,
+    )
+    .unwrap()
 }
 
 #[wasm_bindgen]
@@ -11,7 +11,9 @@ use libsecp256k1::{
 use sha3::Digest;
 use solana_program::{
     instruction::Instruction,
+    program_pack::Pack,
     pubkey::Pubkey,
+    rent::Rent,
     system_instruction,
 };
 use solana_program_test::{
@@ -39,15 +41,23 @@ use std::{
 };
 
 use bridge::{
-    accounts::FeeCollector,
+    accounts::{
+        FeeCollector,
+        SplFeeCustody,
+        SplFeeCustodyDerivationData,
+    },
     instruction,
     instructions,
     types::ConsistencyLevel,
     PostVAAData,
     VerifySignaturesData,
+    VerifySignaturesV2Data,
 };
 
-use solitaire::processors::seeded::Seeded;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
 
 pub use helpers::*;
 
@@ -115,6 +125,14 @@ mod helpers {
         client.get_account(account).await.unwrap().unwrap().lamports
     }
 
+    /// Fetch the balance of an SPL token account
+    pub async fn get_token_balance(client: &mut BanksClient, account: Pubkey) -> u64 {
+        let account = client.get_account(account).await.unwrap().unwrap();
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    }
+
     /// Generate `count` secp256k1 private keys, along with their ethereum-styled public key
     /// encoding: 0x0123456789ABCDEF01234
     pub fn generate_keys(count: u8) -> (Vec<[u8; 20]>, Vec<SecretKey>) {
@@ -349,6 +367,47 @@ mod helpers {
         Ok(signature_set.pubkey())
     }
 
+    /// Same as `verify_signatures`, but using the variable length signer map required by guardian
+    /// sets larger than the original fixed layout.
+    pub async fn verify_signatures_v2(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        body: [u8; 32],
+        secret_keys: &[SecretKey],
+        guardian_set_version: u32,
+    ) -> Result<Pubkey, BanksClientError> {
+        let signature_set = Keypair::new();
+        let tx_signers = [payer, &signature_set];
+        // Push Secp256k1 instructions for each signature we want to verify.
+        for (i, key) in secret_keys.iter().enumerate() {
+            // Set this signers signature position as present at 0.
+            let mut signers = vec![-1; secret_keys.len()];
+            signers[i] = 0;
+
+            execute(
+                client,
+                payer,
+                &tx_signers,
+                &[
+                    new_secp256k1_instruction(key, &body),
+                    instructions::verify_signatures_v2(
+                        *program,
+                        payer.pubkey(),
+                        guardian_set_version,
+                        signature_set.pubkey(),
+                        VerifySignaturesV2Data { signers },
+                    )
+                    .unwrap(),
+                ],
+                CommitmentLevel::Processed,
+            )
+            .await?;
+        }
+
+        Ok(signature_set.pubkey())
+    }
+
     pub async fn post_vaa(
         client: &mut BanksClient,
         program: &Pubkey,
@@ -478,4 +537,231 @@ mod helpers {
         )
         .await
     }
+
+    pub async fn set_guardian_set_expiration(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        guardian_set_index: Option<u32>,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_guardian_set_expiration(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                guardian_set_index,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn create_mint(
+        client: &mut BanksClient,
+        payer: &Keypair,
+        mint_authority: &Pubkey,
+        mint: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let mint_key = mint.pubkey();
+        execute(
+            client,
+            payer,
+            &[payer, mint],
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &mint_key,
+                    Rent::default().minimum_balance(spl_token::state::Mint::LEN),
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::id(),
+                ),
+                spl_token::instruction::initialize_mint(
+                    &spl_token::id(),
+                    &mint_key,
+                    mint_authority,
+                    None,
+                    0,
+                )
+                .unwrap(),
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn create_token_account(
+        client: &mut BanksClient,
+        payer: &Keypair,
+        token_acc: &Keypair,
+        token_authority: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let token_key = token_acc.pubkey();
+        execute(
+            client,
+            payer,
+            &[payer, token_acc],
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &token_key,
+                    Rent::default().minimum_balance(spl_token::state::Account::LEN),
+                    spl_token::state::Account::LEN as u64,
+                    &spl_token::id(),
+                ),
+                spl_token::instruction::initialize_account(
+                    &spl_token::id(),
+                    &token_key,
+                    mint,
+                    token_authority,
+                )
+                .unwrap(),
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn mint_tokens(
+        client: &mut BanksClient,
+        payer: &Keypair,
+        mint_authority: &Keypair,
+        mint: &Pubkey,
+        token_account: &Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer, mint_authority],
+            &[spl_token::instruction::mint_to(
+                &spl_token::id(),
+                mint,
+                token_account,
+                &mint_authority.pubkey(),
+                &[],
+                amount,
+            )
+            .unwrap()],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn post_message_spl_fee(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        emitter: &Keypair,
+        mint: Pubkey,
+        // Token account owned by the payer the fee is paid from.
+        from: Pubkey,
+        nonce: u32,
+        data: Vec<u8>,
+        fee: u64,
+    ) -> Result<Pubkey, BanksClientError> {
+        let fee_custody = SplFeeCustody::<'_, { AccountState::Initialized }>::key(
+            &SplFeeCustodyDerivationData { mint },
+            program,
+        );
+        let message = Keypair::new();
+
+        let instruction = instructions::post_message_spl_fee(
+            *program,
+            payer.pubkey(),
+            emitter.pubkey(),
+            message.pubkey(),
+            mint,
+            nonce,
+            data,
+            ConsistencyLevel::Confirmed,
+        )
+        .unwrap();
+
+        execute(
+            client,
+            payer,
+            &[payer, emitter, &message],
+            &[
+                spl_token::instruction::transfer(
+                    &spl_token::id(),
+                    &from,
+                    &fee_custody,
+                    &payer.pubkey(),
+                    &[],
+                    fee,
+                )
+                .unwrap(),
+                instruction,
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await?;
+
+        Ok(message.pubkey())
+    }
+
+    pub async fn set_spl_fee(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        mint: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_spl_fee(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                mint,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn transfer_spl_fees(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        mint: Pubkey,
+        recipient: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::transfer_spl_fees(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                mint,
+                recipient,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
 }
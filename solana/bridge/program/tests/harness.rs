@@ -0,0 +1,647 @@
+//! Handler tests run in process through the Solitaire harness, see `integration.rs` for tests
+//! against the BPF build.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use bridge::{
+    accounts::{
+        Bridge,
+        FeeCollector,
+        GuardianSet,
+        GuardianSetDerivationData,
+        Sequence,
+        SequenceDerivationData,
+    },
+    api::{
+        InitializeAccounts,
+        InitializeData,
+        PostMessageAccounts,
+        PostMessageData,
+    },
+    error::Error,
+    instruction,
+    types::ConsistencyLevel,
+    BridgeConfig,
+    BridgeData,
+    GuardianSetData,
+    PostedMessageData,
+    SequenceTracker,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_instruction,
+    system_program,
+    sysvar::{
+        clock::Clock,
+        rent::Rent,
+    },
+};
+use solitaire::{
+    harness::{
+        Account,
+        Harness,
+    },
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Context,
+    Data,
+    ExecutionContext,
+    Migrate,
+    Owned,
+    Peel,
+    SolitaireError,
+    Versioned,
+};
+
+const FEE: u64 = 500;
+
+fn initialize(harness: &mut Harness, program_id: &Pubkey, payer: Pubkey) {
+    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData { index: 0 },
+        program_id,
+    );
+    let accounts = InitializeAccounts {
+        bridge: (),
+        guardian_set,
+        fee_collector: (),
+        payer,
+        clock: (),
+    };
+    harness
+        .execute(
+            program_id,
+            instruction::Initialize::execute,
+            &with_system(accounts.to_account_metas(program_id)),
+            &InitializeData {
+                guardian_set_expiration_time: 60,
+                fee: FEE,
+                initial_guardians: vec![[7; 20]],
+            },
+        )
+        .unwrap();
+}
+
+/// Accounts a handler only invokes are passed after its own.
+fn with_system(mut metas: Vec<AccountMeta>) -> Vec<AccountMeta> {
+    metas.push(AccountMeta::new_readonly(system_program::id(), false));
+    metas
+}
+
+fn post_message_accounts(
+    program_id: &Pubkey,
+    payer: Pubkey,
+    emitter: Pubkey,
+) -> PostMessageAccounts {
+    PostMessageAccounts {
+        bridge: (),
+        message: Pubkey::new_unique(),
+        emitter,
+        sequence: Sequence::key(
+            &SequenceDerivationData {
+                emitter_key: &emitter,
+            },
+            program_id,
+        ),
+        payer,
+        fee_collector: (),
+        clock: (),
+    }
+}
+
+fn post_message_data(payload: &[u8]) -> PostMessageData {
+    PostMessageData {
+        nonce: 9,
+        payload: payload.to_vec(),
+        consistency_level: ConsistencyLevel::Finalized,
+    }
+}
+
+#[test]
+fn initialize_creates_bridge() {
+    let program_id = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let mut harness = Harness::new();
+    harness.fund(&payer, 1_000_000_000);
+    harness.set_sysvar(&Clock {
+        unix_timestamp: 1234,
+        ..Clock::default()
+    });
+
+    initialize(&mut harness, &program_id, payer);
+
+    let bridge: BridgeData = harness
+        .data(&Bridge::<'_, { AccountState::Initialized }>::key(
+            None,
+            &program_id,
+        ))
+        .unwrap();
+    assert_eq!(bridge.config.fee, FEE);
+    assert_eq!(bridge.config.guardian_set_expiration_time, 60);
+
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Initialized }>::key(
+        &GuardianSetDerivationData { index: 0 },
+        &program_id,
+    );
+    let guardian_set: GuardianSetData = harness.data(&guardian_set_key).unwrap();
+    assert_eq!(guardian_set.keys, vec![[7; 20]]);
+    assert_eq!(guardian_set.creation_time, 1234);
+
+    let fee_collector = FeeCollector::key(None, &program_id);
+    assert_eq!(
+        harness.lamports(&fee_collector),
+        Rent::default().minimum_balance(0)
+    );
+    assert_eq!(bridge.last_lamports, harness.lamports(&fee_collector));
+    assert_eq!(
+        harness.account(&fee_collector).unwrap().owner,
+        system_program::id()
+    );
+}
+
+#[test]
+fn post_message_requires_fee() {
+    let program_id = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let emitter = Pubkey::new_unique();
+    let mut harness = Harness::new();
+    harness.fund(&payer, 1_000_000_000);
+    initialize(&mut harness, &program_id, payer);
+
+    let accounts = post_message_accounts(&program_id, payer, emitter);
+    let err = harness
+        .execute(
+            &program_id,
+            instruction::PostMessage::execute,
+            &with_system(accounts.to_account_metas(&program_id)),
+            &post_message_data(b"hello"),
+        )
+        .unwrap_err();
+    assert!(
+        matches!(err, SolitaireError::Program { code, .. } if code == Error::InsufficientFees.code())
+    );
+
+    // Nothing from the failed instruction is kept.
+    assert!(harness.account(&accounts.message).is_none());
+    assert!(harness.account(&accounts.sequence).is_none());
+}
+
+#[test]
+fn post_message_increments_sequence() {
+    let program_id = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let emitter = Pubkey::new_unique();
+    let fee_collector = FeeCollector::key(None, &program_id);
+    let mut harness = Harness::new();
+    harness.fund(&payer, 1_000_000_000);
+    initialize(&mut harness, &program_id, payer);
+
+    for sequence in 0..2 {
+        harness
+            .process(&system_instruction::transfer(&payer, &fee_collector, FEE))
+            .unwrap();
+        let accounts = post_message_accounts(&program_id, payer, emitter);
+        harness
+            .execute(
+                &program_id,
+                instruction::PostMessage::execute,
+                &with_system(accounts.to_account_metas(&program_id)),
+                &post_message_data(b"hello"),
+            )
+            .unwrap();
+
+        let message =
+            PostedMessageData::try_from_slice(&harness.account(&accounts.message).unwrap().data)
+                .unwrap();
+        assert_eq!(message.sequence, sequence);
+        assert_eq!(message.emitter_address, emitter.to_bytes());
+        assert_eq!(message.payload, b"hello");
+        assert_eq!(
+            harness.account(&accounts.message).unwrap().owner,
+            program_id
+        );
+
+        let tracker: SequenceTracker = harness.data(&accounts.sequence).unwrap();
+        assert_eq!(tracker.sequence, sequence + 1);
+    }
+}
+
+/// Flips the first data byte of its first account and moves a lamport from it to the second,
+/// regardless of who owns them.
+fn scribble(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if data[0] == 0 {
+        accounts[0].data.borrow_mut()[0] ^= 1;
+    } else {
+        **accounts[0].lamports.borrow_mut() -= 1;
+        **accounts[1].lamports.borrow_mut() += 1;
+    }
+    Ok(())
+}
+
+#[test]
+fn harness_refuses_changes_to_foreign_accounts() {
+    let program_id = Pubkey::new_unique();
+    let scribbler = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let bridge_key = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let fee_collector = FeeCollector::key(None, &program_id);
+    let mut harness = Harness::new();
+    harness.add_program(scribbler, scribble);
+    harness.fund(&payer, 1_000_000_000);
+    initialize(&mut harness, &program_id, payer);
+    let bridge = harness.account(&bridge_key).unwrap().clone();
+
+    // Writable accounts can still only be written to and debited by their owner.
+    for (data, accounts) in [
+        (0, vec![AccountMeta::new(bridge_key, false)]),
+        (
+            1,
+            vec![
+                AccountMeta::new(fee_collector, false),
+                AccountMeta::new(payer, false),
+            ],
+        ),
+    ] {
+        assert!(harness
+            .process(&Instruction {
+                program_id: scribbler,
+                accounts,
+                data: vec![data],
+            })
+            .is_err());
+    }
+    assert_eq!(harness.account(&bridge_key), Some(&bridge));
+    assert_eq!(
+        harness.lamports(&fee_collector),
+        Rent::default().minimum_balance(0)
+    );
+
+    // Accounts it owns are fine.
+    let owned = Pubkey::new_unique();
+    harness.set_account(
+        owned,
+        Account {
+            lamports: 10,
+            data: vec![0],
+            owner: scribbler,
+            executable: false,
+        },
+    );
+    harness
+        .process(&Instruction {
+            program_id: scribbler,
+            accounts: vec![AccountMeta::new(owned, false)],
+            data: vec![0],
+        })
+        .unwrap();
+    assert_eq!(harness.account(&owned).unwrap().data, vec![1]);
+}
+
+/// Sets the number of keys of the guardian set passed first and resizes it to fit, paid by the
+/// payer in the data. The set is peeled whether it's writable or not, to leave the checks to
+/// `Data::realloc`.
+fn resize_guardian_set(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> solitaire::Result<()> {
+    let (keys, payer) = <(u8, Pubkey)>::try_from_slice(data)?;
+    let ctx = ExecutionContext {
+        program_id,
+        accounts,
+    };
+    let mut peel = Context::new(program_id, &accounts[0], &());
+    peel.immutable = false;
+    let mut set = GuardianSet::<'_, { AccountState::MaybeInitialized }>::peel(&mut peel)?;
+    set.keys = vec![[7; 20]; keys as usize];
+    set.realloc(&ctx, &payer)?;
+    set.persist(program_id)
+}
+
+fn guardian_set_with(harness: &mut Harness, program_id: &Pubkey, keys: usize) -> Pubkey {
+    let key = Pubkey::new_unique();
+    let data = GuardianSetData {
+        index: 0,
+        keys: vec![[7; 20]; keys],
+        creation_time: 0,
+        expiration_time: 0,
+    }
+    .try_to_vec()
+    .unwrap();
+    harness.set_account(
+        key,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: *program_id,
+            executable: false,
+        },
+    );
+    key
+}
+
+#[test]
+fn realloc_resizes_data_accounts() {
+    let program_id = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let mut harness = Harness::new();
+    harness.fund(&payer, 1_000_000_000);
+    let set = guardian_set_with(&mut harness, &program_id, 1);
+    let before = harness.lamports(&set);
+    let metas = with_system(vec![
+        AccountMeta::new(set, false),
+        AccountMeta::new(payer, true),
+    ]);
+
+    // Growing tops the rent exemption up from the payer.
+    harness
+        .execute(&program_id, resize_guardian_set, &metas, &(3u8, payer))
+        .unwrap();
+    let grown = harness.account(&set).unwrap().clone();
+    let data: GuardianSetData = harness.data(&set).unwrap();
+    assert_eq!(data.keys.len(), 3);
+    assert_eq!(grown.data.len(), data.try_to_vec().unwrap().len());
+    assert_eq!(
+        grown.lamports,
+        Rent::default().minimum_balance(grown.data.len())
+    );
+    assert_eq!(
+        harness.lamports(&payer),
+        1_000_000_000 - (grown.lamports - before)
+    );
+
+    // Shrinking drops the bytes no longer needed and keeps the lamports.
+    harness
+        .execute(&program_id, resize_guardian_set, &metas, &(0u8, payer))
+        .unwrap();
+    let shrunk = harness.account(&set).unwrap();
+    assert_eq!(shrunk.data.len(), 4 + 4 + 8);
+    assert_eq!(shrunk.lamports, grown.lamports);
+    assert!(harness
+        .data::<GuardianSetData>(&set)
+        .unwrap()
+        .keys
+        .is_empty());
+}
+
+#[test]
+fn realloc_refuses_what_it_cant_persist() {
+    let program_id = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let mut harness = Harness::new();
+    harness.fund(&payer, 1_000_000_000);
+    let set = guardian_set_with(&mut harness, &program_id, 1);
+    let before = harness.account(&set).unwrap().clone();
+
+    // The payer and the system program funding the resize have to be passed.
+    for metas in [
+        with_system(vec![AccountMeta::new(set, false)]),
+        vec![AccountMeta::new(set, false), AccountMeta::new(payer, true)],
+    ] {
+        let err = harness
+            .execute(&program_id, resize_guardian_set, &metas, &(3u8, payer))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            SolitaireError::ProgramError(ProgramError::NotEnoughAccountKeys)
+        ));
+    }
+
+    // A read-only account can't be resized.
+    let metas = with_system(vec![
+        AccountMeta::new_readonly(set, false),
+        AccountMeta::new(payer, true),
+    ]);
+    let err = harness
+        .execute(&program_id, resize_guardian_set, &metas, &(3u8, payer))
+        .unwrap_err();
+    assert!(matches!(err, SolitaireError::NonWriteableAccount(key) if key == set));
+    assert_eq!(harness.account(&set), Some(&before));
+
+    // Neither can an account the program doesn't own, such as one that was never created.
+    let missing = Pubkey::new_unique();
+    let metas = with_system(vec![
+        AccountMeta::new(missing, false),
+        AccountMeta::new(payer, true),
+    ]);
+    let err = harness
+        .execute(&program_id, resize_guardian_set, &metas, &(3u8, payer))
+        .unwrap_err();
+    assert!(matches!(err, SolitaireError::InvalidOwner(owner) if owner == system_program::id()));
+    assert_eq!(harness.lamports(&payer), 1_000_000_000);
+}
+
+/// Closes the guardian set passed first onto the second account, peeled like in
+/// `resize_guardian_set`.
+fn close_guardian_set(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _data: &[u8],
+) -> solitaire::Result<()> {
+    let ctx = ExecutionContext {
+        program_id,
+        accounts,
+    };
+    let mut peel = Context::new(program_id, &accounts[0], &());
+    peel.immutable = false;
+    let mut set = GuardianSet::<'_, { AccountState::MaybeInitialized }>::peel(&mut peel)?;
+    set.close(&ctx, &accounts[1])?;
+    set.persist(program_id)
+}
+
+#[test]
+fn close_returns_accounts_to_the_system_program() {
+    let program_id = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let mut harness = Harness::new();
+    harness.fund(&recipient, 1_000);
+    let set = guardian_set_with(&mut harness, &program_id, 1);
+    let lamports = harness.lamports(&set);
+
+    harness
+        .execute(
+            &program_id,
+            close_guardian_set,
+            &[
+                AccountMeta::new(set, false),
+                AccountMeta::new(recipient, false),
+            ],
+            &(),
+        )
+        .unwrap();
+    assert_eq!(harness.lamports(&recipient), 1_000 + lamports);
+    let closed = harness.account(&set).cloned().unwrap_or_default();
+    assert_eq!(closed.lamports, 0);
+    assert!(closed.data.is_empty());
+    assert_eq!(closed.owner, system_program::id());
+}
+
+#[test]
+fn close_refuses_what_it_cant_persist() {
+    let program_id = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let mut harness = Harness::new();
+    harness.fund(&recipient, 1_000);
+    let set = guardian_set_with(&mut harness, &program_id, 1);
+    let before = harness.account(&set).unwrap().clone();
+    let close = |harness: &mut Harness, metas: &[AccountMeta]| {
+        harness
+            .execute(&program_id, close_guardian_set, metas, &())
+            .unwrap_err()
+    };
+
+    // Closing onto itself would leave the lamports where they are.
+    let err = close(
+        &mut harness,
+        &[AccountMeta::new(set, false), AccountMeta::new(set, false)],
+    );
+    assert!(matches!(
+        err,
+        SolitaireError::ProgramError(ProgramError::InvalidArgument)
+    ));
+
+    // Both the account and the recipient have to be writable.
+    let err = close(
+        &mut harness,
+        &[
+            AccountMeta::new_readonly(set, false),
+            AccountMeta::new(recipient, false),
+        ],
+    );
+    assert!(matches!(err, SolitaireError::NonWriteableAccount(key) if key == set));
+    let err = close(
+        &mut harness,
+        &[
+            AccountMeta::new(set, false),
+            AccountMeta::new_readonly(recipient, false),
+        ],
+    );
+    assert!(matches!(err, SolitaireError::NonWriteableAccount(key) if key == recipient));
+
+    // And the program has to own the account.
+    let foreign = Pubkey::new_unique();
+    harness.fund(&foreign, 1_000);
+    let err = close(
+        &mut harness,
+        &[
+            AccountMeta::new(foreign, false),
+            AccountMeta::new(recipient, false),
+        ],
+    );
+    assert!(matches!(err, SolitaireError::InvalidOwner(owner) if owner == system_program::id()));
+
+    assert_eq!(harness.account(&set), Some(&before));
+    assert_eq!(harness.lamports(&recipient), 1_000);
+    assert_eq!(harness.lamports(&foreign), 1_000);
+}
+
+/// Bridge state with a field appended, as a later version of the program would store it.
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+struct NextBridgeData {
+    guardian_set_index: u32,
+    last_lamports: u64,
+    config: BridgeConfig,
+    messages: u64,
+}
+
+impl Migrate for NextBridgeData {
+    const VERSION: u8 = 1;
+    type Previous = BridgeData;
+
+    fn migrate(previous: BridgeData) -> Self {
+        NextBridgeData {
+            guardian_set_index: previous.guardian_set_index,
+            last_lamports: previous.last_lamports,
+            config: previous.config,
+            messages: 0,
+        }
+    }
+}
+
+impl Owned for NextBridgeData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+/// Counts a message in the appended field of the bridge passed first, migrating it at the expense
+/// of the payer in the data first if asked to.
+fn count_message(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> solitaire::Result<()> {
+    let (migrate, payer) = <(bool, Pubkey)>::try_from_slice(data)?;
+    let ctx = ExecutionContext {
+        program_id,
+        accounts,
+    };
+    let mut peel = Context::new(program_id, &accounts[0], &());
+    peel.immutable = false;
+    let mut bridge =
+        Data::<'_, Versioned<NextBridgeData>, { AccountState::Initialized }>::peel(&mut peel)?;
+    if migrate {
+        bridge.migrate(&ctx, &payer)?;
+    }
+    bridge.messages += 1;
+    bridge.persist(program_id)
+}
+
+#[test]
+fn bridge_migrates_from_legacy_layout() {
+    let program_id = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let bridge_key = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let mut harness = Harness::new();
+    harness.fund(&payer, 1_000_000_000);
+    initialize(&mut harness, &program_id, payer);
+    let legacy = harness.account(&bridge_key).unwrap().clone();
+    assert_eq!(legacy.data.len(), 24);
+    let funds = harness.lamports(&payer);
+    let metas = with_system(vec![
+        AccountMeta::new(bridge_key, false),
+        AccountMeta::new(payer, true),
+    ]);
+
+    // The appended field can't be stored in the legacy layout.
+    let err = harness
+        .execute(&program_id, count_message, &metas, &(false, payer))
+        .unwrap_err();
+    assert!(matches!(err, SolitaireError::IoError(_)));
+    assert_eq!(harness.account(&bridge_key), Some(&legacy));
+
+    // Migrating grows the account to the new layout, topping up its rent from the payer.
+    harness
+        .execute(&program_id, count_message, &metas, &(true, payer))
+        .unwrap();
+    let upgraded = harness.account(&bridge_key).unwrap().clone();
+    assert_eq!(upgraded.data.len(), 32);
+    assert_eq!(upgraded.lamports, Rent::default().minimum_balance(32));
+    assert_eq!(
+        harness.lamports(&payer),
+        funds - (upgraded.lamports - legacy.lamports)
+    );
+    let bridge: Versioned<NextBridgeData> = harness.data(&bridge_key).unwrap();
+    assert!(!bridge.is_outdated());
+    assert_eq!(bridge.messages, 1);
+    assert_eq!(bridge.config.fee, FEE);
+    assert_eq!(bridge.config.guardian_set_expiration_time, 60);
+    assert_eq!(&upgraded.data[..24], &legacy.data[..]);
+
+    // Once upgraded, the account is read at the new layout without migrating again.
+    harness
+        .execute(&program_id, count_message, &metas, &(false, payer))
+        .unwrap();
+    let bridge: Versioned<NextBridgeData> = harness.data(&bridge_key).unwrap();
+    assert_eq!(bridge.messages, 2);
+    assert_eq!(harness.account(&bridge_key).unwrap().data.len(), 32);
+}
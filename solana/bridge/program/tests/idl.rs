@@ -0,0 +1,27 @@
+//! Clients in other languages are generated from `idl.json`, so any change to the interface of the
+//! program has to be deliberate. After an intended change, regenerate it with:
+//!
+//! UPDATE_IDL=1 cargo test --test idl
+
+use std::{
+    env,
+    fs,
+    path::Path,
+};
+
+#[test]
+fn idl_is_stable() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("idl.json");
+    let idl = serde_json::to_string_pretty(&bridge::instruction::idl()).unwrap() + "\n";
+
+    if env::var("UPDATE_IDL").is_ok() {
+        fs::write(&path, idl).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        expected == idl,
+        "The bridge IDL changed, regenerate idl.json with UPDATE_IDL=1 if this is intended"
+    );
+}
@@ -10,10 +10,12 @@ use solana_program_test::{
 };
 use solana_sdk::{
     commitment_config::CommitmentLevel,
+    instruction::InstructionError,
     signature::{
         Keypair,
         Signer,
     },
+    transaction::TransactionError,
 };
 use solitaire::{
     processors::seeded::Seeded,
@@ -32,16 +34,25 @@ use bridge::{
         PostedVAAData,
         PostedVAADerivationData,
         SignatureSetData,
+        SplFeeConfig,
+        SplFeeConfigData,
+        SplFeeCustody,
+        SplFeeCustodyDerivationData,
     },
+    error::Error,
     instructions,
     types::{
         ConsistencyLevel,
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetGuardianSetExpiration,
         GovernancePayloadSetMessageFee,
+        GovernancePayloadSetSplFee,
         GovernancePayloadTransferFees,
+        GovernancePayloadTransferSplFees,
         GovernancePayloadUpgrade,
     },
     SerializeGovernancePayload,
+    MAX_LEN_GUARDIAN_KEYS,
 };
 use primitive_types::U256;
 use solana_program::rent::Rent;
@@ -863,6 +874,95 @@ async fn guardian_set_change() {
     }
 }
 
+#[tokio::test]
+async fn guardian_set_change_large() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+
+    // Upgrade to the largest guardian set whose upgrade VAA can still be posted, which is larger
+    // than the original fixed size signer map allows.
+    let (new_public_keys, new_secret_keys) = common::generate_keys(MAX_LEN_GUARDIAN_KEYS as u8);
+
+    let nonce = rand::thread_rng().gen();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadGuardianSetChange {
+        new_guardian_set_index: 1,
+        new_guardian_set: new_public_keys.clone(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::upgrade_guardian_set(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        0,
+        1,
+        sequence,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData { index: 1 },
+        program,
+    );
+    let guardian_set: GuardianSetData = common::get_account_data(client, guardian_set_key).await;
+    assert_eq!(guardian_set.index, 1);
+    assert_eq!(guardian_set.keys, new_public_keys);
+
+    context.public = new_public_keys;
+    context.secret = new_secret_keys;
+
+    // Messages signed by the new set can only be verified with the variable length signer map.
+    let message_emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(message_emitter.pubkey().to_bytes());
+    let message = [0u8; 32].to_vec();
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&message_emitter, message.clone(), nonce, sequence, 1, 1);
+    let signature_set =
+        common::verify_signatures_v2(client, program, payer, body, &context.secret, 1)
+            .await
+            .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::sync(client, payer).await;
+
+    // Verify on chain Message and Signatures
+    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
+    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
+    assert_eq!(posted_message.message.payload, message);
+    assert_eq!(signatures.guardian_set_index, 1);
+    assert_eq!(signatures.signatures.len(), MAX_LEN_GUARDIAN_KEYS);
+    assert!(signatures.signatures.iter().all(|signed| *signed));
+}
+
 #[tokio::test]
 async fn guardian_set_change_fails() {
     // Initialize a wormhole bridge on Solana to test with.
@@ -909,6 +1009,211 @@ async fn guardian_set_change_fails() {
     .is_err());
 }
 
+#[tokio::test]
+async fn set_guardian_set_expiration() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+
+    // Upgrade the guardian set so that set 0 becomes an old, but still valid, set.
+    let (new_public_keys, new_secret_keys) = common::generate_keys(1);
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadGuardianSetChange {
+        new_guardian_set_index: 1,
+        new_guardian_set: new_public_keys.clone(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::upgrade_guardian_set(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        0,
+        1,
+        sequence,
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    let old_secret_keys = std::mem::replace(&mut context.secret, new_secret_keys);
+    context.public = new_public_keys;
+
+    // Shorten the expiration period and expire guardian set 0 immediately.
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadSetGuardianSetExpiration {
+        guardian_set_expiration_time: 60,
+        guardian_set_index: 0,
+        expiration_time: 1,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 1, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 1)
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::set_guardian_set_expiration(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+        Some(0),
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    // Confirm the new period is set and the old guardian set has expired.
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData { index: 0 },
+        program,
+    );
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    let guardian_set: GuardianSetData = common::get_account_data(client, guardian_set_key).await;
+    assert_eq!(bridge.guardian_set_index, 1);
+    assert_eq!(bridge.config.guardian_set_expiration_time, 60);
+    assert_eq!(bridge.config.fee, 500);
+    assert_eq!(guardian_set.expiration_time, 1);
+
+    // VAAs signed by the expired guardian set can no longer be posted.
+    let message_emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(message_emitter.pubkey().to_bytes());
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&message_emitter, vec![0u8; 32], nonce, sequence, 0, 1);
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &old_secret_keys, 0)
+            .await
+            .unwrap();
+    assert!(common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .is_err());
+
+    // The expired set can not be revived by moving its expiration back into the future.
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadSetGuardianSetExpiration {
+        guardian_set_expiration_time: 60,
+        guardian_set_index: 0,
+        expiration_time: 2_000_000_000,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 1, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 1)
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    assert!(common::set_guardian_set_expiration(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+        Some(0),
+    )
+    .await
+    .is_err());
+    common::sync(client, payer).await;
+
+    let guardian_set: GuardianSetData = common::get_account_data(client, guardian_set_key).await;
+    assert_eq!(guardian_set.expiration_time, 1);
+}
+
+#[tokio::test]
+async fn set_guardian_set_expiration_fails() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Attempt to expire the currently active guardian set.
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetGuardianSetExpiration {
+        guardian_set_expiration_time: 60,
+        guardian_set_index: 0,
+        expiration_time: 1,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    assert!(common::set_guardian_set_expiration(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+        Some(0),
+    )
+    .await
+    .is_err());
+    common::sync(client, payer).await;
+
+    // Nothing should have changed.
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.config.guardian_set_expiration_time, 2_000_000_000);
+}
+
 #[tokio::test]
 async fn set_fees() {
     // Initialize a wormhole bridge on Solana to test with.
@@ -1424,6 +1729,267 @@ async fn transfer_too_much() {
     );
 }
 
+#[tokio::test]
+async fn spl_fees() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+
+    // Create a token with a funded account for the payer and an empty one to withdraw fees to.
+    let mint = Keypair::new();
+    let from = Keypair::new();
+    let recipient = Keypair::new();
+    common::create_mint(client, payer, &payer.pubkey(), &mint)
+        .await
+        .unwrap();
+    common::create_token_account(client, payer, &from, &payer.pubkey(), &mint.pubkey())
+        .await
+        .unwrap();
+    common::create_token_account(client, payer, &recipient, &payer.pubkey(), &mint.pubkey())
+        .await
+        .unwrap();
+    common::mint_tokens(client, payer, payer, &mint.pubkey(), &from.pubkey(), 1_000)
+        .await
+        .unwrap();
+
+    let spl_fee_config_key =
+        SplFeeConfig::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let fee_custody = SplFeeCustody::<'_, { AccountState::Uninitialized }>::key(
+        &SplFeeCustodyDerivationData {
+            mint: mint.pubkey(),
+        },
+        program,
+    );
+
+    // Enable token fees.
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetSplFee {
+        mint: mint.pubkey(),
+        fee: U256::from(100),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::set_spl_fee(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+        mint.pubkey(),
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    let spl_fee_config: SplFeeConfigData =
+        common::get_account_data(client, spl_fee_config_key).await;
+    assert_eq!(spl_fee_config.mint, mint.pubkey());
+    assert_eq!(spl_fee_config.fee, 100);
+    assert_eq!(spl_fee_config.last_balance, 0);
+    assert_eq!(common::get_token_balance(client, fee_custody).await, 0);
+
+    // Paying less than the fee fails.
+    let message_emitter = Keypair::new();
+    assert!(common::post_message_spl_fee(
+        client,
+        program,
+        payer,
+        &message_emitter,
+        mint.pubkey(),
+        from.pubkey(),
+        0,
+        vec![0u8; 32],
+        99,
+    )
+    .await
+    .is_err());
+
+    // Paying the full fee succeeds, twice in a row to check the tracked balance.
+    for expected_balance in [100, 200] {
+        common::post_message_spl_fee(
+            client,
+            program,
+            payer,
+            &message_emitter,
+            mint.pubkey(),
+            from.pubkey(),
+            0,
+            vec![0u8; 32],
+            100,
+        )
+        .await
+        .unwrap();
+        common::sync(client, payer).await;
+
+        assert_eq!(
+            common::get_token_balance(client, fee_custody).await,
+            expected_balance
+        );
+        let spl_fee_config: SplFeeConfigData =
+            common::get_account_data(client, spl_fee_config_key).await;
+        assert_eq!(spl_fee_config.last_balance, expected_balance);
+    }
+
+    // Withdraw part of the collected fees.
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadTransferSplFees {
+        mint: mint.pubkey(),
+        amount: U256::from(150),
+        to: recipient.pubkey().to_bytes(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::transfer_spl_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+        mint.pubkey(),
+        recipient.pubkey(),
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    assert_eq!(common::get_token_balance(client, fee_custody).await, 50);
+    assert_eq!(
+        common::get_token_balance(client, recipient.pubkey()).await,
+        150
+    );
+    let spl_fee_config: SplFeeConfigData =
+        common::get_account_data(client, spl_fee_config_key).await;
+    assert_eq!(spl_fee_config.last_balance, 50);
+
+    // Withdrawing must not allow posting messages without paying.
+    assert!(common::post_message_spl_fee(
+        client,
+        program,
+        payer,
+        &message_emitter,
+        mint.pubkey(),
+        from.pubkey(),
+        0,
+        vec![0u8; 32],
+        0,
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn spl_fees_disabled() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let mint = Keypair::new();
+    let from = Keypair::new();
+    common::create_mint(client, payer, &payer.pubkey(), &mint)
+        .await
+        .unwrap();
+    common::create_token_account(client, payer, &from, &payer.pubkey(), &mint.pubkey())
+        .await
+        .unwrap();
+    common::mint_tokens(client, payer, payer, &mint.pubkey(), &from.pubkey(), 1_000)
+        .await
+        .unwrap();
+
+    // A zero fee creates the custody account but keeps paying in tokens disabled.
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetSplFee {
+        mint: mint.pubkey(),
+        fee: U256::from(0),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::set_spl_fee(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+        mint.pubkey(),
+    )
+    .await
+    .unwrap();
+    common::sync(client, payer).await;
+
+    let err = common::post_message_spl_fee(
+        client,
+        program,
+        payer,
+        &Keypair::new(),
+        mint.pubkey(),
+        from.pubkey(),
+        0,
+        vec![0u8; 32],
+        100,
+    )
+    .await
+    .unwrap_err();
+
+    // The program error is returned with its code, after the token transfer at index 0.
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(
+            1,
+            InstructionError::Custom(Error::SplFeeNotEnabled.code())
+        )
+    );
+}
+
 #[tokio::test]
 async fn foreign_bridge_messages() {
     // Initialize a wormhole bridge on Solana to test with.
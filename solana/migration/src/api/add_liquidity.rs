@@ -16,6 +16,7 @@ use crate::{
 };
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 
@@ -36,7 +37,7 @@ pub struct AddLiquidity<'b> {
     pub authority_signer: AuthoritySigner<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct AddLiquidityData {
     pub amount: u64,
 }
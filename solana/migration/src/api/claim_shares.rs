@@ -13,6 +13,7 @@ use crate::{
 };
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 
@@ -37,7 +38,7 @@ pub struct ClaimShares<'b> {
     pub authority_signer: AuthoritySigner<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct ClaimSharesData {
     pub amount: u64,
 }
@@ -14,6 +14,7 @@ use crate::{
 };
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 use solana_program::program::invoke_signed;
@@ -36,7 +37,7 @@ pub struct CreatePool<'b> {
     pub custody_signer: CustodySigner<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CreatePoolData {}
 
 pub fn create_pool(
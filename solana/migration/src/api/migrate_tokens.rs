@@ -16,6 +16,7 @@ use crate::{
 };
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 
@@ -42,7 +43,7 @@ pub struct MigrateTokens<'b> {
     pub authority_signer: AuthoritySigner<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct MigrateTokensData {
     pub amount: u64,
 }
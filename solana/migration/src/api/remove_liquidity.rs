@@ -17,6 +17,7 @@ use crate::{
 };
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 use solitaire::{
@@ -41,7 +42,7 @@ pub struct RemoveLiquidity<'b> {
     pub authority_signer: AuthoritySigner<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct RemoveLiquidityData {
     pub amount: u64,
 }
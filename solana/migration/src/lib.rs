@@ -10,7 +10,7 @@ use api::{
 };
 use solitaire::{
     solitaire,
-    SolitaireError,
+    ErrorCode,
 };
 
 pub mod accounts;
@@ -28,16 +28,12 @@ extern crate wasm_bindgen;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 pub mod wasm;
 
+#[derive(ErrorCode)]
+#[error_code(offset = 4000)]
 pub enum MigrationError {
     WrongMint,
 }
 
-impl From<MigrationError> for SolitaireError {
-    fn from(t: MigrationError) -> SolitaireError {
-        SolitaireError::Custom(t as u64)
-    }
-}
-
 solitaire! {
     AddLiquidity => add_liquidity,
     RemoveLiquidity => remove_liquidity,
@@ -1,6 +1,6 @@
 use crate::types::*;
 use bridge::{
-    accounts::BridgeData,
+    accounts::VersionedBridgeData,
     api::ForeignAddress,
 };
 use primitive_types::U256;
@@ -14,7 +14,7 @@ pub type AuthoritySigner<'b> = Derive<Info<'b>, "authority_signer">;
 pub type CustodySigner<'b> = Derive<Info<'b>, "custody_signer">;
 pub type MintSigner<'b> = Derive<Info<'b>, "mint_signer">;
 
-pub type CoreBridge<'a, const STATE: AccountState> = Data<'a, BridgeData, { STATE }>;
+pub type CoreBridge<'a, const STATE: AccountState> = Data<'a, VersionedBridgeData, { STATE }>;
 
 pub type EmitterAccount<'b> = Derive<Info<'b>, "emitter">;
 
@@ -62,8 +62,8 @@ pub struct CompleteNative<'b> {
 impl<'a> From<&CompleteNative<'a>> for EndpointDerivationData {
     fn from(accs: &CompleteNative<'a>) -> Self {
         EndpointDerivationData {
-            emitter_chain: accs.vaa.meta().emitter_chain,
-            emitter_address: accs.vaa.meta().emitter_address,
+            emitter_chain: accs.vaa.meta().emitter_chain(),
+            emitter_address: accs.vaa.meta().emitter_address(),
         }
     }
 }
@@ -76,7 +76,7 @@ impl<'a> From<&CompleteNative<'a>> for CustodyAccountDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CompleteNativeData {}
 
 pub fn complete_native(
@@ -180,8 +180,8 @@ pub struct CompleteWrapped<'b> {
 impl<'a> From<&CompleteWrapped<'a>> for EndpointDerivationData {
     fn from(accs: &CompleteWrapped<'a>) -> Self {
         EndpointDerivationData {
-            emitter_chain: accs.vaa.meta().emitter_chain,
-            emitter_address: accs.vaa.meta().emitter_address,
+            emitter_chain: accs.vaa.meta().emitter_chain(),
+            emitter_address: accs.vaa.meta().emitter_address(),
         }
     }
 }
@@ -204,7 +204,7 @@ impl<'a> From<&CompleteWrapped<'a>> for WrappedMetaDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CompleteWrappedData {}
 
 pub fn complete_wrapped(
@@ -313,8 +313,8 @@ pub struct CompleteWrappedMeta<'b> {
 impl<'a> From<&CompleteWrappedMeta<'a>> for EndpointDerivationData {
     fn from(accs: &CompleteWrappedMeta<'a>) -> Self {
         EndpointDerivationData {
-            emitter_chain: accs.vaa.meta().emitter_chain,
-            emitter_address: accs.vaa.meta().emitter_address,
+            emitter_chain: accs.vaa.meta().emitter_chain(),
+            emitter_address: accs.vaa.meta().emitter_address(),
         }
     }
 }
@@ -337,7 +337,7 @@ impl<'a> From<&CompleteWrappedMeta<'a>> for WrappedMetaDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CompleteWrappedMetaData {}
 
 pub fn complete_wrapped_meta(
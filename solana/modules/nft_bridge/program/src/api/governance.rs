@@ -43,9 +43,9 @@ where
     T: DeserializePayload,
 {
     let expected_emitter = std::env!("EMITTER_ADDRESS");
-    let current_emitter = format!("{}", Pubkey::new_from_array(vaa.meta().emitter_address));
+    let current_emitter = format!("{}", Pubkey::new_from_array(vaa.meta().emitter_address()));
     // Fail if the emitter is not the known governance key, or the emitting chain is not Solana.
-    if expected_emitter != current_emitter || vaa.meta().emitter_chain != CHAIN_ID_SOLANA {
+    if expected_emitter != current_emitter || vaa.meta().emitter_chain() != CHAIN_ID_SOLANA {
         Err(InvalidGovernanceKey.into())
     } else {
         Ok(())
@@ -85,7 +85,7 @@ pub struct UpgradeContract<'b> {
     pub system: Info<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct UpgradeContractData {}
 
 pub fn upgrade_contract(
@@ -131,7 +131,7 @@ impl<'a> From<&RegisterChain<'a>> for EndpointDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct RegisterChainData {}
 
 pub fn register_chain(
@@ -106,7 +106,7 @@ impl<'a> From<&TransferNative<'a>> for SplTokenMetaDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct TransferNativeData {
     pub nonce: u32,
     pub target_address: Address,
@@ -274,7 +274,7 @@ impl<'a> From<&TransferWrapped<'a>> for SplTokenMetaDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct TransferWrappedData {
     pub nonce: u32,
     pub target_address: Address,
@@ -48,6 +48,8 @@ pub use api::{
 
 use solitaire::*;
 
+#[derive(ErrorCode)]
+#[error_code(offset = 3000)]
 pub enum TokenBridgeError {
     AlreadyExecuted,
     InvalidChain,
@@ -64,12 +66,6 @@ pub enum TokenBridgeError {
     InvalidRecipient,
 }
 
-impl From<TokenBridgeError> for SolitaireError {
-    fn from(t: TokenBridgeError) -> SolitaireError {
-        SolitaireError::Custom(t as u64)
-    }
-}
-
 solitaire! {
     Initialize          => initialize,
     CompleteNative      => complete_native,
@@ -3,10 +3,29 @@
 
 use std::{
     fmt::Display,
+    fs,
     mem::size_of,
+    path::Path,
     process::exit,
+    rc::Rc,
 };
 
+use borsh::BorshDeserialize;
+use bridge::{
+    accounts::{
+        GuardianSet,
+        GuardianSetData,
+        GuardianSetDerivationData,
+        PostedVAA,
+        PostedVAAData,
+        PostedVAADerivationData,
+    },
+    instructions::hash_vaa,
+    types::ConsistencyLevel,
+    vaa::VAA,
+    DeserializePayload,
+    CHAIN_ID_SOLANA,
+};
 use clap::{
     crate_description,
     crate_name,
@@ -38,7 +57,10 @@ use solana_sdk::{
         CommitmentConfig,
         CommitmentLevel,
     },
+    instruction::Instruction,
+    message::Message,
     native_token::*,
+    program_pack::Pack,
     pubkey::Pubkey,
     signature::{
         read_keypair_file,
@@ -49,9 +71,45 @@ use solana_sdk::{
 };
 use solitaire::{
     processors::seeded::Seeded,
+    AccountState,
     Derive,
     Info,
 };
+use token_bridge::{
+    accounts::{
+        ConfigAccount,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        Endpoint,
+        EndpointDerivationData,
+        WrappedDerivationData,
+        WrappedMetaDerivationData,
+        WrappedMint,
+        WrappedTokenMeta,
+    },
+    messages::{
+        PayloadAssetMeta,
+        PayloadGovernanceRegisterChain,
+        PayloadTransfer,
+        PayloadTransferWithPayload,
+    },
+    token_2022,
+    types::{
+        EndpointRegistration,
+        TransferConsistency,
+        VersionedWrappedMeta,
+    },
+    CompleteNativeData,
+    CompleteNativeWithPayloadData,
+    CompleteWrappedData,
+    CompleteWrappedWithPayloadData,
+    CreateWrappedData,
+    RegisterChainData,
+    TransferNativeData,
+    TransferNativeWithPayloadData,
+    TransferWrappedData,
+    TransferWrappedWithPayloadData,
+};
 
 struct Config {
     rpc_client: RpcClient,
@@ -60,8 +118,16 @@ struct Config {
     commitment_config: CommitmentConfig,
 }
 
+/// Instructions that have to be executed together in a single transaction, along with the
+/// single-use keypairs (such as new message or signature set accounts) that must sign it next to
+/// the owner and fee payer.
+struct PendingTransaction {
+    instructions: Vec<Instruction>,
+    ephemeral_signers: Vec<Rc<Keypair>>,
+}
+
 type Error = Box<dyn std::error::Error>;
-type CommmandResult = Result<Option<Transaction>, Error>;
+type CommmandResult = Result<Vec<PendingTransaction>, Error>;
 
 // [`get_recent_blockhash`] is deprecated, but devnet deployment hangs using the
 // recommended method, so allowing deprecated here. This is only the client, so
@@ -77,15 +143,20 @@ fn command_init_bridge(config: &Config, bridge: &Pubkey, core_bridge: &Pubkey) -
     let ix = token_bridge::instructions::initialize(*bridge, config.owner.pubkey(), *core_bridge)
         .unwrap();
     println!("config account: {}, ", ix.accounts[1].pubkey);
-    let mut transaction = Transaction::new_with_payer(&[ix], Some(&config.fee_payer.pubkey()));
 
-    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    let (_, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
     check_fee_payer_balance(
         config,
-        minimum_balance_for_rent_exemption + fee_calculator.calculate_fee(transaction.message()),
+        minimum_balance_for_rent_exemption
+            + fee_calculator.calculate_fee(&Message::new(
+                &[ix.clone()],
+                Some(&config.fee_payer.pubkey()),
+            )),
     )?;
-    transaction.sign(&[&config.fee_payer, &config.owner], recent_blockhash);
-    Ok(Some(transaction))
+    Ok(vec![PendingTransaction {
+        instructions: vec![ix],
+        ephemeral_signers: vec![],
+    }])
 }
 
 fn command_create_meta(
@@ -122,11 +193,573 @@ fn command_create_meta(
         false,
         false,
     );
-    let mut transaction = Transaction::new_with_payer(&[ix], Some(&config.fee_payer.pubkey()));
+    Ok(vec![PendingTransaction {
+        instructions: vec![ix],
+        ephemeral_signers: vec![],
+    }])
+}
+
+fn command_attest(config: &Config, bridge: &Pubkey, mint: &Pubkey, nonce: u32) -> CommmandResult {
+    println!("Attesting mint {}", mint);
+
+    let core_bridge = core_bridge(config, bridge)?;
+    let message = Keypair::new();
+    let ix = token_bridge::instructions::attest(
+        *bridge,
+        core_bridge,
+        config.owner.pubkey(),
+        message.pubkey(),
+        *mint,
+        nonce,
+    )
+    .map_err(program_error)?;
+    println!("Message account: {}", message.pubkey());
+
+    Ok(vec![PendingTransaction {
+        instructions: vec![ix],
+        ephemeral_signers: vec![Rc::new(message)],
+    }])
+}
+
+/// Transfers tokens out of `from`, an account owned by the owner. Whether the native or wrapped
+/// flow is used depends on whether the token account holds a wrapped asset of this bridge.
+#[allow(clippy::too_many_arguments)]
+fn command_transfer(
+    config: &Config,
+    bridge: &Pubkey,
+    from: &Pubkey,
+    amount: u64,
+    fee: u64,
+    target_chain: u16,
+    target_address: [u8; 32],
+    nonce: u32,
+    payload: Option<Vec<u8>>,
+    consistency_level: TransferConsistency,
+) -> CommmandResult {
+    let core_bridge = core_bridge(config, bridge)?;
+    let (token_program, token_account) = get_token_account(config, from)?;
+    let mint = token_account.mint;
+
+    let wrapped_meta_key = WrappedTokenMeta::<'_, { AccountState::Initialized }>::key(
+        &WrappedMetaDerivationData { mint_key: mint },
+        bridge,
+    );
+    let wrapped_meta = get_account_data::<VersionedWrappedMeta>(config, &wrapped_meta_key).ok();
+
+    if payload.is_some() && fee != 0 {
+        return Err("Transfers with payload do not support relayer fees".into());
+    }
+
+    let message = Keypair::new();
+    let ix = match (wrapped_meta, payload) {
+        (None, None) => {
+            println!("Transferring {} of native mint {}", amount, mint);
+            token_bridge::instructions::transfer_native(
+                *bridge,
+                core_bridge,
+                config.owner.pubkey(),
+                message.pubkey(),
+                *from,
+                mint,
+                TransferNativeData {
+                    nonce,
+                    amount,
+                    fee,
+                    target_address,
+                    target_chain,
+                    consistency_level: consistency_level.clone(),
+                },
+            )
+        }
+        (None, Some(payload)) => {
+            println!(
+                "Transferring {} of native mint {} with payload",
+                amount, mint
+            );
+            token_bridge::instructions::transfer_native_with_payload(
+                *bridge,
+                core_bridge,
+                config.owner.pubkey(),
+                message.pubkey(),
+                *from,
+                mint,
+                TransferNativeWithPayloadData {
+                    nonce,
+                    amount,
+                    target_address,
+                    target_chain,
+                    payload,
+                    cpi_program_id: None,
+                    consistency_level: consistency_level.clone(),
+                },
+            )
+        }
+        (Some(meta), None) => {
+            println!("Transferring {} of wrapped mint {}", amount, mint);
+            token_bridge::instructions::transfer_wrapped(
+                *bridge,
+                core_bridge,
+                config.owner.pubkey(),
+                message.pubkey(),
+                *from,
+                config.owner.pubkey(),
+                meta.chain,
+                meta.token_address,
+                TransferWrappedData {
+                    nonce,
+                    amount,
+                    fee,
+                    target_address,
+                    target_chain,
+                    consistency_level: consistency_level.clone(),
+                },
+            )
+        }
+        (Some(meta), Some(payload)) => {
+            println!(
+                "Transferring {} of wrapped mint {} with payload",
+                amount, mint
+            );
+            token_bridge::instructions::transfer_wrapped_with_payload(
+                *bridge,
+                core_bridge,
+                config.owner.pubkey(),
+                message.pubkey(),
+                *from,
+                config.owner.pubkey(),
+                meta.chain,
+                meta.token_address,
+                TransferWrappedWithPayloadData {
+                    nonce,
+                    amount,
+                    target_address,
+                    target_chain,
+                    payload,
+                    cpi_program_id: None,
+                    consistency_level: consistency_level.clone(),
+                },
+            )
+        }
+    }
+    .map_err(program_error)?;
+    println!("Message account: {}", message.pubkey());
+
+    // The bridge moves the tokens as the authority signer, which needs to be approved first.
+    let approve_ix = token_2022::with_program(
+        spl_token::instruction::approve(
+            &spl_token::id(),
+            from,
+            &token_bridge::accounts::AuthoritySigner::key(None, bridge),
+            &config.owner.pubkey(),
+            &[],
+            amount,
+        )?,
+        &token_program,
+    );
+
+    Ok(vec![PendingTransaction {
+        instructions: vec![approve_ix, ix],
+        ephemeral_signers: vec![Rc::new(message)],
+    }])
+}
+
+/// Posts a transfer VAA (if not yet posted) and completes the transfer into the token account it
+/// names. Transfers with payload have to be redeemed by the owner of the recipient account.
+fn command_redeem(
+    config: &Config,
+    bridge: &Pubkey,
+    vaa: &VAA,
+    fee_recipient: Option<Pubkey>,
+) -> CommmandResult {
+    let core_bridge = core_bridge(config, bridge)?;
+    let mut transactions = post_vaa(config, &core_bridge, vaa)?;
+    let message = posted_vaa_key(&core_bridge, vaa);
+
+    let ix = match vaa.payload.first() {
+        Some(1) => {
+            let payload =
+                PayloadTransfer::deserialize(&mut vaa.payload.as_slice()).map_err(program_error)?;
+            let to = Pubkey::new(&payload.to);
+            if payload.token_chain == CHAIN_ID_SOLANA {
+                let mint = Pubkey::new(&payload.token_address);
+                println!(
+                    "Completing transfer of {} native {} to {}",
+                    payload.amount, mint, to
+                );
+                let (to, to_owner) = recipient_accounts(config, to, &mint)?;
+                token_bridge::instructions::complete_native(
+                    *bridge,
+                    core_bridge,
+                    config.owner.pubkey(),
+                    message,
+                    vaa.clone().into(),
+                    to,
+                    to_owner,
+                    fee_recipient,
+                    mint,
+                    CompleteNativeData {},
+                )
+            } else {
+                println!(
+                    "Completing transfer of {} wrapped {}:{} to {}",
+                    payload.amount,
+                    payload.token_chain,
+                    hex::encode(payload.token_address),
+                    to
+                );
+                let mint = WrappedMint::<'_, { AccountState::Initialized }>::key(
+                    &WrappedDerivationData {
+                        token_chain: payload.token_chain,
+                        token_address: payload.token_address,
+                    },
+                    bridge,
+                );
+                let (to, to_owner) = recipient_accounts(config, to, &mint)?;
+                token_bridge::instructions::complete_wrapped(
+                    *bridge,
+                    core_bridge,
+                    config.owner.pubkey(),
+                    message,
+                    vaa.clone().into(),
+                    payload,
+                    to,
+                    to_owner,
+                    fee_recipient,
+                    CompleteWrappedData {},
+                )
+            }
+        }
+        Some(3) => {
+            let payload = PayloadTransferWithPayload::deserialize(&mut vaa.payload.as_slice())
+                .map_err(program_error)?;
+            let to = Pubkey::new(&payload.to);
+            if payload.token_chain == CHAIN_ID_SOLANA {
+                let mint = Pubkey::new(&payload.token_address);
+                println!(
+                    "Completing transfer with payload of {} native {} to {}",
+                    payload.amount, mint, to
+                );
+                token_bridge::instructions::complete_native_with_payload(
+                    *bridge,
+                    core_bridge,
+                    config.owner.pubkey(),
+                    message,
+                    vaa.clone().into(),
+                    to,
+                    config.owner.pubkey(),
+                    fee_recipient,
+                    mint,
+                    CompleteNativeWithPayloadData {},
+                )
+            } else {
+                println!(
+                    "Completing transfer with payload of {} wrapped {}:{} to {}",
+                    payload.amount,
+                    payload.token_chain,
+                    hex::encode(payload.token_address),
+                    to
+                );
+                token_bridge::instructions::complete_wrapped_with_payload(
+                    *bridge,
+                    core_bridge,
+                    config.owner.pubkey(),
+                    message,
+                    vaa.clone().into(),
+                    payload,
+                    to,
+                    config.owner.pubkey(),
+                    fee_recipient,
+                    CompleteWrappedWithPayloadData {},
+                )
+            }
+        }
+        _ => return Err("VAA does not contain a token transfer".into()),
+    }
+    .map_err(program_error)?;
+
+    transactions.push(PendingTransaction {
+        instructions: vec![ix],
+        ephemeral_signers: vec![],
+    });
+    Ok(transactions)
+}
+
+fn command_create_wrapped(config: &Config, bridge: &Pubkey, vaa: &VAA) -> CommmandResult {
+    let core_bridge = core_bridge(config, bridge)?;
+    let mut transactions = post_vaa(config, &core_bridge, vaa)?;
+
+    let payload =
+        PayloadAssetMeta::deserialize(&mut vaa.payload.as_slice()).map_err(program_error)?;
+    println!(
+        "Creating wrapped asset for {}:{} ({}, {})",
+        payload.token_chain,
+        hex::encode(payload.token_address),
+        payload.symbol,
+        payload.name
+    );
+    let ix = token_bridge::instructions::create_wrapped(
+        *bridge,
+        core_bridge,
+        config.owner.pubkey(),
+        posted_vaa_key(&core_bridge, vaa),
+        vaa.clone().into(),
+        payload,
+        CreateWrappedData {},
+    )
+    .map_err(program_error)?;
+
+    transactions.push(PendingTransaction {
+        instructions: vec![ix],
+        ephemeral_signers: vec![],
+    });
+    Ok(transactions)
+}
+
+fn command_register_chain(config: &Config, bridge: &Pubkey, vaa: &VAA) -> CommmandResult {
+    let core_bridge = core_bridge(config, bridge)?;
+    let mut transactions = post_vaa(config, &core_bridge, vaa)?;
+
+    let payload = PayloadGovernanceRegisterChain::deserialize(&mut vaa.payload.as_slice())
+        .map_err(program_error)?;
+    println!(
+        "Registering chain {} with endpoint {}",
+        payload.chain,
+        hex::encode(payload.endpoint_address)
+    );
+    let ix = token_bridge::instructions::register_chain(
+        *bridge,
+        core_bridge,
+        config.owner.pubkey(),
+        posted_vaa_key(&core_bridge, vaa),
+        vaa.clone().into(),
+        payload,
+        RegisterChainData {},
+    )
+    .map_err(program_error)?;
+
+    transactions.push(PendingTransaction {
+        instructions: vec![ix],
+        ephemeral_signers: vec![],
+    });
+    Ok(transactions)
+}
+
+fn command_get_wrapped_meta(config: &Config, bridge: &Pubkey, mint: &Pubkey) -> CommmandResult {
+    let wrapped_meta_key = WrappedTokenMeta::<'_, { AccountState::Initialized }>::key(
+        &WrappedMetaDerivationData { mint_key: *mint },
+        bridge,
+    );
+    let wrapped_meta: VersionedWrappedMeta = get_account_data(config, &wrapped_meta_key)?;
+
+    println!("Wrapped meta: {}", wrapped_meta_key);
+    println!("  Token chain: {}", wrapped_meta.chain);
+    println!(
+        "  Token address: {}",
+        hex::encode(wrapped_meta.token_address)
+    );
+    println!("  Original decimals: {}", wrapped_meta.original_decimals);
+    println!(
+        "  Last updated sequence: {}",
+        wrapped_meta.last_updated_sequence
+    );
+    println!("  Last updated: {}", wrapped_meta.last_updated);
+    Ok(vec![])
+}
+
+fn command_get_wrapped_mint(
+    bridge: &Pubkey,
+    token_chain: u16,
+    token_address: [u8; 32],
+) -> CommmandResult {
+    let mint = WrappedMint::<'_, { AccountState::Initialized }>::key(
+        &WrappedDerivationData {
+            token_chain,
+            token_address,
+        },
+        bridge,
+    );
+    println!("Wrapped mint: {}", mint);
+    Ok(vec![])
+}
+
+fn command_get_endpoint(
+    config: &Config,
+    bridge: &Pubkey,
+    emitter_chain: u16,
+    emitter_address: [u8; 32],
+) -> CommmandResult {
+    let endpoint_key = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain,
+            emitter_address,
+        },
+        bridge,
+    );
+    let endpoint: EndpointRegistration = get_account_data(config, &endpoint_key)?;
+
+    println!("Endpoint registration: {}", endpoint_key);
+    println!("  Chain: {}", endpoint.chain);
+    println!("  Contract: {}", hex::encode(endpoint.contract));
+    Ok(vec![])
+}
+
+fn command_get_custody(config: &Config, bridge: &Pubkey, mint: &Pubkey) -> CommmandResult {
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint: *mint },
+        bridge,
+    );
+    let (_, custody) = get_token_account(config, &custody_key)?;
+
+    println!("Custody account: {}", custody_key);
+    println!("  Mint: {}", custody.mint);
+    println!("  Balance: {}", custody.amount);
+    Ok(vec![])
+}
+
+/// Verifies the signatures of a VAA and posts it to the core bridge, unless it has been posted
+/// already.
+fn post_vaa(config: &Config, core_bridge: &Pubkey, vaa: &VAA) -> CommmandResult {
+    let posted_vaa = posted_vaa_key(core_bridge, vaa);
+    if get_account_data::<PostedVAAData>(config, &posted_vaa).is_ok() {
+        println!("VAA already posted at {}", posted_vaa);
+        return Ok(vec![]);
+    }
+    println!("Posting VAA to {}", posted_vaa);
+
+    let guardian_set: GuardianSetData = get_account_data(
+        config,
+        &GuardianSet::<'_, { AccountState::Initialized }>::key(
+            &GuardianSetDerivationData {
+                index: vaa.guardian_set_index,
+            },
+            core_bridge,
+        ),
+    )?;
+
+    let signature_set = Rc::new(Keypair::new());
+    let batches = bridge::instructions::verify_signatures_batches(
+        *core_bridge,
+        config.owner.pubkey(),
+        vaa.guardian_set_index,
+        &guardian_set.keys,
+        signature_set.pubkey(),
+        vaa,
+    )
+    .map_err(program_error)?;
+
+    let mut transactions: Vec<PendingTransaction> = batches
+        .into_iter()
+        .map(|instructions| PendingTransaction {
+            instructions,
+            ephemeral_signers: vec![signature_set.clone()],
+        })
+        .collect();
+    transactions.push(PendingTransaction {
+        instructions: vec![bridge::instructions::post_vaa(
+            *core_bridge,
+            config.owner.pubkey(),
+            signature_set.pubkey(),
+            vaa.clone().into(),
+        )],
+        ephemeral_signers: vec![],
+    });
+    Ok(transactions)
+}
+
+fn posted_vaa_key(core_bridge: &Pubkey, vaa: &VAA) -> Pubkey {
+    PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        core_bridge,
+    )
+}
+
+/// Looks up the core bridge the token bridge was initialized with.
+fn core_bridge(config: &Config, bridge: &Pubkey) -> Result<Pubkey, Error> {
+    let token_bridge_config: token_bridge::types::VersionedConfig = get_account_data(
+        config,
+        &ConfigAccount::<'_, { AccountState::Initialized }>::key(None, bridge),
+    )?;
+    Ok(token_bridge_config.wormhole_bridge)
+}
+
+/// Token account a transfer to `to` is redeemed into. If `to` is a wallet rather than a token
+/// account, that is its associated token account for `mint`, which the redeem creates if needed.
+fn recipient_accounts(
+    config: &Config,
+    to: Pubkey,
+    mint: &Pubkey,
+) -> Result<(Pubkey, Option<Pubkey>), Error> {
+    let is_token_account = config
+        .rpc_client
+        .get_account(&to)
+        .map(|account| account.owner == spl_token::id() || account.owner == token_2022::id())
+        .unwrap_or(false);
+    if is_token_account {
+        return Ok((to, None));
+    }
+
+    let token_program = config.rpc_client.get_account(mint)?.owner;
+    Ok((
+        token_2022::associated_token_address(&to, mint, &token_program),
+        Some(to),
+    ))
+}
 
-    let recent_blockhash = config.rpc_client.get_latest_blockhash()?;
-    transaction.sign(&[&config.fee_payer, &config.owner], recent_blockhash);
-    Ok(Some(transaction))
+/// Token account of either token program along with the program owning it. Only the base layout
+/// is read, Token-2022 accounts may carry extensions after it.
+fn get_token_account(
+    config: &Config,
+    account: &Pubkey,
+) -> Result<(Pubkey, spl_token::state::Account), Error> {
+    let account = config.rpc_client.get_account(account)?;
+    if account.owner != spl_token::id() && account.owner != token_2022::id() {
+        return Err(format!("Not a token account, owned by {}", account.owner).into());
+    }
+    let base = account
+        .data
+        .get(..spl_token::state::Account::LEN)
+        .ok_or("Token account data is too short")?;
+    Ok((account.owner, spl_token::state::Account::unpack(base)?))
+}
+
+fn get_account_data<T: BorshDeserialize>(config: &Config, account: &Pubkey) -> Result<T, Error> {
+    let data = config.rpc_client.get_account_data(account)?;
+    Ok(T::try_from_slice(data.as_slice())?)
+}
+
+fn program_error(e: solitaire::SolitaireError) -> Error {
+    format!("{:?}", e).into()
+}
+
+/// Reads a VAA given either as a hex string or as a path to a file containing the VAA in binary
+/// or hex encoding.
+fn read_vaa(value: &str) -> Result<VAA, Error> {
+    let data = if Path::new(value).is_file() {
+        let contents = fs::read(value)?;
+        match std::str::from_utf8(&contents)
+            .ok()
+            .and_then(|s| hex::decode(s.trim()).ok())
+        {
+            Some(decoded) => decoded,
+            None => contents,
+        }
+    } else {
+        hex::decode(value.trim())?
+    };
+    Ok(VAA::deserialize(&data)?)
+}
+
+/// Parses a hex encoded foreign address, left-padding it with zeroes to 32 bytes.
+fn parse_address(value: &str) -> Result<[u8; 32], Error> {
+    let data = hex::decode(value.trim_start_matches("0x"))?;
+    if data.len() > 32 {
+        return Err(format!("Address {} is longer than 32 bytes", value).into());
+    }
+    let mut address = [0u8; 32];
+    address[32 - data.len()..].copy_from_slice(&data);
+    Ok(address)
 }
 
 fn main() {
@@ -272,6 +905,173 @@ fn main() {
                         .help("URI of the token metadata"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("attest")
+                .about("Attest a token so it can be created as a wrapped asset on other chains")
+                .arg(bridge_arg())
+                .arg(
+                    Arg::with_name("mint")
+                        .validator(is_pubkey_or_keypair)
+                        .value_name("MINT")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Mint of the token to attest"),
+                )
+                .arg(nonce_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("transfer")
+                .about("Transfer tokens to another chain")
+                .arg(bridge_arg())
+                .arg(
+                    Arg::with_name("from")
+                        .validator(is_pubkey_or_keypair)
+                        .value_name("FROM")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Token account owned by the owner to transfer from"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .validator(is_u64)
+                        .value_name("AMOUNT")
+                        .takes_value(true)
+                        .index(3)
+                        .required(true)
+                        .help("Amount to transfer in the smallest unit of the token"),
+                )
+                .arg(
+                    Arg::with_name("target_chain")
+                        .validator(is_u16)
+                        .value_name("TARGET_CHAIN")
+                        .takes_value(true)
+                        .index(4)
+                        .required(true)
+                        .help("Wormhole chain id of the recipient"),
+                )
+                .arg(
+                    Arg::with_name("target_address")
+                        .validator(is_hex)
+                        .value_name("TARGET_ADDRESS")
+                        .takes_value(true)
+                        .index(5)
+                        .required(true)
+                        .help("Hex encoded address of the recipient, left-padded to 32 bytes"),
+                )
+                .arg(
+                    Arg::with_name("fee")
+                        .long("fee")
+                        .validator(is_u64)
+                        .value_name("FEE")
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("Relayer fee, taken from the amount"),
+                )
+                .arg(
+                    Arg::with_name("payload")
+                        .long("payload")
+                        .validator(is_hex)
+                        .value_name("PAYLOAD")
+                        .takes_value(true)
+                        .help(
+                            "Hex encoded payload to deliver to the recipient along with the tokens",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("consistency_level")
+                        .long("consistency-level")
+                        .value_name("CONSISTENCY_LEVEL")
+                        .takes_value(true)
+                        .possible_values(&["finalized", "confirmed"])
+                        .default_value("finalized")
+                        .help("Consistency level at which the VAA should be produced"),
+                )
+                .arg(nonce_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("redeem")
+                .about("Post a transfer VAA and complete the transfer")
+                .arg(bridge_arg())
+                .arg(vaa_arg())
+                .arg(
+                    Arg::with_name("fee_recipient")
+                        .long("fee-recipient")
+                        .validator(is_pubkey_or_keypair)
+                        .value_name("FEE_RECIPIENT")
+                        .takes_value(true)
+                        .help(
+                            "Token account receiving the relayer fee. Defaults to the recipient.",
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("create-wrapped")
+                .about("Post an attestation VAA and create or update the wrapped asset")
+                .arg(bridge_arg())
+                .arg(vaa_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("register-chain")
+                .about("Post a chain registration VAA and register the token bridge endpoint")
+                .arg(bridge_arg())
+                .arg(vaa_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("get-wrapped-meta")
+                .about("Print the origin of a wrapped asset")
+                .arg(bridge_arg())
+                .arg(
+                    Arg::with_name("mint")
+                        .validator(is_pubkey_or_keypair)
+                        .value_name("MINT")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Mint of the wrapped asset"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("get-wrapped-mint")
+                .about("Get the derived mint of a wrapped asset")
+                .arg(bridge_arg())
+                .arg(chain_arg(
+                    "token_chain",
+                    "TOKEN_CHAIN",
+                    "Chain id of the token",
+                ))
+                .arg(address_arg(
+                    "token_address",
+                    "TOKEN_ADDRESS",
+                    "Hex encoded address of the token",
+                )),
+        )
+        .subcommand(
+            SubCommand::with_name("get-endpoint")
+                .about("Print the registered token bridge endpoint of a chain")
+                .arg(bridge_arg())
+                .arg(chain_arg("chain", "CHAIN", "Chain id of the endpoint"))
+                .arg(address_arg(
+                    "address",
+                    "ADDRESS",
+                    "Hex encoded address of the endpoint",
+                )),
+        )
+        .subcommand(
+            SubCommand::with_name("get-custody")
+                .about("Print the custody account of a native token")
+                .arg(bridge_arg())
+                .arg(
+                    Arg::with_name("mint")
+                        .validator(is_pubkey_or_keypair)
+                        .value_name("MINT")
+                        .takes_value(true)
+                        .index(2)
+                        .required(true)
+                        .help("Mint of the native token"),
+                ),
+        )
         .get_matches();
 
     let config = {
@@ -321,7 +1121,7 @@ fn main() {
             let emitter = <Derive<Info<'_>, "emitter">>::key(None, &bridge);
             println!("Emitter Key: {}", emitter);
 
-            Ok(None)
+            Ok(vec![])
         }
         ("metadata", Some(arg_matches)) => {
             let mint = pubkey_of(arg_matches, "mint").unwrap();
@@ -346,30 +1146,92 @@ fn main() {
             println!("URI: {}", meta_info.data.uri);
             println!("Mutable: {}", meta_info.is_mutable);
 
-            Ok(None)
+            Ok(vec![])
+        }
+        ("attest", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let mint = pubkey_of(arg_matches, "mint").unwrap();
+            let nonce: u32 = value_of(arg_matches, "nonce").unwrap();
+
+            command_attest(&config, &bridge, &mint, nonce)
+        }
+        ("transfer", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let from = pubkey_of(arg_matches, "from").unwrap();
+            let amount: u64 = value_of(arg_matches, "amount").unwrap();
+            let fee: u64 = value_of(arg_matches, "fee").unwrap();
+            let target_chain: u16 = value_of(arg_matches, "target_chain").unwrap();
+            let nonce: u32 = value_of(arg_matches, "nonce").unwrap();
+            let payload = arg_matches
+                .value_of("payload")
+                .map(|p| hex::decode(p).unwrap());
+            let consistency_level = match arg_matches.value_of("consistency_level").unwrap() {
+                "confirmed" => ConsistencyLevel::Confirmed,
+                _ => ConsistencyLevel::Finalized,
+            };
+
+            parse_address(arg_matches.value_of("target_address").unwrap()).and_then(
+                |target_address| {
+                    command_transfer(
+                        &config,
+                        &bridge,
+                        &from,
+                        amount,
+                        fee,
+                        target_chain,
+                        target_address,
+                        nonce,
+                        payload,
+                        consistency_level.into(),
+                    )
+                },
+            )
+        }
+        ("redeem", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let fee_recipient = pubkey_of(arg_matches, "fee_recipient");
+            read_vaa(arg_matches.value_of("vaa").unwrap())
+                .and_then(|vaa| command_redeem(&config, &bridge, &vaa, fee_recipient))
+        }
+        ("create-wrapped", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            read_vaa(arg_matches.value_of("vaa").unwrap())
+                .and_then(|vaa| command_create_wrapped(&config, &bridge, &vaa))
+        }
+        ("register-chain", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            read_vaa(arg_matches.value_of("vaa").unwrap())
+                .and_then(|vaa| command_register_chain(&config, &bridge, &vaa))
+        }
+        ("get-wrapped-meta", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let mint = pubkey_of(arg_matches, "mint").unwrap();
+
+            command_get_wrapped_meta(&config, &bridge, &mint)
+        }
+        ("get-wrapped-mint", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let token_chain: u16 = value_of(arg_matches, "token_chain").unwrap();
+            parse_address(arg_matches.value_of("token_address").unwrap()).and_then(
+                |token_address| command_get_wrapped_mint(&bridge, token_chain, token_address),
+            )
+        }
+        ("get-endpoint", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let chain: u16 = value_of(arg_matches, "chain").unwrap();
+            parse_address(arg_matches.value_of("address").unwrap())
+                .and_then(|address| command_get_endpoint(&config, &bridge, chain, address))
+        }
+        ("get-custody", Some(arg_matches)) => {
+            let bridge = pubkey_of(arg_matches, "bridge").unwrap();
+            let mint = pubkey_of(arg_matches, "mint").unwrap();
+
+            command_get_custody(&config, &bridge, &mint)
         }
 
         _ => unreachable!(),
     }
-    .and_then(|transaction| {
-        if let Some(transaction) = transaction {
-            let signature = config
-                .rpc_client
-                .send_and_confirm_transaction_with_spinner_and_config(
-                    &transaction,
-                    config.commitment_config,
-                    RpcSendTransactionConfig {
-                        skip_preflight: true,
-                        preflight_commitment: None,
-                        encoding: None,
-                        max_retries: None,
-                        min_context_slot: None,
-                    },
-                )?;
-            println!("Signature: {}", signature);
-        }
-        Ok(())
-    })
+    .and_then(|transactions| send_transactions(&config, transactions))
     .map_err(|err| {
         eprintln!("{}", err);
         exit(1);
@@ -390,6 +1252,20 @@ where
     }
 }
 
+pub fn is_u16<T>(amount: T) -> Result<(), String>
+where
+    T: AsRef<str> + Display,
+{
+    if amount.as_ref().parse::<u16>().is_ok() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unable to parse input amount as integer, provided: {}",
+            amount
+        ))
+    }
+}
+
 pub fn is_u32<T>(amount: T) -> Result<(), String>
 where
     T: AsRef<str> + Display,
@@ -427,6 +1303,101 @@ where
         .map_err(|e| format!("{}", e))
 }
 
+fn bridge_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("bridge")
+        .long("bridge")
+        .value_name("BRIDGE_KEY")
+        .validator(is_pubkey_or_keypair)
+        .takes_value(true)
+        .index(1)
+        .required(true)
+        .help("Specify the token bridge program address")
+}
+
+fn vaa_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("vaa")
+        .value_name("VAA")
+        .takes_value(true)
+        .index(2)
+        .required(true)
+        .help("Hex encoded VAA, or a file containing the VAA in binary or hex encoding")
+}
+
+fn nonce_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("nonce")
+        .long("nonce")
+        .validator(is_u32)
+        .value_name("NONCE")
+        .takes_value(true)
+        .default_value("0")
+        .help("Nonce of the emitted message")
+}
+
+fn chain_arg<'a, 'b>(name: &'a str, value_name: &'a str, help: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .validator(is_u16)
+        .value_name(value_name)
+        .takes_value(true)
+        .index(2)
+        .required(true)
+        .help(help)
+}
+
+fn address_arg<'a, 'b>(name: &'a str, value_name: &'a str, help: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .validator(is_hex)
+        .value_name(value_name)
+        .takes_value(true)
+        .index(3)
+        .required(true)
+        .help(help)
+}
+
+// [`get_recent_blockhash`] is deprecated, but devnet deployment hangs using the
+// recommended method, so allowing deprecated here. This is only the client, so
+// no risk.
+#[allow(deprecated)]
+fn send_transactions(config: &Config, transactions: Vec<PendingTransaction>) -> Result<(), Error> {
+    for pending in transactions {
+        let mut transaction =
+            Transaction::new_with_payer(&pending.instructions, Some(&config.fee_payer.pubkey()));
+        let (recent_blockhash, _) = config.rpc_client.get_recent_blockhash()?;
+
+        // Only sign with the keypairs the transaction actually requires.
+        let required_signers = &transaction.message.account_keys
+            [..transaction.message.header.num_required_signatures as usize];
+        let mut keypairs: Vec<&Keypair> = Vec::new();
+        let available = [&config.fee_payer, &config.owner]
+            .iter()
+            .copied()
+            .chain(pending.ephemeral_signers.iter().map(|k| k.as_ref()));
+        for keypair in available {
+            let pubkey = keypair.pubkey();
+            if required_signers.contains(&pubkey) && !keypairs.iter().any(|k| k.pubkey() == pubkey)
+            {
+                keypairs.push(keypair);
+            }
+        }
+        transaction.try_sign(&keypairs, recent_blockhash)?;
+
+        let signature = config
+            .rpc_client
+            .send_and_confirm_transaction_with_spinner_and_config(
+                &transaction,
+                config.commitment_config,
+                RpcSendTransactionConfig {
+                    skip_preflight: true,
+                    preflight_commitment: None,
+                    encoding: None,
+                    max_retries: None,
+                    min_context_slot: None,
+                },
+            )?;
+        println!("Signature: {}", signature);
+    }
+    Ok(())
+}
+
 fn check_fee_payer_balance(config: &Config, required_balance: u64) -> Result<(), Error> {
     let balance = config
         .rpc_client
@@ -1,6 +1,6 @@
 use crate::types::*;
 use bridge::{
-    accounts::BridgeData,
+    accounts::VersionedBridgeData,
     api::ForeignAddress,
 };
 use solana_program::pubkey::Pubkey;
@@ -13,14 +13,32 @@ pub type AuthoritySigner<'b> = Derive<Info<'b>, "authority_signer">;
 pub type CustodySigner<'b> = Derive<Info<'b>, "custody_signer">;
 pub type MintSigner<'b> = Derive<Info<'b>, "mint_signer">;
 
-pub type CoreBridge<'a, const STATE: AccountState> = Data<'a, BridgeData, { STATE }>;
+/// Signs the invocation of a recipient program, see [`crate::api::complete_transfer_callback`].
+/// Derived from the recipient, so that it cannot forward the signature to another program.
+pub type CallbackAuthority<'b> = Info<'b>;
+
+pub struct CallbackAuthorityDerivationData {
+    pub recipient_program: Pubkey,
+}
+
+impl<'b> Seeded<&CallbackAuthorityDerivationData> for CallbackAuthority<'b> {
+    fn seeds(data: &CallbackAuthorityDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            String::from("callback_authority").as_bytes().to_vec(),
+            data.recipient_program.to_bytes().to_vec(),
+        ]
+    }
+}
+
+pub type CoreBridge<'a, const STATE: AccountState> = Data<'a, VersionedBridgeData, { STATE }>;
 
 pub type EmitterAccount<'b> = Derive<Info<'b>, "emitter">;
 
 pub type ConfigAccount<'b, const STATE: AccountState> =
-    Derive<Data<'b, Config, { STATE }>, "config">;
+    Derive<Data<'b, VersionedConfig, { STATE }>, "config">;
 
-pub type CustodyAccount<'b, const STATE: AccountState> = Data<'b, SplAccount, { STATE }>;
+/// Custody of a native mint, owned by the token program of the mint
+pub type CustodyAccount<'b, const STATE: AccountState> = Data<'b, TokenAccount, { STATE }>;
 
 pub struct CustodyAccountDerivationData {
     pub mint: Pubkey,
@@ -34,6 +52,25 @@ impl<'b, const STATE: AccountState> Seeded<&CustodyAccountDerivationData>
     }
 }
 
+/// Token account native SOL is unwrapped through when completing a transfer, closed within the
+/// same instruction
+pub type SolUnwrapAccount<'b, const STATE: AccountState> = Data<'b, SplAccount, { STATE }>;
+
+pub struct SolUnwrapDerivationData {
+    pub vaa: Pubkey,
+}
+
+impl<'b, const STATE: AccountState> Seeded<&SolUnwrapDerivationData>
+    for SolUnwrapAccount<'b, { STATE }>
+{
+    fn seeds(data: &SolUnwrapDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            String::from("sol").as_bytes().to_vec(),
+            data.vaa.to_bytes().to_vec(),
+        ]
+    }
+}
+
 pub type WrappedMint<'b, const STATE: AccountState> = Data<'b, SplMint, { STATE }>;
 
 pub struct WrappedDerivationData {
@@ -51,7 +88,8 @@ impl<'b, const STATE: AccountState> Seeded<&WrappedDerivationData> for WrappedMi
     }
 }
 
-pub type WrappedTokenMeta<'b, const STATE: AccountState> = Data<'b, WrappedMeta, { STATE }>;
+pub type WrappedTokenMeta<'b, const STATE: AccountState> =
+    Data<'b, VersionedWrappedMeta, { STATE }>;
 
 pub struct WrappedMetaDerivationData {
     pub mint_key: Pubkey,
@@ -86,6 +124,78 @@ impl<'b, const STATE: AccountState> Seeded<&EndpointDerivationData> for Endpoint
     }
 }
 
+/// Outflow limit of a mint
+pub type TokenGovernor<'b, const STATE: AccountState> = Data<'b, TokenLimit, { STATE }>;
+
+pub struct TokenGovernorDerivationData {
+    pub mint: Pubkey,
+}
+
+impl<'b, const STATE: AccountState> Seeded<&TokenGovernorDerivationData>
+    for TokenGovernor<'b, { STATE }>
+{
+    fn seeds(data: &TokenGovernorDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            String::from("governor").as_bytes().to_vec(),
+            data.mint.to_bytes().to_vec(),
+        ]
+    }
+}
+
+/// Outflow limit towards a chain
+pub type ChainGovernor<'b, const STATE: AccountState> = Data<'b, ChainLimit, { STATE }>;
+
+pub struct ChainGovernorDerivationData {
+    pub chain: ChainID,
+}
+
+impl<'b, const STATE: AccountState> Seeded<&ChainGovernorDerivationData>
+    for ChainGovernor<'b, { STATE }>
+{
+    fn seeds(data: &ChainGovernorDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            String::from("governor").as_bytes().to_vec(),
+            data.chain.to_be_bytes().to_vec(),
+        ]
+    }
+}
+
+/// Transfer queued by the governor, keyed by the account its message will be posted to
+pub type PendingTransferAccount<'b, const STATE: AccountState> =
+    Data<'b, PendingTransfer, { STATE }>;
+
+pub struct PendingTransferDerivationData {
+    pub message: Pubkey,
+}
+
+impl<'b, const STATE: AccountState> Seeded<&PendingTransferDerivationData>
+    for PendingTransferAccount<'b, { STATE }>
+{
+    fn seeds(data: &PendingTransferDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            String::from("pending").as_bytes().to_vec(),
+            data.message.to_bytes().to_vec(),
+        ]
+    }
+}
+
+/// Account the message of a queued transfer is posted to by [`crate::api::release_transfer`], so
+/// that releasing it needs no signature from whoever queued it.
+pub type ReleasedMessage<'b> = Info<'b>;
+
+pub struct ReleasedMessageDerivationData {
+    pub pending: Pubkey,
+}
+
+impl<'b> Seeded<&ReleasedMessageDerivationData> for ReleasedMessage<'b> {
+    fn seeds(data: &ReleasedMessageDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            String::from("released").as_bytes().to_vec(),
+            data.pending.to_bytes().to_vec(),
+        ]
+    }
+}
+
 pub type SplTokenMeta<'b> = Info<'b>;
 
 pub struct SplTokenMetaDerivationData {
@@ -1,17 +1,23 @@
 pub mod attest;
 pub mod complete_transfer;
+pub mod complete_transfer_callback;
 pub mod complete_transfer_payload;
 pub mod create_wrapped;
 pub mod governance;
+pub mod governor;
 pub mod initialize;
 pub mod transfer;
+pub mod transfer_batch;
 pub mod transfer_payload;
 
 pub use attest::*;
 pub use complete_transfer::*;
+pub use complete_transfer_callback::*;
 pub use complete_transfer_payload::*;
 pub use create_wrapped::*;
 pub use governance::*;
+pub use governor::*;
 pub use initialize::*;
 pub use transfer::*;
+pub use transfer_batch::*;
 pub use transfer_payload::*;
@@ -9,6 +9,7 @@ use crate::{
         WrappedTokenMeta,
     },
     messages::PayloadAssetMeta,
+    token_2022,
     types::*,
     TokenBridgeError::*,
 };
@@ -42,8 +43,8 @@ pub struct AttestToken<'b> {
 
     pub config: Mut<ConfigAccount<'b, { AccountState::Initialized }>>,
 
-    /// Mint to attest
-    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    /// Mint to attest, of either token program, see [`token_2022`]
+    pub mint: Data<'b, TokenMint, { AccountState::Initialized }>,
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Uninitialized }>,
 
     /// SPL Metadata for the associated Mint
@@ -83,7 +84,7 @@ impl<'a> From<&AttestToken<'a>> for SplTokenMetaDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct AttestTokenData {
     pub nonce: u32,
 }
@@ -102,6 +103,10 @@ pub fn attest_token(
 
     invoke(&transfer_ix, ctx.accounts)?;
 
+    // Only attest tokens that can be held in custody once transferred.
+    token_2022::token_program(accs.mint.info(), &[])?;
+    token_2022::verify_custody_compatible(&token_2022::mint_extensions(accs.mint.info())?)?;
+
     // Enfoce wrapped meta to be uninitialized.
     let derivation_data: WrappedMetaDerivationData = (&*accs).into();
     accs.wrapped_meta
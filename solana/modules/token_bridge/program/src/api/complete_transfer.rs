@@ -7,12 +7,15 @@ use crate::{
         Endpoint,
         EndpointDerivationData,
         MintSigner,
+        SolUnwrapAccount,
+        SolUnwrapDerivationData,
         WrappedDerivationData,
         WrappedMetaDerivationData,
         WrappedMint,
         WrappedTokenMeta,
     },
     messages::PayloadTransfer,
+    token_2022,
     types::*,
     TokenBridgeError::*,
     INVALID_VAAS,
@@ -25,14 +28,25 @@ use bridge::{
     PayloadMessage,
     CHAIN_ID_SOLANA,
 };
-use solana_program::account_info::AccountInfo;
+use solana_program::{
+    account_info::AccountInfo,
+    program::{
+        invoke,
+        invoke_signed,
+    },
+    pubkey::Pubkey,
+    system_program,
+};
 use solitaire::{
     processors::seeded::{
         invoke_seeded,
         Seeded,
     },
+    CreationLamports::Exempt,
     *,
 };
+use spl_token::state::Account;
+use std::ops::Deref;
 
 #[derive(FromAccounts)]
 pub struct CompleteNative<'b> {
@@ -43,19 +57,26 @@ pub struct CompleteNative<'b> {
     pub claim: Mut<Claim<'b>>,
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
-    pub to: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
-    pub to_fees: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    /// Created if the VAA is sent to a wallet, see [`verify_recipient`]
+    pub to: Mut<Data<'b, TokenAccount, { AccountState::MaybeInitialized }>>,
+    /// Only left uninitialized if it is `to`
+    pub to_fees: Mut<Data<'b, TokenAccount, { AccountState::MaybeInitialized }>>,
     pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
-    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    /// Mint of either token program, see [`token_2022`]
+    pub mint: Data<'b, TokenMint, { AccountState::Initialized }>,
 
     pub custody_signer: CustodySigner<'b>,
+
+    /// Wallet the VAA is sent to if `to` is its associated token account. Last so that accounts
+    /// keep their positions for existing clients, it is only read for transfers to a wallet.
+    pub to_owner: MaybeMut<Info<'b>>,
 }
 
 impl<'a> From<&CompleteNative<'a>> for EndpointDerivationData {
     fn from(accs: &CompleteNative<'a>) -> Self {
         EndpointDerivationData {
-            emitter_chain: accs.vaa.meta().emitter_chain,
-            emitter_address: accs.vaa.meta().emitter_address,
+            emitter_chain: accs.vaa.meta().emitter_chain(),
+            emitter_address: accs.vaa.meta().emitter_address(),
         }
     }
 }
@@ -68,7 +89,7 @@ impl<'a> From<&CompleteNative<'a>> for CustodyAccountDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CompleteNativeData {}
 
 pub fn complete_native(
@@ -76,6 +97,10 @@ pub fn complete_native(
     accs: &mut CompleteNative,
     _data: CompleteNativeData,
 ) -> Result<()> {
+    if accs.config.paused {
+        return Err(Paused.into());
+    }
+
     // Verify the chain registration
     let derivation_data: EndpointDerivationData = (&*accs).into();
     accs.chain_registration
@@ -87,18 +112,21 @@ pub fn complete_native(
         .verify_derivation(ctx.program_id, &derivation_data)?;
 
     // Verify mints
-    if *accs.mint.info().key != accs.to.mint {
-        return Err(InvalidMint.into());
-    }
-    if *accs.mint.info().key != accs.to_fees.mint {
-        return Err(InvalidMint.into());
-    }
+    verify_recipient_mints(&accs.to, &accs.to_fees, accs.mint.info().key)?;
     if *accs.mint.info().key != accs.custody.mint {
         return Err(InvalidMint.into());
     }
     if *accs.custody_signer.key != accs.custody.owner {
         return Err(WrongAccountOwner.into());
     }
+    let mut token_accounts = vec![accs.custody.info()];
+    if accs.to.is_initialized() {
+        token_accounts.push(accs.to.info());
+    }
+    if accs.to_fees.is_initialized() {
+        token_accounts.push(accs.to_fees.info());
+    }
+    let token_program = token_2022::token_program(accs.mint.info(), &token_accounts)?;
 
     // Verify VAA
     if accs.vaa.token_address != accs.mint.info().key.to_bytes() {
@@ -110,9 +138,13 @@ pub fn complete_native(
     if accs.vaa.to_chain != CHAIN_ID_SOLANA {
         return Err(InvalidChain.into());
     }
-    if accs.vaa.to != accs.to.info().key.to_bytes() {
-        return Err(InvalidRecipient.into());
-    }
+    verify_recipient(
+        &accs.vaa.to,
+        &accs.to,
+        &accs.to_owner,
+        accs.mint.info().key,
+        &token_program,
+    )?;
     if INVALID_VAAS.contains(&&*accs.vaa.info().key.to_string()) {
         return Err(InvalidVAA.into());
     }
@@ -120,6 +152,16 @@ pub fn complete_native(
     // Prevent vaa double signing
     claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
 
+    if !accs.to.is_initialized() {
+        create_recipient_account(
+            ctx,
+            accs.payer.key,
+            accs.to_owner.key,
+            accs.mint.info().key,
+            &token_program,
+        )?;
+    }
+
     let mut amount = accs.vaa.amount.as_u64();
     let mut fee = accs.vaa.fee.as_u64();
 
@@ -134,27 +176,202 @@ pub fn complete_native(
         .ok_or(SolitaireError::InsufficientFunds)?;
 
     // Transfer tokens
-    let transfer_ix = spl_token::instruction::transfer(
+    let transfer_ix = spl_token::instruction::transfer_checked(
         &spl_token::id(),
         accs.custody.info().key,
+        accs.mint.info().key,
         accs.to.info().key,
         accs.custody_signer.key,
         &[],
         token_amount,
+        accs.mint.decimals,
+    )?;
+    invoke_seeded(
+        &token_2022::with_program(transfer_ix, &token_program),
+        ctx,
+        &accs.custody_signer,
+        None,
     )?;
-    invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
 
     // Transfer fees
-    let transfer_ix = spl_token::instruction::transfer(
+    let transfer_ix = spl_token::instruction::transfer_checked(
         &spl_token::id(),
         accs.custody.info().key,
+        accs.mint.info().key,
         accs.to_fees.info().key,
         accs.custody_signer.key,
         &[],
         fee,
+        accs.mint.decimals,
+    )?;
+    invoke_seeded(
+        &token_2022::with_program(transfer_ix, &token_program),
+        ctx,
+        &accs.custody_signer,
+        None,
+    )?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct CompleteNativeSol<'b> {
+    /// Receives the fee in lamports
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub vaa: PayloadMessage<'b, PayloadTransfer>,
+    pub claim: Mut<Claim<'b>>,
+    pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
+
+    /// System account of the recipient, receives the lamports
+    pub to: Mut<Info<'b>>,
+    pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
+    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+
+    pub custody_signer: CustodySigner<'b>,
+
+    pub unwrap: Mut<SolUnwrapAccount<'b, { AccountState::Uninitialized }>>,
+}
+
+impl<'a> From<&CompleteNativeSol<'a>> for EndpointDerivationData {
+    fn from(accs: &CompleteNativeSol<'a>) -> Self {
+        EndpointDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain(),
+            emitter_address: accs.vaa.meta().emitter_address(),
+        }
+    }
+}
+
+impl<'a> From<&CompleteNativeSol<'a>> for CustodyAccountDerivationData {
+    fn from(accs: &CompleteNativeSol<'a>) -> Self {
+        CustodyAccountDerivationData {
+            mint: *accs.mint.info().key,
+        }
+    }
+}
+
+impl<'a> From<&CompleteNativeSol<'a>> for SolUnwrapDerivationData {
+    fn from(accs: &CompleteNativeSol<'a>) -> Self {
+        SolUnwrapDerivationData {
+            vaa: *accs.vaa.info().key,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct CompleteNativeSolData {}
+
+/// Complete a transfer of WSOL to a system account, paying out lamports. The WSOL is moved from
+/// custody into a temporary token account, which is closed into the payer, who then pays the
+/// recipient and keeps the fee.
+pub fn complete_native_sol(
+    ctx: &ExecutionContext,
+    accs: &mut CompleteNativeSol,
+    _data: CompleteNativeSolData,
+) -> Result<()> {
+    if accs.config.paused {
+        return Err(Paused.into());
+    }
+
+    // Verify the chain registration
+    let derivation_data: EndpointDerivationData = (&*accs).into();
+    accs.chain_registration
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify that the custody account is derived correctly
+    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
+    accs.custody
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    let unwrap_derivation_data: SolUnwrapDerivationData = (&*accs).into();
+    accs.unwrap
+        .verify_derivation(ctx.program_id, &unwrap_derivation_data)?;
+
+    // Verify mints
+    if *accs.mint.info().key != spl_token::native_mint::id() {
+        return Err(InvalidMint.into());
+    }
+    if *accs.mint.info().key != accs.custody.mint {
+        return Err(InvalidMint.into());
+    }
+    if *accs.custody_signer.key != accs.custody.owner {
+        return Err(WrongAccountOwner.into());
+    }
+
+    // Lamports sent to a token account would not be part of its balance.
+    if *accs.to.owner != system_program::id() {
+        return Err(InvalidRecipient.into());
+    }
+
+    // Verify VAA
+    if accs.vaa.token_address != accs.mint.info().key.to_bytes() {
+        return Err(InvalidMint.into());
+    }
+    if accs.vaa.token_chain != 1 {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to != accs.to.key.to_bytes() {
+        return Err(InvalidRecipient.into());
+    }
+    if INVALID_VAAS.contains(&&*accs.vaa.info().key.to_string()) {
+        return Err(InvalidVAA.into());
+    }
+
+    // Prevent vaa double signing
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let mut amount = accs.vaa.amount.as_u64();
+    let mut fee = accs.vaa.fee.as_u64();
+
+    // Wormhole always caps transfers at 8 decimals; un-truncate if the local token has more
+    if accs.mint.decimals > 8 {
+        amount *= 10u64.pow((accs.mint.decimals - 8) as u32);
+        fee *= 10u64.pow((accs.mint.decimals - 8) as u32);
+    }
+
+    let token_amount = amount
+        .checked_sub(fee)
+        .ok_or(SolitaireError::InsufficientFunds)?;
+
+    // Unwrap through a temporary token account, its rent goes back to the payer on close
+    accs.unwrap
+        .create(&unwrap_derivation_data, ctx, accs.payer.key, Exempt)?;
+    let init_ix = spl_token::instruction::initialize_account(
+        &spl_token::id(),
+        accs.unwrap.info().key,
+        accs.mint.info().key,
+        accs.custody_signer.key,
+    )?;
+    invoke_signed(&init_ix, ctx.accounts, &[])?;
+
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        accs.custody.info().key,
+        accs.unwrap.info().key,
+        accs.custody_signer.key,
+        &[],
+        amount,
     )?;
     invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
 
+    let close_ix = spl_token::instruction::close_account(
+        &spl_token::id(),
+        accs.unwrap.info().key,
+        accs.payer.key,
+        accs.custody_signer.key,
+        &[],
+    )?;
+    invoke_seeded(&close_ix, ctx, &accs.custody_signer, None)?;
+
+    // Pay the recipient, the fee stays with the payer
+    let transfer_ix =
+        solana_program::system_instruction::transfer(accs.payer.key, accs.to.key, token_amount);
+    invoke(&transfer_ix, ctx.accounts)?;
+
     Ok(())
 }
 
@@ -169,19 +386,25 @@ pub struct CompleteWrapped<'b> {
 
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
-    pub to: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
-    pub to_fees: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    /// Created if the VAA is sent to a wallet, see [`verify_recipient`]
+    pub to: Mut<Data<'b, SplAccount, { AccountState::MaybeInitialized }>>,
+    /// Only left uninitialized if it is `to`
+    pub to_fees: Mut<Data<'b, SplAccount, { AccountState::MaybeInitialized }>>,
     pub mint: Mut<WrappedMint<'b, { AccountState::Initialized }>>,
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
 
     pub mint_authority: MintSigner<'b>,
+
+    /// Wallet the VAA is sent to if `to` is its associated token account. Last so that accounts
+    /// keep their positions for existing clients, it is only read for transfers to a wallet.
+    pub to_owner: MaybeMut<Info<'b>>,
 }
 
 impl<'a> From<&CompleteWrapped<'a>> for EndpointDerivationData {
     fn from(accs: &CompleteWrapped<'a>) -> Self {
         EndpointDerivationData {
-            emitter_chain: accs.vaa.meta().emitter_chain,
-            emitter_address: accs.vaa.meta().emitter_address,
+            emitter_chain: accs.vaa.meta().emitter_chain(),
+            emitter_address: accs.vaa.meta().emitter_address(),
         }
     }
 }
@@ -195,7 +418,7 @@ impl<'a> From<&CompleteWrapped<'a>> for WrappedDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CompleteWrappedData {}
 
 pub fn complete_wrapped(
@@ -203,6 +426,10 @@ pub fn complete_wrapped(
     accs: &mut CompleteWrapped,
     _data: CompleteWrappedData,
 ) -> Result<()> {
+    if accs.config.paused {
+        return Err(Paused.into());
+    }
+
     // Verify the chain registration
     let derivation_data: EndpointDerivationData = (&*accs).into();
     accs.chain_registration
@@ -222,26 +449,35 @@ pub fn complete_wrapped(
     }
 
     // Verify mints
-    if *accs.mint.info().key != accs.to.mint {
-        return Err(InvalidMint.into());
-    }
-    if *accs.mint.info().key != accs.to_fees.mint {
-        return Err(InvalidMint.into());
-    }
+    verify_recipient_mints(&accs.to, &accs.to_fees, accs.mint.info().key)?;
 
     // Verify VAA
     if accs.vaa.to_chain != CHAIN_ID_SOLANA {
         return Err(InvalidChain.into());
     }
-    if accs.vaa.to != accs.to.info().key.to_bytes() {
-        return Err(InvalidRecipient.into());
-    }
+    verify_recipient(
+        &accs.vaa.to,
+        &accs.to,
+        &accs.to_owner,
+        accs.mint.info().key,
+        &spl_token::id(),
+    )?;
     if INVALID_VAAS.contains(&&*accs.vaa.info().key.to_string()) {
         return Err(InvalidVAA.into());
     }
 
     claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
 
+    if !accs.to.is_initialized() {
+        create_recipient_account(
+            ctx,
+            accs.payer.key,
+            accs.to_owner.key,
+            accs.mint.info().key,
+            &spl_token::id(),
+        )?;
+    }
+
     let token_amount: u64 = accs
         .vaa
         .amount
@@ -273,3 +509,72 @@ pub fn complete_wrapped(
 
     Ok(())
 }
+
+/// Verify that the accounts receiving a transfer and its fee hold `mint`. `to_fees` may only be
+/// uninitialized if it is `to`, whose associated token account is then created.
+fn verify_recipient_mints<T>(
+    to: &Data<T, { AccountState::MaybeInitialized }>,
+    to_fees: &Data<T, { AccountState::MaybeInitialized }>,
+    mint: &Pubkey,
+) -> Result<()>
+where
+    T: Owned + Default + Deref<Target = Account>,
+{
+    if to.is_initialized() && to.mint != *mint {
+        return Err(InvalidMint.into());
+    }
+    if to_fees.is_initialized() {
+        if to_fees.mint != *mint {
+            return Err(InvalidMint.into());
+        }
+    } else if to_fees.info().key != to.info().key {
+        return Err(InvalidRecipient.into());
+    }
+    Ok(())
+}
+
+/// Verify the recipient of a transfer. The VAA names either the token account itself, or a wallet
+/// whose associated token account then receives the tokens and is created if it does not exist.
+fn verify_recipient<T>(
+    vaa_to: &Address,
+    to: &Data<T, { AccountState::MaybeInitialized }>,
+    to_owner: &Info,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<()>
+where
+    T: Owned + Default + Deref<Target = Account>,
+{
+    if *vaa_to == to.info().key.to_bytes() {
+        if !to.is_initialized() {
+            return Err(InvalidRecipient.into());
+        }
+        return Ok(());
+    }
+
+    if *vaa_to != to_owner.key.to_bytes() {
+        return Err(InvalidRecipient.into());
+    }
+    if *to.info().key != token_2022::associated_token_address(to_owner.key, mint, token_program) {
+        return Err(InvalidAssociatedAccount.into());
+    }
+    // Accounts of the original token program can be handed to another owner.
+    if to.is_initialized() && to.owner != *to_owner.key {
+        return Err(InvalidRecipient.into());
+    }
+    Ok(())
+}
+
+/// Create the associated token account of the wallet a transfer is sent to, funded by the payer.
+/// Relayers recover the rent from the fee of the transfer.
+fn create_recipient_account(
+    ctx: &ExecutionContext,
+    payer: &Pubkey,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<()> {
+    let ix = token_2022::create_associated_token_account(payer, wallet, mint, token_program);
+    invoke(&ix, ctx.accounts)?;
+    Ok(())
+}
@@ -0,0 +1,422 @@
+//! Completion of transfers with payload on behalf of a recipient program.
+//!
+//! [`complete_native_with_payload`](super::complete_native_with_payload) has to be called by the
+//! recipient itself, signing with its redeemer PDA and parsing the payload afterwards. The
+//! instructions here can be sent by anyone: the tokens are moved to a token account owned by the
+//! redeemer PDA of the recipient program, which is then invoked with [`ReceiveTransferData`].
+//!
+//! The recipient is invoked with the following accounts, and the instruction data
+//! [`RECEIVE_TRANSFER_DISCRIMINATOR`] followed by the Borsh encoded [`ReceiveTransferData`]:
+//!
+//! | Index | Account            | Description                                                 |
+//! |-------|--------------------|-------------------------------------------------------------|
+//! | 0     | callback_authority | Signer, the [`CallbackAuthority`] of the recipient          |
+//! | 1     | to                 | Writable, the token account the tokens were moved to        |
+//! | 2     | mint               | Mint of the tokens                                          |
+//! | 3     | vaa                | Posted VAA of the transfer                                  |
+//! | 4..   |                    | Accounts following the accounts of the completing instruction |
+//!
+//! Recipients must check that `callback_authority` signed and is the token bridge PDA with seeds
+//! `["callback_authority", recipient_program]`, using their own program id as `recipient_program`,
+//! see [`CallbackAuthorityDerivationData`]. Anyone can invoke them with the same data otherwise,
+//! and another recipient could forward the authority it was invoked with.
+//!
+//! The discriminator is the one Anchor gives an instruction named `receive_transfer`, so Anchor
+//! programs can implement the interface as a regular instruction taking the fields of
+//! [`ReceiveTransferData`] as arguments.
+
+use crate::{
+    accounts::{
+        CallbackAuthority,
+        CallbackAuthorityDerivationData,
+        ConfigAccount,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodySigner,
+        Endpoint,
+        EndpointDerivationData,
+        MintSigner,
+        WrappedMetaDerivationData,
+        WrappedMint,
+        WrappedTokenMeta,
+    },
+    api::RedeemerAccount,
+    messages::PayloadTransferWithPayload,
+    token_2022,
+    types::*,
+    TokenBridgeError::*,
+};
+use bridge::{
+    accounts::claim::{
+        self,
+        Claim,
+    },
+    PayloadMessage,
+    CHAIN_ID_SOLANA,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    pubkey::Pubkey,
+};
+use solitaire::{
+    processors::seeded::{
+        invoke_seeded,
+        Seeded,
+    },
+    *,
+};
+
+/// First eight bytes of the recipient's instruction data, `sha256("global:receive_transfer")`.
+pub const RECEIVE_TRANSFER_DISCRIMINATOR: [u8; 8] = [182, 83, 34, 71, 33, 147, 21, 169];
+
+/// Number of accounts in [`CompleteNativeWithCallback`] and [`CompleteWrappedWithCallback`], the
+/// accounts following them are handed on to the recipient.
+pub const COMPLETE_WITH_CALLBACK_ACCOUNTS: usize = 11;
+
+/// Transfer the recipient program is invoked with once its tokens were moved.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct ReceiveTransferData {
+    /// Amount the token account received, in the decimals of the mint. Less than the amount of the
+    /// VAA for mints withholding a transfer fee.
+    pub amount: u64,
+    /// Chain ID of the token
+    pub token_chain: ChainID,
+    /// Address of the token on its chain
+    pub token_address: Address,
+    /// Chain the transfer was sent from
+    pub from_chain: ChainID,
+    /// Sender of the transfer on the chain it was sent from
+    pub from_address: Address,
+    /// Arbitrary payload of the transfer
+    pub payload: Vec<u8>,
+}
+
+#[derive(FromAccounts)]
+pub struct CompleteNativeWithCallback<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub vaa: PayloadMessage<'b, PayloadTransferWithPayload>,
+    pub claim: Mut<Claim<'b>>,
+    pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
+
+    /// Owned by the redeemer PDA of the recipient program, see [`verify_recipient`]
+    pub to: Mut<Data<'b, TokenAccount, { AccountState::Initialized }>>,
+    pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
+    /// Mint of either token program, see [`token_2022`]
+    pub mint: Data<'b, TokenMint, { AccountState::Initialized }>,
+
+    pub custody_signer: CustodySigner<'b>,
+
+    /// Program the VAA is sent to
+    pub recipient_program: Info<'b>,
+    pub callback_authority: CallbackAuthority<'b>,
+}
+
+impl<'a> From<&CompleteNativeWithCallback<'a>> for EndpointDerivationData {
+    fn from(accs: &CompleteNativeWithCallback<'a>) -> Self {
+        EndpointDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain(),
+            emitter_address: accs.vaa.meta().emitter_address(),
+        }
+    }
+}
+
+impl<'a> From<&CompleteNativeWithCallback<'a>> for CustodyAccountDerivationData {
+    fn from(accs: &CompleteNativeWithCallback<'a>) -> Self {
+        CustodyAccountDerivationData {
+            mint: *accs.mint.info().key,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct CompleteNativeWithCallbackData {}
+
+pub fn complete_native_with_callback(
+    ctx: &ExecutionContext,
+    accs: &mut CompleteNativeWithCallback,
+    _data: CompleteNativeWithCallbackData,
+) -> Result<()> {
+    if accs.config.paused {
+        return Err(Paused.into());
+    }
+
+    // Verify the chain registration
+    let derivation_data: EndpointDerivationData = (&*accs).into();
+    accs.chain_registration
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify that the custody account is derived correctly
+    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
+    accs.custody
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify mints
+    if *accs.mint.info().key != accs.to.mint {
+        return Err(InvalidMint.into());
+    }
+    if *accs.mint.info().key != accs.custody.mint {
+        return Err(InvalidMint.into());
+    }
+    if *accs.custody_signer.key != accs.custody.owner {
+        return Err(WrongAccountOwner.into());
+    }
+    let token_program =
+        token_2022::token_program(accs.mint.info(), &[accs.to.info(), accs.custody.info()])?;
+
+    // Verify VAA
+    if accs.vaa.token_address != accs.mint.info().key.to_bytes() {
+        return Err(InvalidMint.into());
+    }
+    if accs.vaa.token_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    verify_recipient(&accs.vaa, &accs.recipient_program, &accs.to.owner)?;
+
+    // Prevent vaa double signing
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let mut amount = accs.vaa.amount.as_u64();
+
+    // Wormhole always caps transfers at 8 decimals; un-truncate if the local token has more
+    if accs.mint.decimals > 8 {
+        amount *= 10u64.pow((accs.mint.decimals - 8) as u32);
+    }
+
+    // Transfer tokens
+    let to_balance = token_2022::balance(accs.to.info())?;
+    let transfer_ix = spl_token::instruction::transfer_checked(
+        &spl_token::id(),
+        accs.custody.info().key,
+        accs.mint.info().key,
+        accs.to.info().key,
+        accs.custody_signer.key,
+        &[],
+        amount,
+        accs.mint.decimals,
+    )?;
+    invoke_seeded(
+        &token_2022::with_program(transfer_ix, &token_program),
+        ctx,
+        &accs.custody_signer,
+        None,
+    )?;
+
+    // Mints with a transfer fee withhold part of the transfer, the recipient is told what arrived.
+    let received = token_2022::balance(accs.to.info())? - to_balance;
+
+    invoke_recipient(
+        ctx,
+        &accs.recipient_program,
+        &accs.callback_authority,
+        [
+            accs.to.info().key,
+            accs.mint.info().key,
+            accs.vaa.info().key,
+        ],
+        receive_transfer_data(&accs.vaa, received),
+    )
+}
+
+#[derive(FromAccounts)]
+pub struct CompleteWrappedWithCallback<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    /// Signed message for the transfer
+    pub vaa: PayloadMessage<'b, PayloadTransferWithPayload>,
+    pub claim: Mut<Claim<'b>>,
+
+    pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
+
+    /// Owned by the redeemer PDA of the recipient program, see [`verify_recipient`]
+    pub to: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    pub mint: Mut<WrappedMint<'b, { AccountState::Initialized }>>,
+    pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
+
+    pub mint_authority: MintSigner<'b>,
+
+    /// Program the VAA is sent to
+    pub recipient_program: Info<'b>,
+    pub callback_authority: CallbackAuthority<'b>,
+}
+
+impl<'a> From<&CompleteWrappedWithCallback<'a>> for EndpointDerivationData {
+    fn from(accs: &CompleteWrappedWithCallback<'a>) -> Self {
+        EndpointDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain(),
+            emitter_address: accs.vaa.meta().emitter_address(),
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct CompleteWrappedWithCallbackData {}
+
+pub fn complete_wrapped_with_callback(
+    ctx: &ExecutionContext,
+    accs: &mut CompleteWrappedWithCallback,
+    _data: CompleteWrappedWithCallbackData,
+) -> Result<()> {
+    if accs.config.paused {
+        return Err(Paused.into());
+    }
+
+    // Verify the chain registration
+    let derivation_data: EndpointDerivationData = (&*accs).into();
+    accs.chain_registration
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify mint
+    accs.wrapped_meta.verify_derivation(
+        ctx.program_id,
+        &WrappedMetaDerivationData {
+            mint_key: *accs.mint.info().key,
+        },
+    )?;
+    if accs.wrapped_meta.token_address != accs.vaa.token_address
+        || accs.wrapped_meta.chain != accs.vaa.token_chain
+    {
+        return Err(InvalidMint.into());
+    }
+    if *accs.mint.info().key != accs.to.mint {
+        return Err(InvalidMint.into());
+    }
+
+    // Verify VAA
+    if accs.vaa.to_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    verify_recipient(&accs.vaa, &accs.recipient_program, &accs.to.owner)?;
+
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    // Mint tokens
+    let amount = accs.vaa.amount.as_u64();
+    let mint_ix = spl_token::instruction::mint_to(
+        &spl_token::id(),
+        accs.mint.info().key,
+        accs.to.info().key,
+        accs.mint_authority.key,
+        &[],
+        amount,
+    )?;
+    invoke_seeded(&mint_ix, ctx, &accs.mint_authority, None)?;
+
+    invoke_recipient(
+        ctx,
+        &accs.recipient_program,
+        &accs.callback_authority,
+        [
+            accs.to.info().key,
+            accs.mint.info().key,
+            accs.vaa.info().key,
+        ],
+        receive_transfer_data(&accs.vaa, amount),
+    )
+}
+
+/// The VAA must be sent to `recipient_program`, and the tokens go to an account owned by its
+/// redeemer PDA, see [`RedeemerAccount`], so that only the recipient can move them on.
+fn verify_recipient(
+    vaa: &PayloadMessage<PayloadTransferWithPayload>,
+    recipient_program: &Info,
+    to_owner: &Pubkey,
+) -> Result<()> {
+    let recipient = Pubkey::try_from_slice(&vaa.to)?;
+    if *recipient_program.key != recipient || !recipient_program.executable {
+        return Err(InvalidRecipient.into());
+    }
+    if *to_owner != RedeemerAccount::key((), &recipient) {
+        return Err(InvalidRecipient.into());
+    }
+    Ok(())
+}
+
+fn receive_transfer_data(
+    vaa: &PayloadMessage<PayloadTransferWithPayload>,
+    amount: u64,
+) -> ReceiveTransferData {
+    ReceiveTransferData {
+        amount,
+        token_chain: vaa.token_chain,
+        token_address: vaa.token_address,
+        from_chain: vaa.meta().emitter_chain(),
+        from_address: vaa.from_address,
+        payload: vaa.payload.clone(),
+    }
+}
+
+/// Invoke the recipient with the accounts and data described in the module documentation.
+fn invoke_recipient(
+    ctx: &ExecutionContext,
+    recipient_program: &Info,
+    callback_authority: &CallbackAuthority,
+    [to, mint, vaa]: [&Pubkey; 3],
+    data: ReceiveTransferData,
+) -> Result<()> {
+    let derivation_data = CallbackAuthorityDerivationData {
+        recipient_program: *recipient_program.key,
+    };
+    callback_authority.verify_derivation(ctx.program_id, &derivation_data)?;
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*callback_authority.key, true),
+        AccountMeta::new(*to, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*vaa, false),
+    ];
+    accounts.extend(
+        ctx.accounts[COMPLETE_WITH_CALLBACK_ACCOUNTS..]
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            }),
+    );
+
+    let mut instruction_data = RECEIVE_TRANSFER_DISCRIMINATOR.to_vec();
+    data.serialize(&mut instruction_data)?;
+
+    let ix = Instruction {
+        program_id: *recipient_program.key,
+        accounts,
+        data: instruction_data,
+    };
+    invoke_seeded(&ix, ctx, callback_authority, &derivation_data)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::hash::hash;
+    use solitaire::idl::IdlAccounts;
+
+    #[test]
+    fn test_receive_transfer_discriminator() {
+        let digest = hash(b"global:receive_transfer");
+        assert_eq!(RECEIVE_TRANSFER_DISCRIMINATOR, digest.to_bytes()[..8]);
+    }
+
+    #[test]
+    fn test_complete_with_callback_accounts() {
+        assert_eq!(
+            CompleteNativeWithCallback::accounts().len(),
+            COMPLETE_WITH_CALLBACK_ACCOUNTS
+        );
+        assert_eq!(
+            CompleteWrappedWithCallback::accounts().len(),
+            COMPLETE_WITH_CALLBACK_ACCOUNTS
+        );
+    }
+}
@@ -13,6 +13,7 @@ use crate::{
         WrappedTokenMeta,
     },
     messages::PayloadTransferWithPayload,
+    token_2022,
     types::*,
     TokenBridgeError::*,
 };
@@ -26,6 +27,8 @@ use bridge::{
 };
 use solana_program::account_info::AccountInfo;
 use solitaire::{
+    client::ClientAccount,
+    idl::IdlAccount,
     processors::seeded::{
         invoke_seeded,
         Seeded,
@@ -52,6 +55,18 @@ impl<'a, 'b: 'a> Peel<'a, 'b> for RedeemerAccount<'b> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         MaybeMut::persist(&self.0, program_id)
     }
+
+    fn describe(account: &mut IdlAccount) {
+        MaybeMut::<Signer<Info<'b>>>::describe(account)
+    }
+}
+
+impl<'b> ClientAccount for RedeemerAccount<'b> {
+    type Key = Pubkey;
+
+    fn pubkey(key: &Self::Key, _program_id: &Pubkey) -> Pubkey {
+        *key
+    }
 }
 
 // May or may not be a PDA, so we don't use [`Derive`], instead implement
@@ -102,13 +117,14 @@ pub struct CompleteNativeWithPayload<'b> {
     pub claim: Mut<Claim<'b>>,
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
-    pub to: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    pub to: Mut<Data<'b, TokenAccount, { AccountState::Initialized }>>,
 
     /// See [`verify_recipient_address`]
     pub redeemer: RedeemerAccount<'b>,
-    pub to_fees: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    pub to_fees: Mut<Data<'b, TokenAccount, { AccountState::Initialized }>>,
     pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
-    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    /// Mint of either token program, see [`token_2022`]
+    pub mint: Data<'b, TokenMint, { AccountState::Initialized }>,
 
     pub custody_signer: CustodySigner<'b>,
 }
@@ -116,8 +132,8 @@ pub struct CompleteNativeWithPayload<'b> {
 impl<'a> From<&CompleteNativeWithPayload<'a>> for EndpointDerivationData {
     fn from(accs: &CompleteNativeWithPayload<'a>) -> Self {
         EndpointDerivationData {
-            emitter_chain: accs.vaa.meta().emitter_chain,
-            emitter_address: accs.vaa.meta().emitter_address,
+            emitter_chain: accs.vaa.meta().emitter_chain(),
+            emitter_address: accs.vaa.meta().emitter_address(),
         }
     }
 }
@@ -130,7 +146,7 @@ impl<'a> From<&CompleteNativeWithPayload<'a>> for CustodyAccountDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CompleteNativeWithPayloadData {}
 
 pub fn complete_native_with_payload(
@@ -138,6 +154,10 @@ pub fn complete_native_with_payload(
     accs: &mut CompleteNativeWithPayload,
     _data: CompleteNativeWithPayloadData,
 ) -> Result<()> {
+    if accs.config.paused {
+        return Err(Paused.into());
+    }
+
     // Verify the chain registration
     let derivation_data: EndpointDerivationData = (&*accs).into();
     accs.chain_registration
@@ -161,6 +181,10 @@ pub fn complete_native_with_payload(
     if *accs.custody_signer.key != accs.custody.owner {
         return Err(WrongAccountOwner.into());
     }
+    let token_program = token_2022::token_program(
+        accs.mint.info(),
+        &[accs.to.info(), accs.to_fees.info(), accs.custody.info()],
+    )?;
 
     // Verify VAA
     if accs.vaa.token_address != accs.mint.info().key.to_bytes() {
@@ -194,15 +218,22 @@ pub fn complete_native_with_payload(
     }
 
     // Transfer tokens
-    let transfer_ix = spl_token::instruction::transfer(
+    let transfer_ix = spl_token::instruction::transfer_checked(
         &spl_token::id(),
         accs.custody.info().key,
+        accs.mint.info().key,
         accs.to.info().key,
         accs.custody_signer.key,
         &[],
         amount,
+        accs.mint.decimals,
+    )?;
+    invoke_seeded(
+        &token_2022::with_program(transfer_ix, &token_program),
+        ctx,
+        &accs.custody_signer,
+        None,
     )?;
-    invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
 
     Ok(())
 }
@@ -232,8 +263,8 @@ pub struct CompleteWrappedWithPayload<'b> {
 impl<'a> From<&CompleteWrappedWithPayload<'a>> for EndpointDerivationData {
     fn from(accs: &CompleteWrappedWithPayload<'a>) -> Self {
         EndpointDerivationData {
-            emitter_chain: accs.vaa.meta().emitter_chain,
-            emitter_address: accs.vaa.meta().emitter_address,
+            emitter_chain: accs.vaa.meta().emitter_chain(),
+            emitter_address: accs.vaa.meta().emitter_address(),
         }
     }
 }
@@ -247,7 +278,7 @@ impl<'a> From<&CompleteWrappedWithPayload<'a>> for WrappedDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CompleteWrappedWithPayloadData {}
 
 pub fn complete_wrapped_with_payload(
@@ -255,6 +286,10 @@ pub fn complete_wrapped_with_payload(
     accs: &mut CompleteWrappedWithPayload,
     _data: CompleteWrappedWithPayloadData,
 ) -> Result<()> {
+    if accs.config.paused {
+        return Err(Paused.into());
+    }
+
     // Verify the chain registration
     let derivation_data: EndpointDerivationData = (&*accs).into();
     accs.chain_registration
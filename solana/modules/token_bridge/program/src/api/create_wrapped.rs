@@ -16,6 +16,7 @@ use crate::{
         InvalidChain,
         InvalidMetadata,
         InvalidVAA,
+        StaleAttestation,
     },
     INVALID_VAAS,
 };
@@ -30,6 +31,10 @@ use bridge::{
 use solana_program::{
     account_info::AccountInfo,
     program::invoke_signed,
+    sysvar::{
+        clock::Clock,
+        Sysvar as _,
+    },
 };
 use solitaire::{
     processors::seeded::{
@@ -68,8 +73,8 @@ pub struct CreateWrapped<'b> {
 impl<'a> From<&CreateWrapped<'a>> for EndpointDerivationData {
     fn from(accs: &CreateWrapped<'a>) -> Self {
         EndpointDerivationData {
-            emitter_chain: accs.vaa.meta().emitter_chain,
-            emitter_address: accs.vaa.meta().emitter_address,
+            emitter_chain: accs.vaa.meta().emitter_chain(),
+            emitter_address: accs.vaa.meta().emitter_address(),
         }
     }
 }
@@ -91,12 +96,12 @@ impl<'a> From<&CreateWrapped<'a>> for WrappedMetaDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct CreateWrappedData {}
 
-pub fn create_wrapped(
-    ctx: &ExecutionContext,
-    accs: &mut CreateWrapped,
+pub fn create_wrapped<'b>(
+    ctx: &ExecutionContext<'_, 'b>,
+    accs: &mut CreateWrapped<'b>,
     data: CreateWrappedData,
 ) -> Result<()> {
     // Do not process attestations sourced from the current chain.
@@ -185,15 +190,24 @@ pub fn create_accounts(
     accs.meta.chain = accs.vaa.token_chain;
     accs.meta.token_address = accs.vaa.token_address;
     accs.meta.original_decimals = accs.vaa.decimals;
+    accs.meta.last_updated_sequence = accs.vaa.meta().sequence();
+    accs.meta.last_updated = Clock::get()?.unix_timestamp;
 
     Ok(())
 }
 
-pub fn update_accounts(
-    ctx: &ExecutionContext,
-    accs: &mut CreateWrapped,
+pub fn update_accounts<'b>(
+    ctx: &ExecutionContext<'_, 'b>,
+    accs: &mut CreateWrapped<'b>,
     _data: CreateWrappedData,
 ) -> Result<()> {
+    // Attestations can be redeemed in any order, an older one must not overwrite the metadata
+    // set by a newer one.
+    let sequence = accs.vaa.meta().sequence();
+    if sequence <= accs.meta.last_updated_sequence {
+        return Err(StaleAttestation.into());
+    }
+
     accs.spl_metadata.verify_derivation(
         &spl_token_metadata::id(),
         &SplTokenMetaDerivationData {
@@ -220,6 +234,10 @@ pub fn update_accounts(
     );
     invoke_seeded(&spl_token_metadata_ix, ctx, &accs.mint_authority, None)?;
 
+    accs.meta.migrate(ctx, accs.payer.key)?;
+    accs.meta.last_updated_sequence = sequence;
+    accs.meta.last_updated = Clock::get()?.unix_timestamp;
+
     Ok(())
 }
 
@@ -272,4 +290,50 @@ mod tests {
             assert_eq!(expected, super::truncate_utf8(input, 32));
         }
     }
+
+    #[test]
+    fn test_wrapped_meta_migration() {
+        use crate::types::{
+            VersionedWrappedMeta,
+            WrappedMetaV0,
+        };
+        use borsh::{
+            BorshDeserialize,
+            BorshSerialize,
+        };
+
+        // Accounts created before attestations were ordered hold the bare V0 layout.
+        let v0 = WrappedMetaV0 {
+            chain: 2,
+            token_address: [1u8; 32],
+            original_decimals: 18,
+        }
+        .try_to_vec()
+        .unwrap();
+        let meta = VersionedWrappedMeta::try_from_slice(&v0).unwrap();
+        assert!(meta.is_outdated());
+        assert_eq!(meta.chain, 2);
+        assert_eq!(meta.token_address, [1u8; 32]);
+        assert_eq!(meta.original_decimals, 18);
+        assert_eq!(meta.last_updated_sequence, 0);
+
+        // Unless migrated, it is written back as it was for readers of the old layout.
+        assert_eq!(meta.try_to_vec().unwrap(), v0);
+
+        // Data no layout consumes exactly isn't read at the layout of its prefix.
+        let mut long = v0.clone();
+        long.push(0);
+        assert!(VersionedWrappedMeta::deserialize(&mut long.as_slice()).is_err());
+
+        // Appended fields can't be set without migrating.
+        let mut meta = meta;
+        meta.last_updated_sequence = 7;
+        assert!(meta.try_to_vec().is_err());
+
+        meta.upgrade();
+        let current = VersionedWrappedMeta::try_from_slice(&meta.try_to_vec().unwrap()).unwrap();
+        assert!(!current.is_outdated());
+        assert_eq!(current.original_decimals, 18);
+        assert_eq!(current.last_updated_sequence, 7);
+    }
 }
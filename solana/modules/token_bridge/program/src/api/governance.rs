@@ -1,10 +1,19 @@
 use crate::{
     accounts::{
+        ChainGovernor,
+        ChainGovernorDerivationData,
         ConfigAccount,
         Endpoint,
         EndpointDerivationData,
+        TokenGovernor,
+        TokenGovernorDerivationData,
+        WrappedDerivationData,
+        WrappedMint,
     },
     messages::{
+        GovernancePayloadSetChainLimit,
+        GovernancePayloadSetPaused,
+        GovernancePayloadSetTokenLimit,
         GovernancePayloadUpgrade,
         PayloadGovernanceRegisterChain,
     },
@@ -44,9 +53,9 @@ where
     T: DeserializePayload,
 {
     let expected_emitter = std::env!("EMITTER_ADDRESS");
-    let current_emitter = format!("{}", Pubkey::new_from_array(vaa.meta().emitter_address));
+    let current_emitter = format!("{}", Pubkey::new_from_array(vaa.meta().emitter_address()));
     // Fail if the emitter is not the known governance key, or the emitting chain is not Solana.
-    if expected_emitter != current_emitter || vaa.meta().emitter_chain != CHAIN_ID_SOLANA {
+    if expected_emitter != current_emitter || vaa.meta().emitter_chain() != CHAIN_ID_SOLANA {
         Err(InvalidGovernanceKey.into())
     } else {
         Ok(())
@@ -84,7 +93,7 @@ pub struct UpgradeContract<'b> {
     pub system: Info<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct UpgradeContractData {}
 
 pub fn upgrade_contract(
@@ -136,7 +145,7 @@ impl<'a> From<&RegisterChain<'a>> for EndpointDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct RegisterChainData {}
 
 pub fn register_chain(
@@ -165,3 +174,152 @@ pub fn register_chain(
 
     Ok(())
 }
+
+#[derive(FromAccounts)]
+pub struct SetPaused<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: Mut<ConfigAccount<'b, { AccountState::Initialized }>>,
+
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetPaused>,
+    pub claim: Mut<Claim<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct SetPausedData {}
+
+/// Pause or resume transfers during an incident. Governance itself is never paused, so that the
+/// bridge can always be resumed or upgraded.
+pub fn set_paused<'b>(
+    ctx: &ExecutionContext<'_, 'b>,
+    accs: &mut SetPaused<'b>,
+    _data: SetPausedData,
+) -> Result<()> {
+    accs.config.migrate(ctx, accs.payer.key)?;
+
+    if INVALID_VAAS.contains(&&*accs.vaa.info().key.to_string()) {
+        return Err(InvalidVAA.into());
+    }
+
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    accs.config.paused = accs.vaa.paused;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetTokenLimit<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    /// Marked governed by the first limit, see [`crate::api::TransferGovernor`].
+    pub config: Mut<ConfigAccount<'b, { AccountState::Initialized }>>,
+
+    /// Limit of the mint of the token in the VAA, created by its first limit.
+    pub token_governor: Mut<TokenGovernor<'b, { AccountState::MaybeInitialized }>>,
+
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetTokenLimit>,
+    pub claim: Mut<Claim<'b>>,
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct SetTokenLimitData {}
+
+/// Set the price and daily outflow limit of a token. Transfers of tokens without a limit are not
+/// governed, not even by the limit of their destination chain.
+pub fn set_token_limit<'b>(
+    ctx: &ExecutionContext<'_, 'b>,
+    accs: &mut SetTokenLimit<'b>,
+    _data: SetTokenLimitData,
+) -> Result<()> {
+    accs.config.migrate(ctx, accs.payer.key)?;
+
+    let mint = if accs.vaa.token_chain == CHAIN_ID_SOLANA {
+        Pubkey::new(&accs.vaa.token_address)
+    } else {
+        WrappedMint::<'_, { AccountState::Initialized }>::key(
+            &WrappedDerivationData {
+                token_chain: accs.vaa.token_chain,
+                token_address: accs.vaa.token_address,
+            },
+            ctx.program_id,
+        )
+    };
+    let derivation_data = TokenGovernorDerivationData { mint };
+    accs.token_governor
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if INVALID_VAAS.contains(&&*accs.vaa.info().key.to_string()) {
+        return Err(InvalidVAA.into());
+    }
+
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    if !accs.token_governor.is_initialized() {
+        accs.token_governor
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+    }
+
+    // Outflow recorded so far keeps counting against the new limit.
+    accs.token_governor.price = accs.vaa.price;
+    accs.token_governor.daily_limit = accs.vaa.daily_limit;
+    accs.config.governed = true;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetChainLimit<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    /// Marked governed by the first limit, see [`crate::api::TransferGovernor`].
+    pub config: Mut<ConfigAccount<'b, { AccountState::Initialized }>>,
+
+    /// Limit of the chain in the VAA, created by its first limit.
+    pub chain_governor: Mut<ChainGovernor<'b, { AccountState::MaybeInitialized }>>,
+
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetChainLimit>,
+    pub claim: Mut<Claim<'b>>,
+}
+
+impl<'a> From<&SetChainLimit<'a>> for ChainGovernorDerivationData {
+    fn from(accs: &SetChainLimit<'a>) -> Self {
+        ChainGovernorDerivationData {
+            chain: accs.vaa.chain,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct SetChainLimitData {}
+
+/// Set the daily outflow limit towards a chain, counted in the notional value of governed tokens.
+pub fn set_chain_limit<'b>(
+    ctx: &ExecutionContext<'_, 'b>,
+    accs: &mut SetChainLimit<'b>,
+    _data: SetChainLimitData,
+) -> Result<()> {
+    accs.config.migrate(ctx, accs.payer.key)?;
+
+    let derivation_data: ChainGovernorDerivationData = (&*accs).into();
+    accs.chain_governor
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if INVALID_VAAS.contains(&&*accs.vaa.info().key.to_string()) {
+        return Err(InvalidVAA.into());
+    }
+
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    if !accs.chain_governor.is_initialized() {
+        accs.chain_governor
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+    }
+
+    accs.chain_governor.daily_limit = accs.vaa.daily_limit;
+    accs.config.governed = true;
+
+    Ok(())
+}
@@ -0,0 +1,330 @@
+use crate::{
+    accounts::{
+        ChainGovernor,
+        ChainGovernorDerivationData,
+        ConfigAccount,
+        CoreBridge,
+        EmitterAccount,
+        PendingTransferAccount,
+        PendingTransferDerivationData,
+        ReleasedMessage,
+        ReleasedMessageDerivationData,
+        TokenGovernor,
+        TokenGovernorDerivationData,
+    },
+    api::post_transfer_message_signed,
+    types::*,
+    TokenBridgeError::{
+        InvalidRefund,
+        MissingGovernor,
+        Paused,
+        TransferAlreadyQueued,
+        TransferNotReleasable,
+        UnpricedToken,
+    },
+};
+use bridge::api::PostMessageData;
+use solana_program::{
+    account_info::AccountInfo,
+    pubkey::Pubkey,
+    sysvar::clock::Clock,
+};
+use solitaire::{
+    processors::seeded::Seeded,
+    CreationLamports::Exempt,
+    *,
+};
+
+/// Outflow limits of a transfer, and the account holding its message if it exceeds them.
+///
+/// These are not part of the accounts of the transfer instructions, which predate the governor,
+/// but passed anywhere after them, see [`TransferGovernor::find`].
+#[derive(FromAccounts)]
+pub struct TransferGovernor<'b> {
+    /// Outflow limit of the mint, see [`record_outflow`]
+    pub token_governor: Mut<TokenGovernor<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Outflow limit towards the target chain
+    pub chain_governor: Mut<ChainGovernor<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Holds the message if the transfer exceeds the outflow limits, see [`queue_transfer`]
+    pub pending: Mut<PendingTransferAccount<'b, { AccountState::MaybeInitialized }>>,
+}
+
+impl<'b> TransferGovernor<'b> {
+    /// Look up the governor accounts of a transfer among the accounts of the instruction by their
+    /// derived keys. Callers may leave all of them out until governance sets a first limit.
+    pub fn find(
+        ctx: &ExecutionContext<'_, 'b>,
+        config: &ConfigAccount<'_, { AccountState::Initialized }>,
+        mint: &Pubkey,
+        target_chain: ChainID,
+        message: &Pubkey,
+    ) -> Result<Option<Box<Self>>> {
+        let keys = [
+            TokenGovernor::<'_, { AccountState::MaybeInitialized }>::key(
+                &TokenGovernorDerivationData { mint: *mint },
+                ctx.program_id,
+            ),
+            ChainGovernor::<'_, { AccountState::MaybeInitialized }>::key(
+                &ChainGovernorDerivationData {
+                    chain: target_chain,
+                },
+                ctx.program_id,
+            ),
+            PendingTransferAccount::<'_, { AccountState::MaybeInitialized }>::key(
+                &PendingTransferDerivationData { message: *message },
+                ctx.program_id,
+            ),
+        ];
+        let accounts: Vec<AccountInfo<'b>> = keys
+            .iter()
+            .filter_map(|key| ctx.accounts.iter().find(|info| info.key == key))
+            .cloned()
+            .collect();
+
+        if accounts.is_empty() && !config.governed {
+            return Ok(None);
+        }
+        if accounts.len() != keys.len() {
+            return Err(MissingGovernor.into());
+        }
+        FromAccounts::from(ctx.program_id, &mut accounts.iter(), &()).map(Some)
+    }
+}
+
+/// Record a transfer against the outflow limits and queue its message if it exceeds them. Returns
+/// the message if it is to be posted right away, which it always is for transfers without
+/// governor accounts.
+#[allow(clippy::too_many_arguments)]
+pub fn govern_transfer<'b>(
+    ctx: &ExecutionContext<'_, 'b>,
+    config: &ConfigAccount<'_, { AccountState::Initialized }>,
+    payer: &Pubkey,
+    message_account: &Pubkey,
+    clock: &Sysvar<'_, Clock>,
+    mint: &Pubkey,
+    decimals: u8,
+    amount: u64,
+    target_chain: ChainID,
+    message: PostMessageData,
+) -> Result<Option<PostMessageData>> {
+    let mut governor =
+        match TransferGovernor::find(ctx, config, mint, target_chain, message_account)? {
+            Some(governor) => governor,
+            None => return Ok(Some(message)),
+        };
+
+    let message = if record_outflow(
+        ctx,
+        &mut governor.token_governor,
+        &mut governor.chain_governor,
+        clock,
+        mint,
+        decimals,
+        amount,
+        target_chain,
+    )? {
+        Some(message)
+    } else {
+        queue_transfer(
+            ctx,
+            payer,
+            &mut governor.pending,
+            message_account,
+            clock,
+            message,
+        )?;
+        None
+    };
+
+    // The accounts are not part of the instruction's accounts, which are persisted on return.
+    governor.persist(ctx.program_id)?;
+
+    Ok(message)
+}
+
+/// Seconds a transfer exceeding the outflow limits is held before it can be released.
+pub const RELEASE_DELAY: i64 = 24 * 60 * 60;
+
+/// Record the notional value of an outbound transfer against the limits of its mint and its
+/// destination chain. Returns false, recording nothing, if the transfer would exceed either of
+/// them and has to be queued instead. Mints without a price are only let through to chains without
+/// a limit, as their transfers could not be counted against it.
+#[allow(clippy::too_many_arguments)]
+pub fn record_outflow(
+    ctx: &ExecutionContext,
+    token_governor: &mut Mut<TokenGovernor<{ AccountState::MaybeInitialized }>>,
+    chain_governor: &mut Mut<ChainGovernor<{ AccountState::MaybeInitialized }>>,
+    clock: &Sysvar<Clock>,
+    mint: &Pubkey,
+    decimals: u8,
+    amount: u64,
+    target_chain: ChainID,
+) -> Result<bool> {
+    token_governor
+        .verify_derivation(ctx.program_id, &TokenGovernorDerivationData { mint: *mint })?;
+    chain_governor.verify_derivation(
+        ctx.program_id,
+        &ChainGovernorDerivationData {
+            chain: target_chain,
+        },
+    )?;
+
+    // Without a price there is no notional value to count.
+    if !token_governor.is_initialized() {
+        if chain_governor.is_initialized() {
+            return Err(UnpricedToken.into());
+        }
+        return Ok(true);
+    }
+
+    // Amounts are truncated to at most 8 decimals before they are sent.
+    let notional = u128::from(amount) * u128::from(token_governor.price)
+        / 10u128.pow(u32::from(decimals.min(8)));
+    let notional = notional.min(u128::from(u64::MAX)) as u64;
+    let hour = clock.unix_timestamp.max(0) as u64 / 3600;
+
+    let exceeds =
+        |limit: u64, outflow: &Outflow| outflow.total(hour).saturating_add(notional) > limit;
+    if exceeds(token_governor.daily_limit, &token_governor.outflow) {
+        return Ok(false);
+    }
+    if chain_governor.is_initialized()
+        && exceeds(chain_governor.daily_limit, &chain_governor.outflow)
+    {
+        return Ok(false);
+    }
+
+    token_governor.outflow.record(hour, notional);
+    if chain_governor.is_initialized() {
+        chain_governor.outflow.record(hour, notional);
+    }
+
+    Ok(true)
+}
+
+/// Hold the message of a transfer exceeding the outflow limits until [`release_transfer`] posts
+/// it. The tokens were already taken, and the message fee is only paid on release.
+pub fn queue_transfer(
+    ctx: &ExecutionContext,
+    payer: &Pubkey,
+    pending: &mut Mut<PendingTransferAccount<{ AccountState::MaybeInitialized }>>,
+    message: &Pubkey,
+    clock: &Sysvar<Clock>,
+    data: PostMessageData,
+) -> Result<()> {
+    let derivation_data = PendingTransferDerivationData { message: *message };
+    pending.verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if pending.is_initialized() {
+        return Err(TransferAlreadyQueued.into());
+    }
+
+    pending.release_time = clock.unix_timestamp + RELEASE_DELAY;
+    pending.payer = *payer;
+    pending.nonce = data.nonce;
+    pending.consistency_level = data.consistency_level;
+    pending.payload = data.payload;
+    pending.create(&derivation_data, ctx, payer, Exempt)?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct ReleaseTransfer<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    /// Queued transfer, closed once released.
+    pub pending: Mut<PendingTransferAccount<'b, { AccountState::Initialized }>>,
+
+    /// Payer of the queued transfer, refunded the rent of `pending`
+    pub refund: Mut<Info<'b>>,
+
+    /// CPI Context
+    pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the message, derived from `pending`
+    pub message: Mut<ReleasedMessage<'b>>,
+
+    /// Emitter of the VAA
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+impl<'a> From<&ReleaseTransfer<'a>> for ReleasedMessageDerivationData {
+    fn from(accs: &ReleaseTransfer<'a>) -> Self {
+        ReleasedMessageDerivationData {
+            pending: *accs.pending.info().key,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct ReleaseTransferData {
+    /// Account the transfer was queued for, which does not sign
+    pub message: Pubkey,
+}
+
+/// Post the message of a queued transfer once its delay has passed. Released transfers do not
+/// count against the outflow limits.
+pub fn release_transfer<'b>(
+    ctx: &ExecutionContext<'_, 'b>,
+    accs: &mut ReleaseTransfer<'b>,
+    data: ReleaseTransferData,
+) -> Result<()> {
+    if accs.config.paused {
+        return Err(Paused.into());
+    }
+
+    accs.pending.verify_derivation(
+        ctx.program_id,
+        &PendingTransferDerivationData {
+            message: data.message,
+        },
+    )?;
+    let derivation_data: ReleasedMessageDerivationData = (&*accs).into();
+    accs.message
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if *accs.refund.key != accs.pending.payer {
+        return Err(InvalidRefund.into());
+    }
+
+    if accs.clock.unix_timestamp < accs.pending.release_time {
+        return Err(TransferNotReleasable.into());
+    }
+
+    post_transfer_message_signed(
+        ctx,
+        &accs.config,
+        &accs.payer,
+        &accs.bridge,
+        &accs.message,
+        Some(
+            accs.message
+                .self_bumped_seeds(&derivation_data, ctx.program_id),
+        ),
+        &accs.emitter,
+        &accs.sequence,
+        &accs.fee_collector,
+        &accs.clock,
+        PostMessageData {
+            nonce: accs.pending.nonce,
+            payload: accs.pending.payload.clone(),
+            consistency_level: accs.pending.consistency_level.clone(),
+        },
+    )?;
+
+    accs.pending.close(ctx, &accs.refund)?;
+
+    Ok(())
+}
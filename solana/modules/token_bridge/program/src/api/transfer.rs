@@ -13,18 +13,20 @@ use crate::{
         WrappedMint,
         WrappedTokenMeta,
     },
+    api::govern_transfer,
     messages::PayloadTransfer,
+    token_2022,
     types::*,
     TokenBridgeError,
     TokenBridgeError::{
         InvalidChain,
         InvalidFee,
+        Paused,
         WrongAccountOwner,
     },
 };
 use bridge::{
     api::PostMessageData,
-    types::ConsistencyLevel,
     vaa::SerializePayload,
     CHAIN_ID_SOLANA,
 };
@@ -40,6 +42,8 @@ use solana_program::{
         invoke_signed,
     },
     program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
     sysvar::clock::Clock,
 };
 use solitaire::{
@@ -48,6 +52,7 @@ use solitaire::{
         Seeded,
     },
     CreationLamports::Exempt,
+    IsSigned::SignedWithSeeds,
     *,
 };
 
@@ -57,9 +62,10 @@ pub struct TransferNative<'b> {
 
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
-    pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    pub from: Mut<Data<'b, TokenAccount, { AccountState::Initialized }>>,
 
-    pub mint: Mut<Data<'b, SplMint, { AccountState::Initialized }>>,
+    /// Mint of either token program, see [`token_2022`]
+    pub mint: Mut<Data<'b, TokenMint, { AccountState::Initialized }>>,
 
     pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
 
@@ -95,13 +101,15 @@ impl<'a> From<&TransferNative<'a>> for CustodyAccountDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct TransferNativeData {
     pub nonce: u32,
     pub amount: u64,
     pub fee: u64,
     pub target_address: Address,
     pub target_chain: ChainID,
+    /// Must stay the last field, see [`TransferConsistency`]
+    pub consistency_level: TransferConsistency,
 }
 
 pub fn transfer_native(
@@ -109,6 +117,10 @@ pub fn transfer_native(
     accs: &mut TransferNative,
     data: TransferNativeData,
 ) -> Result<()> {
+    if accs.config.paused {
+        return Err(Paused.into());
+    }
+
     // Prevent transferring to the same chain.
     if data.target_chain == CHAIN_ID_SOLANA {
         return Err(InvalidChain.into());
@@ -124,8 +136,6 @@ pub fn transfer_native(
         &accs.custody,
         &accs.authority_signer,
         &accs.custody_signer,
-        &accs.bridge,
-        &accs.fee_collector,
         data.amount,
         data.fee,
     )?;
@@ -139,31 +149,273 @@ pub fn transfer_native(
         to_chain: data.target_chain,
         fee: U256::from(fee),
     };
-    let params = (
-        bridge::instruction::Instruction::PostMessage,
-        PostMessageData {
-            nonce: data.nonce,
-            payload: payload.try_to_vec()?,
-            consistency_level: ConsistencyLevel::Finalized,
-        },
+    let message = PostMessageData {
+        nonce: data.nonce,
+        payload: payload.try_to_vec()?,
+        consistency_level: data.consistency_level.level(),
+    };
+
+    match govern_transfer(
+        ctx,
+        &accs.config,
+        accs.payer.key,
+        accs.message.key,
+        &accs.clock,
+        accs.mint.info().key,
+        accs.mint.decimals,
+        amount,
+        data.target_chain,
+        message,
+    )? {
+        Some(message) => post_transfer_message(
+            ctx,
+            &accs.config,
+            &accs.payer,
+            &accs.bridge,
+            &accs.message,
+            &accs.emitter,
+            &accs.sequence,
+            &accs.fee_collector,
+            &accs.clock,
+            message,
+        ),
+        None => Ok(()),
+    }
+}
+
+#[derive(FromAccounts)]
+pub struct TransferNativeSol<'b> {
+    /// Pays the transferred lamports along with the fees
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    /// Native mint, SOL is held in the same custody as WSOL
+    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+
+    pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    pub custody_signer: CustodySigner<'b>,
+
+    /// CPI Context
+    pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted message
+    pub message: Signer<Mut<Info<'b>>>,
+
+    /// Emitter of the VAA
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+impl<'a> From<&TransferNativeSol<'a>> for CustodyAccountDerivationData {
+    fn from(accs: &TransferNativeSol<'a>) -> Self {
+        CustodyAccountDerivationData {
+            mint: *accs.mint.info().key,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct TransferNativeSolData {
+    pub nonce: u32,
+    /// Lamports to send
+    pub amount: u64,
+    pub fee: u64,
+    pub target_address: Address,
+    pub target_chain: ChainID,
+    /// Must stay the last field, see [`TransferConsistency`]
+    pub consistency_level: TransferConsistency,
+}
+
+/// Send lamports of the payer as WSOL. The lamports are wrapped by moving them straight into the
+/// WSOL custody account and syncing its balance, so no token account of the payer is involved.
+pub fn transfer_native_sol(
+    ctx: &ExecutionContext,
+    accs: &mut TransferNativeSol,
+    data: TransferNativeSolData,
+) -> Result<()> {
+    if accs.config.paused {
+        return Err(Paused.into());
+    }
+
+    // Prevent transferring to the same chain.
+    if data.target_chain == CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+
+    if *accs.mint.info().key != spl_token::native_mint::id() {
+        return Err(TokenBridgeError::InvalidMint.into());
+    }
+
+    // Fee must be less than amount
+    if data.fee > data.amount {
+        return Err(InvalidFee.into());
+    }
+
+    // Verify that the custody account is derived correctly
+    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
+    accs.custody
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if !accs.custody.is_initialized() {
+        create_custody(
+            ctx,
+            &derivation_data,
+            accs.payer.key,
+            &accs.custody,
+            accs.mint.info(),
+            &accs.custody_signer,
+            &spl_token::id(),
+            spl_token::state::Account::LEN,
+        )?;
+    }
+
+    let trunc_divisor = 10u64.pow(8.max(accs.mint.decimals as u32) - 8);
+    // Truncate to 8 decimals
+    let amount: u64 = data.amount / trunc_divisor;
+    let fee: u64 = data.fee / trunc_divisor;
+    // Untruncate the amount to drop the remainder so we don't "burn" user's funds.
+    let amount_trunc: u64 = amount * trunc_divisor;
+
+    // Wrap the lamports into custody
+    let transfer_ix = solana_program::system_instruction::transfer(
+        accs.payer.key,
+        accs.custody.info().key,
+        amount_trunc,
     );
+    invoke(&transfer_ix, ctx.accounts)?;
+    let sync_ix = spl_token::instruction::sync_native(&spl_token::id(), accs.custody.info().key)?;
+    invoke(&sync_ix, ctx.accounts)?;
+
+    // Post message
+    let payload = PayloadTransfer {
+        amount: U256::from(amount),
+        token_address: accs.mint.info().key.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: data.target_address,
+        to_chain: data.target_chain,
+        fee: U256::from(fee),
+    };
+    let message = PostMessageData {
+        nonce: data.nonce,
+        payload: payload.try_to_vec()?,
+        consistency_level: data.consistency_level.level(),
+    };
+
+    match govern_transfer(
+        ctx,
+        &accs.config,
+        accs.payer.key,
+        accs.message.key,
+        &accs.clock,
+        accs.mint.info().key,
+        accs.mint.decimals,
+        amount,
+        data.target_chain,
+        message,
+    )? {
+        Some(message) => post_transfer_message(
+            ctx,
+            &accs.config,
+            &accs.payer,
+            &accs.bridge,
+            &accs.message,
+            &accs.emitter,
+            &accs.sequence,
+            &accs.fee_collector,
+            &accs.clock,
+            message,
+        ),
+        None => Ok(()),
+    }
+}
 
+/// Pay the message fee and post the message of a transfer through the core bridge.
+#[allow(clippy::too_many_arguments)]
+pub fn post_transfer_message(
+    ctx: &ExecutionContext,
+    config: &ConfigAccount<{ AccountState::Initialized }>,
+    payer: &Mut<Signer<AccountInfo>>,
+    bridge: &Mut<CoreBridge<{ AccountState::Initialized }>>,
+    message: &Signer<Mut<Info>>,
+    emitter: &EmitterAccount,
+    sequence: &Mut<Info>,
+    fee_collector: &Mut<Info>,
+    clock: &Sysvar<Clock>,
+    data: PostMessageData,
+) -> Result<()> {
+    post_transfer_message_signed(
+        ctx,
+        config,
+        payer,
+        bridge,
+        message,
+        None,
+        emitter,
+        sequence,
+        fee_collector,
+        clock,
+        data,
+    )
+}
+
+/// [`post_transfer_message`] to an account of this program rather than a keypair of the sender,
+/// signing for it with `message_seeds`.
+#[allow(clippy::too_many_arguments)]
+pub fn post_transfer_message_signed(
+    ctx: &ExecutionContext,
+    config: &ConfigAccount<{ AccountState::Initialized }>,
+    payer: &Mut<Signer<AccountInfo>>,
+    bridge: &Mut<CoreBridge<{ AccountState::Initialized }>>,
+    message: &AccountInfo,
+    message_seeds: Option<Vec<Vec<u8>>>,
+    emitter: &EmitterAccount,
+    sequence: &Mut<Info>,
+    fee_collector: &Mut<Info>,
+    clock: &Sysvar<Clock>,
+    data: PostMessageData,
+) -> Result<()> {
+    // Pay fee
+    let transfer_ix = solana_program::system_instruction::transfer(
+        payer.key,
+        fee_collector.key,
+        bridge.config.fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    let params = (bridge::instruction::Instruction::PostMessage, data);
     let ix = Instruction::new_with_bytes(
-        accs.config.wormhole_bridge,
+        config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
         vec![
-            AccountMeta::new(*accs.bridge.info().key, false),
-            AccountMeta::new(*accs.message.key, true),
-            AccountMeta::new_readonly(*accs.emitter.key, true),
-            AccountMeta::new(*accs.sequence.key, false),
-            AccountMeta::new(*accs.payer.key, true),
-            AccountMeta::new(*accs.fee_collector.key, false),
-            AccountMeta::new_readonly(*accs.clock.info().key, false),
+            AccountMeta::new(*bridge.info().key, false),
+            AccountMeta::new(*message.key, true),
+            AccountMeta::new_readonly(*emitter.key, true),
+            AccountMeta::new(*sequence.key, false),
+            AccountMeta::new(*payer.key, true),
+            AccountMeta::new(*fee_collector.key, false),
+            AccountMeta::new_readonly(*clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
         ],
     );
-    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
+
+    let mut seeds = vec![emitter.self_bumped_seeds(None, ctx.program_id)];
+    seeds.extend(message_seeds);
+    let seeds: Vec<Vec<&[u8]>> = seeds
+        .iter()
+        .map(|seeds| seeds.iter().map(Vec::as_slice).collect())
+        .collect();
+    let seeds: Vec<&[&[u8]]> = seeds.iter().map(Vec::as_slice).collect();
+    invoke_signed(&ix, ctx.accounts, &seeds)?;
 
     Ok(())
 }
@@ -173,13 +425,11 @@ pub fn verify_and_execute_native_transfers(
     ctx: &ExecutionContext,
     derivation_data: &CustodyAccountDerivationData,
     payer: &Mut<Signer<AccountInfo>>,
-    from: &Mut<Data<SplAccount, { AccountState::Initialized }>>,
-    mint: &Mut<Data<SplMint, { AccountState::Initialized }>>,
+    from: &Mut<Data<TokenAccount, { AccountState::Initialized }>>,
+    mint: &Mut<Data<TokenMint, { AccountState::Initialized }>>,
     custody: &Mut<CustodyAccount<{ AccountState::MaybeInitialized }>>,
     authority_signer: &AuthoritySigner,
     custody_signer: &CustodySigner,
-    bridge: &Mut<CoreBridge<{ AccountState::Initialized }>>,
-    fee_collector: &Mut<Info>,
     raw_amount: u64,
     raw_fee: u64,
 ) -> Result<(u64, u64)> {
@@ -203,47 +453,103 @@ pub fn verify_and_execute_native_transfers(
         }
     }
 
-    if !custody.is_initialized() {
-        custody.create(derivation_data, ctx, payer.key, Exempt)?;
+    let token_program = token_2022::token_program(mint.info(), &[from.info()])?;
+    let extensions = token_2022::mint_extensions(mint.info())?;
+    token_2022::verify_custody_compatible(&extensions)?;
 
-        let init_ix = spl_token::instruction::initialize_account(
-            &spl_token::id(),
-            custody.info().key,
-            mint.info().key,
-            custody_signer.key,
+    if !custody.is_initialized() {
+        create_custody(
+            ctx,
+            derivation_data,
+            payer.key,
+            custody,
+            mint.info(),
+            custody_signer,
+            &token_program,
+            token_2022::account_size(&extensions),
         )?;
-        invoke_signed(&init_ix, ctx.accounts, &[])?;
     }
 
     let trunc_divisor = 10u64.pow(8.max(mint.decimals as u32) - 8);
-    // Truncate to 8 decimals
-    let amount: u64 = raw_amount / trunc_divisor;
-    let fee: u64 = raw_fee / trunc_divisor;
     // Untruncate the amount to drop the remainder so we don't  "burn" user's funds.
-    let amount_trunc: u64 = amount * trunc_divisor;
+    let amount_trunc: u64 = (raw_amount / trunc_divisor) * trunc_divisor;
 
     // Transfer tokens
-    let transfer_ix = spl_token::instruction::transfer(
+    let custody_balance = token_2022::balance(custody.info())?;
+    let transfer_ix = spl_token::instruction::transfer_checked(
         &spl_token::id(),
         from.info().key,
+        mint.info().key,
         custody.info().key,
         authority_signer.key,
         &[],
         amount_trunc,
+        mint.decimals,
+    )?;
+    invoke_seeded(
+        &token_2022::with_program(transfer_ix, &token_program),
+        ctx,
+        authority_signer,
+        None,
     )?;
-    invoke_seeded(&transfer_ix, ctx, authority_signer, None)?;
 
-    // Pay fee
-    let transfer_ix = solana_program::system_instruction::transfer(
-        payer.key,
-        fee_collector.key,
-        bridge.config.fee,
-    );
-    invoke(&transfer_ix, ctx.accounts)?;
+    // Mints with a transfer fee withhold part of the transfer, only what arrived in custody is
+    // bridged. A remainder below 8 decimals stays in custody.
+    let received = token_2022::balance(custody.info())? - custody_balance;
+
+    // Truncate to 8 decimals
+    let amount: u64 = received / trunc_divisor;
+    let fee: u64 = raw_fee / trunc_divisor;
+    if fee > amount {
+        return Err(InvalidFee.into());
+    }
 
     Ok((amount, fee))
 }
 
+/// Create the custody account of a native mint, owned by the token program of the mint and sized
+/// for the account extensions the mint requires.
+#[allow(clippy::too_many_arguments)]
+pub fn create_custody(
+    ctx: &ExecutionContext,
+    derivation_data: &CustodyAccountDerivationData,
+    payer: &Pubkey,
+    custody: &Mut<CustodyAccount<{ AccountState::MaybeInitialized }>>,
+    mint: &AccountInfo,
+    custody_signer: &CustodySigner,
+    token_program: &Pubkey,
+    size: usize,
+) -> Result<()> {
+    let seeds = CustodyAccount::<'_, { AccountState::MaybeInitialized }>::bumped_seeds(
+        derivation_data,
+        ctx.program_id,
+    );
+    let seeds: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+    create_account(
+        ctx,
+        custody.info(),
+        payer,
+        Exempt,
+        size,
+        token_program,
+        SignedWithSeeds(&[seeds.as_slice()]),
+    )?;
+
+    let init_ix = spl_token::instruction::initialize_account(
+        &spl_token::id(),
+        custody.info().key,
+        mint.key,
+        custody_signer.key,
+    )?;
+    invoke_signed(
+        &token_2022::with_program(init_ix, token_program),
+        ctx.accounts,
+        &[],
+    )?;
+
+    Ok(())
+}
+
 #[derive(FromAccounts)]
 pub struct TransferWrapped<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
@@ -291,13 +597,15 @@ impl<'a> From<&TransferWrapped<'a>> for WrappedMetaDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct TransferWrappedData {
     pub nonce: u32,
     pub amount: u64,
     pub fee: u64,
     pub target_address: Address,
     pub target_chain: ChainID,
+    /// Must stay the last field, see [`TransferConsistency`]
+    pub consistency_level: TransferConsistency,
 }
 
 pub fn transfer_wrapped(
@@ -305,6 +613,10 @@ pub fn transfer_wrapped(
     accs: &mut TransferWrapped,
     data: TransferWrappedData,
 ) -> Result<()> {
+    if accs.config.paused {
+        return Err(Paused.into());
+    }
+
     // Prevent transferring to the same chain.
     if data.target_chain == CHAIN_ID_SOLANA {
         return Err(InvalidChain.into());
@@ -314,14 +626,11 @@ pub fn transfer_wrapped(
     verify_and_execute_wrapped_transfers(
         ctx,
         &derivation_data,
-        &accs.payer,
         &accs.from,
         &accs.from_owner,
         &accs.mint,
         &accs.wrapped_meta,
         &accs.authority_signer,
-        &accs.bridge,
-        &accs.fee_collector,
         data.amount,
         data.fee,
     )?;
@@ -335,47 +644,49 @@ pub fn transfer_wrapped(
         to_chain: data.target_chain,
         fee: U256::from(data.fee),
     };
-    let params = (
-        bridge::instruction::Instruction::PostMessage,
-        PostMessageData {
-            nonce: data.nonce,
-            payload: payload.try_to_vec()?,
-            consistency_level: ConsistencyLevel::Finalized,
-        },
-    );
-
-    let ix = Instruction::new_with_bytes(
-        accs.config.wormhole_bridge,
-        params.try_to_vec()?.as_slice(),
-        vec![
-            AccountMeta::new(*accs.bridge.info().key, false),
-            AccountMeta::new(*accs.message.key, true),
-            AccountMeta::new_readonly(*accs.emitter.key, true),
-            AccountMeta::new(*accs.sequence.key, false),
-            AccountMeta::new(*accs.payer.key, true),
-            AccountMeta::new(*accs.fee_collector.key, false),
-            AccountMeta::new_readonly(*accs.clock.info().key, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
-        ],
-    );
-    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
+    let message = PostMessageData {
+        nonce: data.nonce,
+        payload: payload.try_to_vec()?,
+        consistency_level: data.consistency_level.level(),
+    };
 
-    Ok(())
+    match govern_transfer(
+        ctx,
+        &accs.config,
+        accs.payer.key,
+        accs.message.key,
+        &accs.clock,
+        accs.mint.info().key,
+        accs.mint.decimals,
+        data.amount,
+        data.target_chain,
+        message,
+    )? {
+        Some(message) => post_transfer_message(
+            ctx,
+            &accs.config,
+            &accs.payer,
+            &accs.bridge,
+            &accs.message,
+            &accs.emitter,
+            &accs.sequence,
+            &accs.fee_collector,
+            &accs.clock,
+            message,
+        ),
+        None => Ok(()),
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
 pub fn verify_and_execute_wrapped_transfers(
     ctx: &ExecutionContext,
     derivation_data: &WrappedMetaDerivationData,
-    payer: &Mut<Signer<AccountInfo>>,
     from: &Mut<Data<SplAccount, { AccountState::Initialized }>>,
     from_owner: &MaybeMut<Signer<Info>>,
     mint: &Mut<WrappedMint<{ AccountState::Initialized }>>,
     wrapped_meta: &WrappedTokenMeta<{ AccountState::Initialized }>,
     authority_signer: &AuthoritySigner,
-    bridge: &Mut<CoreBridge<{ AccountState::Initialized }>>,
-    fee_collector: &Mut<Info>,
     amount: u64,
     fee: u64,
 ) -> Result<()> {
@@ -408,14 +719,44 @@ pub fn verify_and_execute_wrapped_transfers(
     )?;
     invoke_seeded(&burn_ix, ctx, authority_signer, None)?;
 
-    // Pay fee
-    let transfer_ix = solana_program::system_instruction::transfer(
-        payer.key,
-        fee_collector.key,
-        bridge.config.fee,
-    );
-
-    invoke(&transfer_ix, ctx.accounts)?;
-
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bridge::types::ConsistencyLevel;
+
+    #[test]
+    fn test_transfer_consistency() {
+        let data = TransferNativeData {
+            nonce: 7,
+            amount: 100,
+            fee: 1,
+            target_address: [3u8; 32],
+            target_chain: 2,
+            consistency_level: ConsistencyLevel::Confirmed.into(),
+        };
+        let bytes = data.try_to_vec().unwrap();
+        let data = TransferNativeData::try_from_slice(&bytes).unwrap();
+        assert!(matches!(
+            data.consistency_level.level(),
+            ConsistencyLevel::Confirmed
+        ));
+
+        // Clients predating the consistency level leave it out.
+        let legacy = &bytes[..bytes.len() - 2];
+        let data = TransferNativeData::try_from_slice(legacy).unwrap();
+        assert_eq!(data.target_chain, 2);
+        assert!(matches!(
+            data.consistency_level.level(),
+            ConsistencyLevel::Finalized
+        ));
+
+        let data = TransferNativeData::try_from_slice(&[legacy, &[0]].concat()).unwrap();
+        assert!(matches!(
+            data.consistency_level.level(),
+            ConsistencyLevel::Finalized
+        ));
+    }
+}
@@ -0,0 +1,354 @@
+use crate::{
+    accounts::{
+        AuthoritySigner,
+        ChainGovernor,
+        ConfigAccount,
+        CoreBridge,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        CustodySigner,
+        EmitterAccount,
+        PendingTransferAccount,
+        TokenGovernor,
+        WrappedMetaDerivationData,
+        WrappedMint,
+        WrappedTokenMeta,
+    },
+    messages::PayloadTransfer,
+    types::*,
+    TokenBridgeError::{
+        DuplicateBatchMint,
+        InvalidBatchSize,
+        InvalidChain,
+        Paused,
+    },
+};
+use bridge::{
+    api::PostMessageData,
+    vaa::SerializePayload,
+    CHAIN_ID_SOLANA,
+};
+use primitive_types::U256;
+use solana_program::{
+    account_info::AccountInfo,
+    pubkey::Pubkey,
+    sysvar::clock::Clock,
+};
+use solitaire::*;
+
+use super::{
+    post_transfer_message,
+    queue_transfer,
+    record_outflow,
+    verify_and_execute_native_transfers,
+    verify_and_execute_wrapped_transfers,
+};
+
+/// Most legs a batch may carry, bounded by the accounts and compute a transaction allows.
+pub const MAX_BATCH_LEGS: usize = 4;
+
+/// Number of accounts in [`TransferBatch`], the accounts of the legs follow them.
+const TRANSFER_BATCH_ACCOUNTS: usize = 10;
+
+/// Accounts shared by all legs of a batch, followed by a [`TransferBatchNative`] or
+/// [`TransferBatchWrapped`] per leg in the order of [`TransferBatchData::legs`].
+#[derive(FromAccounts)]
+pub struct TransferBatch<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub authority_signer: AuthoritySigner<'b>,
+
+    pub custody_signer: CustodySigner<'b>,
+
+    /// CPI Context
+    pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
+
+    /// Emitter of the VAAs
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fees
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+
+    /// Outflow limit towards the target chain, shared by all legs
+    pub chain_governor: Mut<ChainGovernor<'b, { AccountState::MaybeInitialized }>>,
+}
+
+/// Accounts of a leg moving a native token into custody.
+#[derive(FromAccounts)]
+pub struct TransferBatchNative<'b> {
+    pub from: Mut<Data<'b, TokenAccount, { AccountState::Initialized }>>,
+
+    /// Mint of either token program, see [`crate::token_2022`]
+    pub mint: Mut<Data<'b, TokenMint, { AccountState::Initialized }>>,
+
+    pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Account to store the posted message
+    pub message: Signer<Mut<Info<'b>>>,
+
+    /// Outflow limit of the mint, see [`record_outflow`]
+    pub token_governor: Mut<TokenGovernor<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Holds the message if the leg exceeds the outflow limits, see [`queue_transfer`]
+    pub pending: Mut<PendingTransferAccount<'b, { AccountState::MaybeInitialized }>>,
+}
+
+/// Accounts of a leg burning a wrapped token.
+#[derive(FromAccounts)]
+pub struct TransferBatchWrapped<'b> {
+    pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    pub from_owner: MaybeMut<Signer<Info<'b>>>,
+    pub mint: Mut<WrappedMint<'b, { AccountState::Initialized }>>,
+    pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
+
+    /// Account to store the posted message
+    pub message: Signer<Mut<Info<'b>>>,
+
+    /// Outflow limit of the mint, see [`record_outflow`]
+    pub token_governor: Mut<TokenGovernor<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Holds the message if the leg exceeds the outflow limits, see [`queue_transfer`]
+    pub pending: Mut<PendingTransferAccount<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TransferBatchKind {
+    Native,
+    Wrapped,
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Clone, Debug)]
+pub struct TransferBatchLeg {
+    pub kind: TransferBatchKind,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
+pub struct TransferBatchData {
+    /// Shared by the messages of all legs
+    pub nonce: u32,
+    pub legs: Vec<TransferBatchLeg>,
+    pub target_address: Address,
+    pub target_chain: ChainID,
+    /// Must stay the last field, see [`TransferConsistency`]
+    pub consistency_level: TransferConsistency,
+}
+
+pub fn transfer_batch(
+    ctx: &ExecutionContext,
+    accs: &mut TransferBatch,
+    data: TransferBatchData,
+) -> Result<()> {
+    if accs.config.paused {
+        return Err(Paused.into());
+    }
+
+    // Prevent transferring to the same chain.
+    if data.target_chain == CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+
+    if data.legs.is_empty() || data.legs.len() > MAX_BATCH_LEGS {
+        return Err(InvalidBatchSize.into());
+    }
+
+    // Legs are persisted one after the other, a mint appearing twice would have the outflow
+    // recorded by its first leg overwritten by the second.
+    let mut mints: Vec<Pubkey> = Vec::with_capacity(data.legs.len());
+    let accounts = &mut ctx.accounts[TRANSFER_BATCH_ACCOUNTS..].iter();
+    for leg in &data.legs {
+        match leg.kind {
+            TransferBatchKind::Native => {
+                let mut leg_accs: Box<TransferBatchNative> =
+                    FromAccounts::from(ctx.program_id, accounts, &())?;
+                verify_unique_mint(&mut mints, leg_accs.mint.info().key)?;
+                transfer_batch_native(ctx, accs, &mut leg_accs, &data, leg)?;
+                Persist::persist(leg_accs.as_ref(), ctx.program_id)?;
+            }
+            TransferBatchKind::Wrapped => {
+                let mut leg_accs: Box<TransferBatchWrapped> =
+                    FromAccounts::from(ctx.program_id, accounts, &())?;
+                verify_unique_mint(&mut mints, leg_accs.mint.info().key)?;
+                transfer_batch_wrapped(ctx, accs, &mut leg_accs, &data, leg)?;
+                Persist::persist(leg_accs.as_ref(), ctx.program_id)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn verify_unique_mint(mints: &mut Vec<Pubkey>, mint: &Pubkey) -> Result<()> {
+    if mints.contains(mint) {
+        return Err(DuplicateBatchMint.into());
+    }
+    mints.push(*mint);
+    Ok(())
+}
+
+/// Move a native leg into custody and post or queue its message.
+fn transfer_batch_native(
+    ctx: &ExecutionContext,
+    accs: &mut TransferBatch,
+    leg_accs: &mut TransferBatchNative,
+    data: &TransferBatchData,
+    leg: &TransferBatchLeg,
+) -> Result<()> {
+    let derivation_data = CustodyAccountDerivationData {
+        mint: *leg_accs.mint.info().key,
+    };
+    let (amount, fee) = verify_and_execute_native_transfers(
+        ctx,
+        &derivation_data,
+        &accs.payer,
+        &leg_accs.from,
+        &leg_accs.mint,
+        &leg_accs.custody,
+        &accs.authority_signer,
+        &accs.custody_signer,
+        leg.amount,
+        leg.fee,
+    )?;
+
+    let payload = PayloadTransfer {
+        amount: U256::from(amount),
+        token_address: leg_accs.mint.info().key.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: data.target_address,
+        to_chain: data.target_chain,
+        fee: U256::from(fee),
+    };
+    let mint = *leg_accs.mint.info().key;
+    let decimals = leg_accs.mint.decimals;
+    post_or_queue_leg(
+        ctx,
+        accs,
+        &mut leg_accs.token_governor,
+        &mut leg_accs.pending,
+        &leg_accs.message,
+        &mint,
+        decimals,
+        amount,
+        data,
+        payload,
+    )
+}
+
+/// Burn a wrapped leg and post or queue its message.
+fn transfer_batch_wrapped(
+    ctx: &ExecutionContext,
+    accs: &mut TransferBatch,
+    leg_accs: &mut TransferBatchWrapped,
+    data: &TransferBatchData,
+    leg: &TransferBatchLeg,
+) -> Result<()> {
+    let derivation_data = WrappedMetaDerivationData {
+        mint_key: *leg_accs.mint.info().key,
+    };
+    verify_and_execute_wrapped_transfers(
+        ctx,
+        &derivation_data,
+        &leg_accs.from,
+        &leg_accs.from_owner,
+        &leg_accs.mint,
+        &leg_accs.wrapped_meta,
+        &accs.authority_signer,
+        leg.amount,
+        leg.fee,
+    )?;
+
+    let payload = PayloadTransfer {
+        amount: U256::from(leg.amount),
+        token_address: leg_accs.wrapped_meta.token_address,
+        token_chain: leg_accs.wrapped_meta.chain,
+        to: data.target_address,
+        to_chain: data.target_chain,
+        fee: U256::from(leg.fee),
+    };
+    let mint = *leg_accs.mint.info().key;
+    let decimals = leg_accs.mint.decimals;
+    post_or_queue_leg(
+        ctx,
+        accs,
+        &mut leg_accs.token_governor,
+        &mut leg_accs.pending,
+        &leg_accs.message,
+        &mint,
+        decimals,
+        leg.amount,
+        data,
+        payload,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn post_or_queue_leg(
+    ctx: &ExecutionContext,
+    accs: &mut TransferBatch,
+    token_governor: &mut Mut<TokenGovernor<{ AccountState::MaybeInitialized }>>,
+    pending: &mut Mut<PendingTransferAccount<{ AccountState::MaybeInitialized }>>,
+    message_account: &Signer<Mut<Info>>,
+    mint: &Pubkey,
+    decimals: u8,
+    amount: u64,
+    data: &TransferBatchData,
+    payload: PayloadTransfer,
+) -> Result<()> {
+    let message = PostMessageData {
+        nonce: data.nonce,
+        payload: payload.try_to_vec()?,
+        consistency_level: data.consistency_level.level(),
+    };
+
+    if record_outflow(
+        ctx,
+        token_governor,
+        &mut accs.chain_governor,
+        &accs.clock,
+        mint,
+        decimals,
+        amount,
+        data.target_chain,
+    )? {
+        post_transfer_message(
+            ctx,
+            &accs.config,
+            &accs.payer,
+            &accs.bridge,
+            message_account,
+            &accs.emitter,
+            &accs.sequence,
+            &accs.fee_collector,
+            &accs.clock,
+            message,
+        )
+    } else {
+        queue_transfer(
+            ctx,
+            accs.payer.key,
+            pending,
+            message_account.key,
+            &accs.clock,
+            message,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solitaire::idl::IdlAccounts;
+
+    #[test]
+    fn test_transfer_batch_accounts() {
+        assert_eq!(TransferBatch::accounts().len(), TRANSFER_BATCH_ACCOUNTS);
+    }
+}
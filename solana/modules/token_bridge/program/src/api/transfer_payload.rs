@@ -14,30 +14,31 @@ use crate::{
     },
     messages::PayloadTransferWithPayload,
     types::*,
-    TokenBridgeError::InvalidChain,
+    TokenBridgeError::{
+        InvalidChain,
+        Paused,
+    },
 };
 use bridge::{
     api::PostMessageData,
-    types::ConsistencyLevel,
     vaa::SerializePayload,
     CHAIN_ID_SOLANA,
 };
 use primitive_types::U256;
 use solana_program::{
     account_info::AccountInfo,
-    instruction::{
-        AccountMeta,
-        Instruction,
-    },
     pubkey::Pubkey,
     sysvar::clock::Clock,
 };
 use solitaire::{
-    processors::seeded::invoke_seeded,
+    client::ClientAccount,
+    idl::IdlAccount,
     *,
 };
 
 use super::{
+    govern_transfer,
+    post_transfer_message,
     verify_and_execute_native_transfers,
     verify_and_execute_wrapped_transfers,
 };
@@ -59,6 +60,18 @@ impl<'a, 'b: 'a> Peel<'a, 'b> for SenderAccount<'b> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         MaybeMut::persist(&self.0, program_id)
     }
+
+    fn describe(account: &mut IdlAccount) {
+        MaybeMut::<Signer<Info<'b>>>::describe(account)
+    }
+}
+
+impl<'b> ClientAccount for SenderAccount<'b> {
+    type Key = Pubkey;
+
+    fn pubkey(key: &Self::Key, _program_id: &Pubkey) -> Pubkey {
+        *key
+    }
 }
 
 // May or may not be a PDA, so we don't use [`Derive`], instead implement
@@ -127,8 +140,8 @@ pub struct TransferNativeWithPayload<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
-    pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
-    pub mint: Mut<Data<'b, SplMint, { AccountState::Initialized }>>,
+    pub from: Mut<Data<'b, TokenAccount, { AccountState::Initialized }>>,
+    pub mint: Mut<Data<'b, TokenMint, { AccountState::Initialized }>>,
     pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
 
     // This could allow someone to race someone else's tx if they do the approval in a separate tx.
@@ -166,7 +179,7 @@ impl<'a> From<&TransferNativeWithPayload<'a>> for CustodyAccountDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct TransferNativeWithPayloadData {
     pub nonce: u32,
     pub amount: u64,
@@ -175,6 +188,8 @@ pub struct TransferNativeWithPayloadData {
     pub payload: Vec<u8>,
     /// See [`derive_sender_address`]
     pub cpi_program_id: Option<Pubkey>,
+    /// Must stay the last field, see [`TransferConsistency`]
+    pub consistency_level: TransferConsistency,
 }
 
 pub fn transfer_native_with_payload(
@@ -182,6 +197,10 @@ pub fn transfer_native_with_payload(
     accs: &mut TransferNativeWithPayload,
     data: TransferNativeWithPayloadData,
 ) -> Result<()> {
+    if accs.config.paused {
+        return Err(Paused.into());
+    }
+
     // Prevent transferring to the same chain.
     if data.target_chain == CHAIN_ID_SOLANA {
         return Err(InvalidChain.into());
@@ -197,8 +216,6 @@ pub fn transfer_native_with_payload(
         &accs.custody,
         &accs.authority_signer,
         &accs.custody_signer,
-        &accs.bridge,
-        &accs.fee_collector,
         data.amount,
         0,
     )?;
@@ -213,33 +230,38 @@ pub fn transfer_native_with_payload(
         from_address: accs.sender.derive_sender_address(&data.cpi_program_id)?,
         payload: data.payload,
     };
-    let params = (
-        bridge::instruction::Instruction::PostMessage,
-        PostMessageData {
-            nonce: data.nonce,
-            payload: payload.try_to_vec()?,
-            consistency_level: ConsistencyLevel::Finalized,
-        },
-    );
-
-    let ix = Instruction::new_with_bytes(
-        accs.config.wormhole_bridge,
-        params.try_to_vec()?.as_slice(),
-        vec![
-            AccountMeta::new(*accs.bridge.info().key, false),
-            AccountMeta::new(*accs.message.key, true),
-            AccountMeta::new_readonly(*accs.emitter.key, true),
-            AccountMeta::new(*accs.sequence.key, false),
-            AccountMeta::new(*accs.payer.key, true),
-            AccountMeta::new(*accs.fee_collector.key, false),
-            AccountMeta::new_readonly(*accs.clock.info().key, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
-        ],
-    );
-    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
-
-    Ok(())
+    let message = PostMessageData {
+        nonce: data.nonce,
+        payload: payload.try_to_vec()?,
+        consistency_level: data.consistency_level.level(),
+    };
+
+    match govern_transfer(
+        ctx,
+        &accs.config,
+        accs.payer.key,
+        accs.message.key,
+        &accs.clock,
+        accs.mint.info().key,
+        accs.mint.decimals,
+        amount,
+        data.target_chain,
+        message,
+    )? {
+        Some(message) => post_transfer_message(
+            ctx,
+            &accs.config,
+            &accs.payer,
+            &accs.bridge,
+            &accs.message,
+            &accs.emitter,
+            &accs.sequence,
+            &accs.fee_collector,
+            &accs.clock,
+            message,
+        ),
+        None => Ok(()),
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -295,7 +317,7 @@ impl<'a> From<&TransferWrappedWithPayload<'a>> for WrappedMetaDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSchema, BorshSerialize, Default)]
 pub struct TransferWrappedWithPayloadData {
     pub nonce: u32,
     pub amount: u64,
@@ -304,6 +326,8 @@ pub struct TransferWrappedWithPayloadData {
     pub payload: Vec<u8>,
     /// See [`derive_sender_address`]
     pub cpi_program_id: Option<Pubkey>,
+    /// Must stay the last field, see [`TransferConsistency`]
+    pub consistency_level: TransferConsistency,
 }
 
 pub fn transfer_wrapped_with_payload(
@@ -311,6 +335,10 @@ pub fn transfer_wrapped_with_payload(
     accs: &mut TransferWrappedWithPayload,
     data: TransferWrappedWithPayloadData,
 ) -> Result<()> {
+    if accs.config.paused {
+        return Err(Paused.into());
+    }
+
     // Prevent transferring to the same chain.
     if data.target_chain == CHAIN_ID_SOLANA {
         return Err(InvalidChain.into());
@@ -320,14 +348,11 @@ pub fn transfer_wrapped_with_payload(
     verify_and_execute_wrapped_transfers(
         ctx,
         &derivation_data,
-        &accs.payer,
         &accs.from,
         &accs.from_owner,
         &accs.mint,
         &accs.wrapped_meta,
         &accs.authority_signer,
-        &accs.bridge,
-        &accs.fee_collector,
         data.amount,
         0,
     )?;
@@ -342,31 +367,36 @@ pub fn transfer_wrapped_with_payload(
         from_address: accs.sender.derive_sender_address(&data.cpi_program_id)?,
         payload: data.payload,
     };
-    let params = (
-        bridge::instruction::Instruction::PostMessage,
-        PostMessageData {
-            nonce: data.nonce,
-            payload: payload.try_to_vec()?,
-            consistency_level: ConsistencyLevel::Finalized,
-        },
-    );
-
-    let ix = Instruction::new_with_bytes(
-        accs.config.wormhole_bridge,
-        params.try_to_vec()?.as_slice(),
-        vec![
-            AccountMeta::new(*accs.bridge.info().key, false),
-            AccountMeta::new(*accs.message.key, true),
-            AccountMeta::new_readonly(*accs.emitter.key, true),
-            AccountMeta::new(*accs.sequence.key, false),
-            AccountMeta::new(*accs.payer.key, true),
-            AccountMeta::new(*accs.fee_collector.key, false),
-            AccountMeta::new_readonly(*accs.clock.info().key, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
-        ],
-    );
-    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
-
-    Ok(())
+    let message = PostMessageData {
+        nonce: data.nonce,
+        payload: payload.try_to_vec()?,
+        consistency_level: data.consistency_level.level(),
+    };
+
+    match govern_transfer(
+        ctx,
+        &accs.config,
+        accs.payer.key,
+        accs.message.key,
+        &accs.clock,
+        accs.mint.info().key,
+        accs.mint.decimals,
+        data.amount,
+        data.target_chain,
+        message,
+    )? {
+        Some(message) => post_transfer_message(
+            ctx,
+            &accs.config,
+            &accs.payer,
+            &accs.bridge,
+            &accs.message,
+            &accs.emitter,
+            &accs.sequence,
+            &accs.fee_collector,
+            &accs.clock,
+            message,
+        ),
+        None => Ok(()),
+    }
 }
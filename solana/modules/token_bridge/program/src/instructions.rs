@@ -1,6 +1,10 @@
 use crate::{
     accounts::{
         AuthoritySigner,
+        CallbackAuthority,
+        CallbackAuthorityDerivationData,
+        ChainGovernor,
+        ChainGovernorDerivationData,
         ConfigAccount,
         CustodyAccount,
         CustodyAccountDerivationData,
@@ -9,8 +13,16 @@ use crate::{
         Endpoint,
         EndpointDerivationData,
         MintSigner,
+        PendingTransferAccount,
+        PendingTransferDerivationData,
+        ReleasedMessage,
+        ReleasedMessageDerivationData,
+        SolUnwrapAccount,
+        SolUnwrapDerivationData,
         SplTokenMeta,
         SplTokenMetaDerivationData,
+        TokenGovernor,
+        TokenGovernorDerivationData,
         WrappedDerivationData,
         WrappedMetaDerivationData,
         WrappedMint,
@@ -19,23 +31,35 @@ use crate::{
     api::{
         complete_transfer::{
             CompleteNativeData,
+            CompleteNativeSolData,
             CompleteWrappedData,
         },
         AttestTokenData,
         CreateWrappedData,
         RegisterChainData,
+        ReleaseTransferData,
         SenderAccount,
+        SetChainLimitData,
+        SetPausedData,
+        SetTokenLimitData,
+        TransferBatchData,
         TransferNativeData,
+        TransferNativeSolData,
         TransferWrappedData,
         UpgradeContractData,
     },
     messages::{
+        GovernancePayloadSetChainLimit,
+        GovernancePayloadSetTokenLimit,
         PayloadAssetMeta,
         PayloadGovernanceRegisterChain,
         PayloadTransfer,
         PayloadTransferWithPayload,
     },
+    token_2022,
+    CompleteNativeWithCallbackData,
     CompleteNativeWithPayloadData,
+    CompleteWrappedWithCallbackData,
     CompleteWrappedWithPayloadData,
     TransferNativeWithPayloadData,
     TransferWrappedWithPayloadData,
@@ -85,6 +109,8 @@ pub fn initialize(
     })
 }
 
+/// `to_owner` is the wallet the VAA is sent to if `to` is its associated token account, which the
+/// instruction creates if it does not exist yet.
 #[allow(clippy::too_many_arguments)]
 pub fn complete_native(
     program_id: Pubkey,
@@ -93,6 +119,7 @@ pub fn complete_native(
     message_key: Pubkey,
     vaa: PostVAAData,
     to: Pubkey,
+    to_owner: Option<Pubkey>,
     fee_recipient: Option<Pubkey>,
     mint: Pubkey,
     data: CompleteNativeData,
@@ -129,17 +156,91 @@ pub fn complete_native(
             AccountMeta::new(custody_key, false),
             AccountMeta::new_readonly(mint, false),
             AccountMeta::new_readonly(custody_signer_key, false),
+            AccountMeta::new_readonly(to_owner.unwrap_or(to), false),
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(token_2022::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
         ],
         data: (crate::instruction::Instruction::CompleteNative, data).try_to_vec()?,
     })
 }
 
+/// Required accounts
+///
+/// | name               | account                                       | signer |
+/// |--------------------+-----------------------------------------------+--------|
+/// | payer              | Pubkey                                        | true   |
+/// | config             | PDA(program_id, \["config"\])                 | false  |
+/// | vaa                | Pubkey                                        | false  |
+/// | claim              | PDA(bridge_id, \[emitter, chain, sequence\])  | false  |
+/// | chain_registration | PDA(program_id, \[chain, emitter\])           | false  |
+/// | to                 | Pubkey                                        | false  |
+/// | custody            | PDA(program_id, \[native mint\])              | false  |
+/// | mint               | native mint                                   | false  |
+/// | custody_signer     | PDA(program_id, \["custody_signer"\])         | false  |
+/// | unwrap             | PDA(program_id, \["sol", vaa\])               | false  |
+/// | rent               | rent sysvar                                   | false  |
+/// | system_program     | system program                                | false  |
+/// | bridge_id          | bridge_id program                             | false  |
+/// | spl_token          | spl_token program                             | false  |
+pub fn complete_native_sol(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    to: Pubkey,
+    data: CompleteNativeSolData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa.clone());
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+    let mint = spl_token::native_mint::id();
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let unwrap_key = SolUnwrapAccount::<'_, { AccountState::Uninitialized }>::key(
+        &SolUnwrapDerivationData { vaa: message_key },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            message_acc,
+            claim_acc,
+            AccountMeta::new_readonly(endpoint, false),
+            AccountMeta::new(to, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(custody_signer_key, false),
+            AccountMeta::new(unwrap_key, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: (crate::instruction::Instruction::CompleteNativeSol, data).try_to_vec()?,
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn complete_native_with_payload(
     program_id: Pubkey,
@@ -192,6 +293,7 @@ pub fn complete_native_with_payload(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(token_2022::id(), false),
         ],
         data: (
             crate::instruction::Instruction::CompleteNativeWithPayload,
@@ -201,6 +303,8 @@ pub fn complete_native_with_payload(
     })
 }
 
+/// `to_owner` is the wallet the VAA is sent to if `to` is its associated token account, which the
+/// instruction creates if it does not exist yet.
 #[allow(clippy::too_many_arguments)]
 pub fn complete_wrapped(
     program_id: Pubkey,
@@ -210,6 +314,7 @@ pub fn complete_wrapped(
     vaa: PostVAAData,
     payload: PayloadTransfer,
     to: Pubkey,
+    to_owner: Option<Pubkey>,
     fee_recipient: Option<Pubkey>,
     data: CompleteWrappedData,
 ) -> solitaire::Result<Instruction> {
@@ -252,12 +357,14 @@ pub fn complete_wrapped(
             AccountMeta::new(mint_key, false),
             AccountMeta::new_readonly(meta_key, false),
             AccountMeta::new_readonly(mint_authority_key, false),
+            AccountMeta::new_readonly(to_owner.unwrap_or(to), false),
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
         ],
         data: (crate::instruction::Instruction::CompleteWrapped, data).try_to_vec()?,
     })
@@ -331,6 +438,151 @@ pub fn complete_wrapped_with_payload(
     })
 }
 
+/// `to` must be owned by the redeemer PDA of the program the VAA is sent to, which is invoked once
+/// the tokens are moved with `callback_accounts` following the accounts listed in
+/// [`crate::api::complete_transfer_callback`].
+#[allow(clippy::too_many_arguments)]
+pub fn complete_native_with_callback(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: PayloadTransferWithPayload,
+    to: Pubkey,
+    mint: Pubkey,
+    callback_accounts: Vec<AccountMeta>,
+    data: CompleteNativeWithCallbackData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa.clone());
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let recipient_program = Pubkey::new(&payload.to);
+    let callback_authority_key = CallbackAuthority::key(
+        &CallbackAuthorityDerivationData { recipient_program },
+        &program_id,
+    );
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        message_acc,
+        claim_acc,
+        AccountMeta::new_readonly(endpoint, false),
+        AccountMeta::new(to, false),
+        AccountMeta::new(custody_key, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(custody_signer_key, false),
+        AccountMeta::new_readonly(recipient_program, false),
+        AccountMeta::new_readonly(callback_authority_key, false),
+    ];
+    accounts.extend(callback_accounts);
+    accounts.extend([
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(token_2022::id(), false),
+    ]);
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (
+            crate::instruction::Instruction::CompleteNativeWithCallback,
+            data,
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// See [`complete_native_with_callback`].
+#[allow(clippy::too_many_arguments)]
+pub fn complete_wrapped_with_callback(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: PayloadTransferWithPayload,
+    to: Pubkey,
+    callback_accounts: Vec<AccountMeta>,
+    data: CompleteWrappedWithCallbackData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa.clone());
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+    let mint_key = WrappedMint::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedDerivationData {
+            token_chain: payload.token_chain,
+            token_address: payload.token_address,
+        },
+        &program_id,
+    );
+    let meta_key = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
+        &WrappedMetaDerivationData { mint_key },
+        &program_id,
+    );
+    let mint_authority_key = MintSigner::key(None, &program_id);
+    let recipient_program = Pubkey::new(&payload.to);
+    let callback_authority_key = CallbackAuthority::key(
+        &CallbackAuthorityDerivationData { recipient_program },
+        &program_id,
+    );
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        message_acc,
+        claim_acc,
+        AccountMeta::new_readonly(endpoint, false),
+        AccountMeta::new(to, false),
+        AccountMeta::new(mint_key, false),
+        AccountMeta::new_readonly(meta_key, false),
+        AccountMeta::new_readonly(mint_authority_key, false),
+        AccountMeta::new_readonly(recipient_program, false),
+        AccountMeta::new_readonly(callback_authority_key, false),
+    ];
+    accounts.extend(callback_accounts);
+    accounts.extend([
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ]);
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (
+            crate::instruction::Instruction::CompleteWrappedWithCallback,
+            data,
+        )
+            .try_to_vec()?,
+    })
+}
+
 pub fn create_wrapped(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -428,6 +680,179 @@ pub fn register_chain(
     })
 }
 
+pub fn set_paused(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    data: SetPausedData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(config_key, false),
+            message_acc,
+            claim_acc,
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+        ],
+        data: (crate::instruction::Instruction::SetPaused, data).try_to_vec()?,
+    })
+}
+
+pub fn set_token_limit(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: GovernancePayloadSetTokenLimit,
+    data: SetTokenLimitData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
+    let mint = if payload.token_chain == CHAIN_ID_SOLANA {
+        Pubkey::new(&payload.token_address)
+    } else {
+        WrappedMint::<'_, { AccountState::Initialized }>::key(
+            &WrappedDerivationData {
+                token_chain: payload.token_chain,
+                token_address: payload.token_address,
+            },
+            &program_id,
+        )
+    };
+    let token_governor = TokenGovernor::<'_, { AccountState::Uninitialized }>::key(
+        &TokenGovernorDerivationData { mint },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(config_key, false),
+            AccountMeta::new(token_governor, false),
+            message_acc,
+            claim_acc,
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+        ],
+        data: (crate::instruction::Instruction::SetTokenLimit, data).try_to_vec()?,
+    })
+}
+
+pub fn set_chain_limit(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    payload: GovernancePayloadSetChainLimit,
+    data: SetChainLimitData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
+    let chain_governor = ChainGovernor::<'_, { AccountState::Uninitialized }>::key(
+        &ChainGovernorDerivationData {
+            chain: payload.chain,
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(config_key, false),
+            AccountMeta::new(chain_governor, false),
+            message_acc,
+            claim_acc,
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+        ],
+        data: (crate::instruction::Instruction::SetChainLimit, data).try_to_vec()?,
+    })
+}
+
+/// Post a transfer queued by the governor, `message_key` has to be the account the transfer was
+/// queued for. It does not sign, the message is posted to an account derived from the queued
+/// transfer. `refund` is the payer of the queued transfer, as stored in it.
+pub fn release_transfer(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    refund: Pubkey,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let pending_key = PendingTransferAccount::<'_, { AccountState::Initialized }>::key(
+        &PendingTransferDerivationData {
+            message: message_key,
+        },
+        &program_id,
+    );
+    let released_key = ReleasedMessage::key(
+        &ReleasedMessageDerivationData {
+            pending: pending_key,
+        },
+        &program_id,
+    );
+    let emitter_key = EmitterAccount::key(None, &program_id);
+
+    // Bridge keys
+    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let sequence_key = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter_key,
+        },
+        &bridge_id,
+    );
+    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new(pending_key, false),
+            AccountMeta::new(refund, false),
+            AccountMeta::new(bridge_config, false),
+            AccountMeta::new(released_key, false),
+            AccountMeta::new_readonly(emitter_key, false),
+            AccountMeta::new(sequence_key, false),
+            AccountMeta::new(fee_collector_key, false),
+            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+        ],
+        data: (
+            crate::instruction::Instruction::ReleaseTransfer,
+            ReleaseTransferData {
+                message: message_key,
+            },
+        )
+            .try_to_vec()?,
+    })
+}
+
 fn claimable_vaa(
     bridge_id: Pubkey,
     message_key: Pubkey,
@@ -448,6 +873,38 @@ fn claimable_vaa(
     )
 }
 
+/// Outflow limits of a transfer of `mint` to `target_chain`, and the account holding the transfer
+/// if it exceeds them.
+fn governor_accounts(
+    program_id: Pubkey,
+    mint: Pubkey,
+    target_chain: u16,
+    message_key: Pubkey,
+) -> (AccountMeta, AccountMeta, AccountMeta) {
+    let token_governor_key = TokenGovernor::<'_, { AccountState::Uninitialized }>::key(
+        &TokenGovernorDerivationData { mint },
+        &program_id,
+    );
+    let chain_governor_key = ChainGovernor::<'_, { AccountState::Uninitialized }>::key(
+        &ChainGovernorDerivationData {
+            chain: target_chain,
+        },
+        &program_id,
+    );
+    let pending_key = PendingTransferAccount::<'_, { AccountState::Uninitialized }>::key(
+        &PendingTransferDerivationData {
+            message: message_key,
+        },
+        &program_id,
+    );
+
+    (
+        AccountMeta::new(token_governor_key, false),
+        AccountMeta::new(chain_governor_key, false),
+        AccountMeta::new(pending_key, false),
+    )
+}
+
 /// Required accounts
 ///
 /// | name             | account                                                           | signer |
@@ -468,6 +925,10 @@ fn claimable_vaa(
 /// | system_program   | system program                                                    | false  |
 /// | bridge_id        | bridge_id program                                                 | false  |
 /// | spl_token        | spl_token program                                                 | false  |
+/// | token_2022       | token_2022 program                                                | false  |
+/// | token_governor   | PDA(program_id, \["governor", mint\])                             | false  |
+/// | chain_governor   | PDA(program_id, \["governor", target_chain\])                     | false  |
+/// | pending          | PDA(program_id, \["pending", message\])                           | false  |
 pub fn transfer_native(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -497,6 +958,9 @@ pub fn transfer_native(
     );
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
 
+    let (token_governor, chain_governor, pending) =
+        governor_accounts(program_id, mint, data.target_chain, message_key);
+
     let instruction = crate::instruction::Instruction::TransferNative;
 
     Ok(Instruction {
@@ -521,11 +985,97 @@ pub fn transfer_native(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(token_2022::id(), false),
+            // Governor, may be left out until governance sets a first limit
+            token_governor,
+            chain_governor,
+            pending,
         ],
         data: (instruction, data).try_to_vec()?,
     })
 }
 
+/// Required accounts
+///
+/// | name             | account                                       | signer |
+/// |------------------+-----------------------------------------------+--------|
+/// | payer            | Pubkey                                        | true   |
+/// | config           | PDA(program_id, \["config"\])                 | false  |
+/// | mint             | native mint                                   | false  |
+/// | custody          | PDA(program_id, \[native mint\])              | false  |
+/// | custody_signer   | PDA(program_id, \["custody_signer"\])         | false  |
+/// | bridge_config    | PDA(bridge_id,  \["Bridge"\])                 | false  |
+/// | message          | Pubkey                                        | true   |
+/// | emitter          | PDA(program_id, \["emitter"\])                | false  |
+/// | sequence         | PDA(bridge_id,  \["Sequence", emitter\])      | false  |
+/// | fee_collector    | PDA(bridge_id,  \["fee_collector"\])          | false  |
+/// | clock            | clock sysvar                                  | false  |
+/// | rent             | rent sysvar                                   | false  |
+/// | system_program   | system program                                | false  |
+/// | bridge_id        | bridge_id program                             | false  |
+/// | spl_token        | spl_token program                             | false  |
+/// | token_governor   | PDA(program_id, \["governor", native mint\])  | false  |
+/// | chain_governor   | PDA(program_id, \["governor", target_chain\]) | false  |
+/// | pending          | PDA(program_id, \["pending", message\])       | false  |
+pub fn transfer_native_sol(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    data: TransferNativeSolData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let mint = spl_token::native_mint::id();
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let emitter_key = EmitterAccount::key(None, &program_id);
+
+    // Bridge keys
+    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let sequence_key = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter_key,
+        },
+        &bridge_id,
+    );
+    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+
+    let (token_governor, chain_governor, pending) =
+        governor_accounts(program_id, mint, data.target_chain, message_key);
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(custody_signer_key, false),
+            AccountMeta::new(bridge_config, false),
+            AccountMeta::new(message_key, true),
+            AccountMeta::new_readonly(emitter_key, false),
+            AccountMeta::new(sequence_key, false),
+            AccountMeta::new(fee_collector_key, false),
+            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            // Governor, may be left out until governance sets a first limit
+            token_governor,
+            chain_governor,
+            pending,
+        ],
+        data: (crate::instruction::Instruction::TransferNativeSol, data).try_to_vec()?,
+    })
+}
+
 /// Required accounts
 ///
 /// | name             | account                                                                | signer |
@@ -548,6 +1098,10 @@ pub fn transfer_native(
 /// | system_program   | system program                                                         | false  |
 /// | bridge_id        | bridge_id program                                                      | false  |
 /// | spl_token        | spl_token program                                                      | false  |
+/// | token_2022       | token_2022 program                                                     | false  |
+/// | token_governor   | PDA(program_id, \["governor", mint\])                                  | false  |
+/// | chain_governor   | PDA(program_id, \["governor", target_chain\])                          | false  |
+/// | pending          | PDA(program_id, \["pending", message\])                                | false  |
 pub fn transfer_native_with_payload(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -582,6 +1136,9 @@ pub fn transfer_native_with_payload(
         None => payer,
     };
 
+    let (token_governor, chain_governor, pending) =
+        governor_accounts(program_id, mint, data.target_chain, message_key);
+
     let instruction = crate::instruction::Instruction::TransferNativeWithPayload;
 
     Ok(Instruction {
@@ -607,6 +1164,11 @@ pub fn transfer_native_with_payload(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(token_2022::id(), false),
+            // Governor, may be left out until governance sets a first limit
+            token_governor,
+            chain_governor,
+            pending,
         ],
         data: (instruction, data).try_to_vec()?,
     })
@@ -634,6 +1196,9 @@ pub fn transfer_native_with_payload(
 /// | bridge_id        | bridge_id program                                                      | false  |
 /// | spl_token        | spl_token program                                                      | false  |
 #[allow(clippy::too_many_arguments)]
+/// | token_governor   | PDA(program_id, \["governor", wrapped_mint\])                          | false  |
+/// | chain_governor   | PDA(program_id, \["governor", target_chain\])                          | false  |
+/// | pending          | PDA(program_id, \["pending", message\])                                | false  |
 pub fn transfer_wrapped(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -674,6 +1239,9 @@ pub fn transfer_wrapped(
     );
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
 
+    let (token_governor, chain_governor, pending) =
+        governor_accounts(program_id, wrapped_mint_key, data.target_chain, message_key);
+
     let instruction = crate::instruction::Instruction::TransferWrapped;
 
     Ok(Instruction {
@@ -698,6 +1266,10 @@ pub fn transfer_wrapped(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            // Governor, may be left out until governance sets a first limit
+            token_governor,
+            chain_governor,
+            pending,
         ],
         data: (instruction, data).try_to_vec()?,
     })
@@ -726,6 +1298,9 @@ pub fn transfer_wrapped(
 /// | bridge_id        | bridge_id program                                                      | false  |
 /// | spl_token        | spl_token program                                                      | false  |
 #[allow(clippy::too_many_arguments)]
+/// | token_governor   | PDA(program_id, \["governor", wrapped_mint\])                          | false  |
+/// | chain_governor   | PDA(program_id, \["governor", target_chain\])                          | false  |
+/// | pending          | PDA(program_id, \["pending", message\])                                | false  |
 pub fn transfer_wrapped_with_payload(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -771,6 +1346,9 @@ pub fn transfer_wrapped_with_payload(
         None => payer,
     };
 
+    let (token_governor, chain_governor, pending) =
+        governor_accounts(program_id, wrapped_mint_key, data.target_chain, message_key);
+
     let instruction = crate::instruction::Instruction::TransferWrappedWithPayload;
 
     Ok(Instruction {
@@ -796,11 +1374,192 @@ pub fn transfer_wrapped_with_payload(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            // Governor, may be left out until governance sets a first limit
+            token_governor,
+            chain_governor,
+            pending,
         ],
         data: (instruction, data).try_to_vec()?,
     })
 }
 
+/// Accounts of a leg of [`transfer_batch`], given in the order of the legs of its data.
+pub enum TransferBatchLegAccounts {
+    Native {
+        from: Pubkey,
+        mint: Pubkey,
+        message_key: Pubkey,
+    },
+    Wrapped {
+        from: Pubkey,
+        from_owner: Pubkey,
+        token_chain: u16,
+        token_address: ForeignAddress,
+        message_key: Pubkey,
+    },
+}
+
+/// Required accounts
+///
+/// | name             | account                                       | signer |
+/// |------------------+-----------------------------------------------+--------|
+/// | payer            | Pubkey                                        | true   |
+/// | config           | PDA(program_id, \["config"\])                 | false  |
+/// | authority_signer | PDA(program_id, \["authority_signer"\])       | false  |
+/// | custody_signer   | PDA(program_id, \["custody_signer"\])         | false  |
+/// | bridge_config    | PDA(bridge_id,  \["Bridge"\])                 | false  |
+/// | emitter          | PDA(program_id, \["emitter"\])                | false  |
+/// | sequence         | PDA(bridge_id,  \["Sequence", emitter\])      | false  |
+/// | fee_collector    | PDA(bridge_id,  \["fee_collector"\])          | false  |
+/// | clock            | clock sysvar                                  | false  |
+/// | chain_governor   | PDA(program_id, \["governor", target_chain\]) | false  |
+/// | legs             | accounts of each leg, see below               |        |
+/// | rent             | rent sysvar                                   | false  |
+/// | system_program   | system program                                | false  |
+/// | bridge_id        | bridge_id program                             | false  |
+/// | spl_token        | spl_token program                             | false  |
+/// | token_2022       | token_2022 program                            | false  |
+///
+/// Accounts of a native leg
+///
+/// | name             | account                                       | signer |
+/// |------------------+-----------------------------------------------+--------|
+/// | from             | Pubkey                                        | false  |
+/// | mint             | Pubkey                                        | false  |
+/// | custody          | PDA(program_id, \[mint\])                     | false  |
+/// | message          | Pubkey                                        | true   |
+/// | token_governor   | PDA(program_id, \["governor", mint\])         | false  |
+/// | pending          | PDA(program_id, \["pending", message\])       | false  |
+///
+/// Accounts of a wrapped leg
+///
+/// | name             | account                                                    | signer |
+/// |------------------+------------------------------------------------------------+--------|
+/// | from             | Pubkey                                                     | false  |
+/// | from_owner       | Pubkey                                                     | true   |
+/// | wrapped_mint     | PDA(program_id, \["wrapped", token_chain, token_address\]) | false  |
+/// | wrapped_meta     | PDA(program_id, \["meta", wrapped_mint\])                  | false  |
+/// | message          | Pubkey                                                     | true   |
+/// | token_governor   | PDA(program_id, \["governor", wrapped_mint\])              | false  |
+/// | pending          | PDA(program_id, \["pending", message\])                    | false  |
+pub fn transfer_batch(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    legs: &[TransferBatchLegAccounts],
+    data: TransferBatchData,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let authority_signer_key = AuthoritySigner::key(None, &program_id);
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let emitter_key = EmitterAccount::key(None, &program_id);
+
+    // Bridge keys
+    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let sequence_key = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter_key,
+        },
+        &bridge_id,
+    );
+    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+
+    let chain_governor_key = ChainGovernor::<'_, { AccountState::Uninitialized }>::key(
+        &ChainGovernorDerivationData {
+            chain: data.target_chain,
+        },
+        &program_id,
+    );
+
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(config_key, false),
+        AccountMeta::new_readonly(authority_signer_key, false),
+        AccountMeta::new_readonly(custody_signer_key, false),
+        AccountMeta::new(bridge_config, false),
+        AccountMeta::new_readonly(emitter_key, false),
+        AccountMeta::new(sequence_key, false),
+        AccountMeta::new(fee_collector_key, false),
+        AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+        AccountMeta::new(chain_governor_key, false),
+    ];
+
+    for leg in legs {
+        match *leg {
+            TransferBatchLegAccounts::Native {
+                from,
+                mint,
+                message_key,
+            } => {
+                let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+                    &CustodyAccountDerivationData { mint },
+                    &program_id,
+                );
+                let (token_governor, _, pending) =
+                    governor_accounts(program_id, mint, data.target_chain, message_key);
+                accounts.extend([
+                    AccountMeta::new(from, false),
+                    AccountMeta::new(mint, false),
+                    AccountMeta::new(custody_key, false),
+                    AccountMeta::new(message_key, true),
+                    token_governor,
+                    pending,
+                ]);
+            }
+            TransferBatchLegAccounts::Wrapped {
+                from,
+                from_owner,
+                token_chain,
+                token_address,
+                message_key,
+            } => {
+                let wrapped_mint_key = WrappedMint::<'_, { AccountState::Uninitialized }>::key(
+                    &WrappedDerivationData {
+                        token_chain,
+                        token_address,
+                    },
+                    &program_id,
+                );
+                let wrapped_meta_key = WrappedTokenMeta::<'_, { AccountState::Uninitialized }>::key(
+                    &WrappedMetaDerivationData {
+                        mint_key: wrapped_mint_key,
+                    },
+                    &program_id,
+                );
+                let (token_governor, _, pending) =
+                    governor_accounts(program_id, wrapped_mint_key, data.target_chain, message_key);
+                accounts.extend([
+                    AccountMeta::new(from, false),
+                    AccountMeta::new_readonly(from_owner, true),
+                    AccountMeta::new(wrapped_mint_key, false),
+                    AccountMeta::new_readonly(wrapped_meta_key, false),
+                    AccountMeta::new(message_key, true),
+                    token_governor,
+                    pending,
+                ]);
+            }
+        }
+    }
+
+    accounts.extend([
+        // Dependencies
+        AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        // Program
+        AccountMeta::new_readonly(bridge_id, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(token_2022::id(), false),
+    ]);
+
+    let instruction = crate::instruction::Instruction::TransferBatch;
+
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: (instruction, data).try_to_vec()?,
+    })
+}
+
 pub fn attest(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -18,18 +18,28 @@ pub mod wasm;
 pub mod accounts;
 pub mod api;
 pub mod messages;
+pub mod token_2022;
 pub mod types;
 
 pub use api::{
     attest_token,
     complete_native,
+    complete_native_sol,
+    complete_native_with_callback,
     complete_native_with_payload,
     complete_wrapped,
+    complete_wrapped_with_callback,
     complete_wrapped_with_payload,
     create_wrapped,
     initialize,
     register_chain,
+    release_transfer,
+    set_chain_limit,
+    set_paused,
+    set_token_limit,
+    transfer_batch,
     transfer_native,
+    transfer_native_sol,
     transfer_native_with_payload,
     transfer_wrapped,
     transfer_wrapped_with_payload,
@@ -38,10 +48,16 @@ pub use api::{
     AttestTokenData,
     CompleteNative,
     CompleteNativeData,
+    CompleteNativeSol,
+    CompleteNativeSolData,
+    CompleteNativeWithCallback,
+    CompleteNativeWithCallbackData,
     CompleteNativeWithPayload,
     CompleteNativeWithPayloadData,
     CompleteWrapped,
     CompleteWrappedData,
+    CompleteWrappedWithCallback,
+    CompleteWrappedWithCallbackData,
     CompleteWrappedWithPayload,
     CompleteWrappedWithPayloadData,
     CreateWrapped,
@@ -50,8 +66,20 @@ pub use api::{
     InitializeData,
     RegisterChain,
     RegisterChainData,
+    ReleaseTransfer,
+    ReleaseTransferData,
+    SetChainLimit,
+    SetChainLimitData,
+    SetPaused,
+    SetPausedData,
+    SetTokenLimit,
+    SetTokenLimitData,
+    TransferBatch,
+    TransferBatchData,
     TransferNative,
     TransferNativeData,
+    TransferNativeSol,
+    TransferNativeSolData,
     TransferNativeWithPayload,
     TransferNativeWithPayloadData,
     TransferWrapped,
@@ -75,6 +103,8 @@ pub(crate) static INVALID_VAAS: &[&str; 7] = &[
     "GvAarWUV8khMLrTRouzBh3xSr8AeLDXxoKNJ6FgxGyg5",
 ];
 
+#[derive(ErrorCode)]
+#[error_code(offset = 2000)]
 pub enum TokenBridgeError {
     AlreadyExecuted,
     InvalidChain,
@@ -89,12 +119,22 @@ pub enum TokenBridgeError {
     InvalidFee,
     InvalidRecipient,
     InvalidVAA,
-}
-
-impl From<TokenBridgeError> for SolitaireError {
-    fn from(t: TokenBridgeError) -> SolitaireError {
-        SolitaireError::Custom(t as u64)
-    }
+    Paused,
+    TransferAlreadyQueued,
+    TransferNotReleasable,
+    NonTransferableMint,
+    PermanentDelegateMint,
+    InvalidBatchSize,
+    DuplicateBatchMint,
+    InvalidAssociatedAccount,
+    StaleAttestation,
+    UnpricedToken,
+    TransferHookMint,
+    DefaultFrozenMint,
+    CloseableMint,
+    MissingGovernor,
+    InvalidRefund,
+    UnsupportedMintExtension,
 }
 
 solitaire! {
@@ -111,4 +151,13 @@ solitaire! {
     CompleteWrappedWithPayload => complete_wrapped_with_payload,
     TransferWrappedWithPayload => transfer_wrapped_with_payload,
     TransferNativeWithPayload => transfer_native_with_payload,
+    SetPaused => set_paused,
+    SetTokenLimit => set_token_limit,
+    SetChainLimit => set_chain_limit,
+    ReleaseTransfer => release_transfer,
+    TransferNativeSol => transfer_native_sol,
+    CompleteNativeSol => complete_native_sol,
+    TransferBatch => transfer_batch,
+    CompleteNativeWithCallback => complete_native_with_callback,
+    CompleteWrappedWithCallback => complete_wrapped_with_callback,
 }
@@ -369,10 +369,160 @@ impl SerializeGovernancePayload for GovernancePayloadUpgrade {
 impl DeserializeGovernancePayload for GovernancePayloadUpgrade {
 }
 
+#[derive(PartialEq, Debug)]
+pub struct GovernancePayloadSetPaused {
+    /// Whether transfers are refused from now on
+    pub paused: bool,
+}
+
+impl SerializePayload for GovernancePayloadSetPaused {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        self.write_governance_header(v)?;
+        v.write_u8(self.paused as u8)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetPaused
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let paused = match c.read_u8()? {
+            0 => false,
+            1 => true,
+            _ => return Err(InvalidAccountData.into()),
+        };
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetPaused { paused })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetPaused {
+    const MODULE: &'static str = "TokenBridge";
+    // 3 is RecoverChainId on the EVM token bridge.
+    const ACTION: u8 = 6;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetPaused {
+}
+
+#[derive(PartialEq, Debug)]
+pub struct GovernancePayloadSetTokenLimit {
+    /// Chain ID of the chain the token originates from
+    pub token_chain: ChainID,
+    /// Address of the token on its original chain
+    pub token_address: Address,
+    /// Notional value of one whole token
+    pub price: u64,
+    /// Notional value of the token that may leave Solana within a day
+    pub daily_limit: u64,
+}
+
+impl SerializePayload for GovernancePayloadSetTokenLimit {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        self.write_governance_header(v)?;
+        v.write_u16::<BigEndian>(self.token_chain)?;
+        v.write_all(&self.token_address)?;
+        v.write_u64::<BigEndian>(self.price)?;
+        v.write_u64::<BigEndian>(self.daily_limit)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetTokenLimit
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let token_chain = c.read_u16::<BigEndian>()?;
+        let mut token_address = [0u8; 32];
+        c.read_exact(&mut token_address)?;
+        let price = c.read_u64::<BigEndian>()?;
+        let daily_limit = c.read_u64::<BigEndian>()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetTokenLimit {
+            token_chain,
+            token_address,
+            price,
+            daily_limit,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetTokenLimit {
+    const MODULE: &'static str = "TokenBridge";
+    const ACTION: u8 = 4;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetTokenLimit {
+}
+
+#[derive(PartialEq, Debug)]
+pub struct GovernancePayloadSetChainLimit {
+    /// Chain ID of the destination chain
+    pub chain: ChainID,
+    /// Notional value that may leave Solana towards the chain within a day
+    pub daily_limit: u64,
+}
+
+impl SerializePayload for GovernancePayloadSetChainLimit {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        self.write_governance_header(v)?;
+        v.write_u16::<BigEndian>(self.chain)?;
+        v.write_u64::<BigEndian>(self.daily_limit)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetChainLimit
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let chain = c.read_u16::<BigEndian>()?;
+        let daily_limit = c.read_u64::<BigEndian>()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetChainLimit { chain, daily_limit })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetChainLimit {
+    const MODULE: &'static str = "TokenBridge";
+    const ACTION: u8 = 5;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetChainLimit {
+}
+
 #[cfg(feature = "no-entrypoint")]
 #[allow(unused_imports)]
 mod tests {
     use crate::messages::{
+        GovernancePayloadSetChainLimit,
+        GovernancePayloadSetPaused,
+        GovernancePayloadSetTokenLimit,
         GovernancePayloadUpgrade,
         PayloadAssetMeta,
         PayloadGovernanceRegisterChain,
@@ -440,6 +590,49 @@ mod tests {
         assert_eq!(original, deser);
     }
 
+    #[test]
+    pub fn test_serde_gov_set_paused() {
+        for paused in [false, true] {
+            let original = GovernancePayloadSetPaused { paused };
+
+            let data = original.try_to_vec().unwrap();
+            let deser = GovernancePayloadSetPaused::deserialize(&mut data.as_slice()).unwrap();
+
+            assert_eq!(original, deser);
+        }
+    }
+
+    #[test]
+    pub fn test_serde_gov_set_token_limit() {
+        let mut token_address = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut token_address);
+
+        let original = GovernancePayloadSetTokenLimit {
+            token_chain: 2,
+            token_address,
+            price: 1_500,
+            daily_limit: 1_000_000,
+        };
+
+        let data = original.try_to_vec().unwrap();
+        let deser = GovernancePayloadSetTokenLimit::deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(original, deser);
+    }
+
+    #[test]
+    pub fn test_serde_gov_set_chain_limit() {
+        let original = GovernancePayloadSetChainLimit {
+            chain: 2,
+            daily_limit: 5_000_000,
+        };
+
+        let data = original.try_to_vec().unwrap();
+        let deser = GovernancePayloadSetChainLimit::deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(original, deser);
+    }
+
     #[test]
     pub fn test_serde_gov_register_chain() {
         let mut endpoint_address = [0u8; 32];
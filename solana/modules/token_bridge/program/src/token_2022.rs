@@ -0,0 +1,361 @@
+//! Support for mints of the Token-2022 program. Its instructions and the base layouts of its
+//! accounts are those of the original token program, so instructions are built with `spl_token`
+//! and pointed at the program owning the mint. Extensions follow the base layout as
+//! type-length-value entries.
+
+use crate::TokenBridgeError::{
+    CloseableMint,
+    DefaultFrozenMint,
+    InvalidMint,
+    NonTransferableMint,
+    PermanentDelegateMint,
+    TransferHookMint,
+    UnsupportedMintExtension,
+    WrongAccountOwner,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+use solitaire::{
+    Result,
+    SolitaireError,
+};
+use spl_token::state::{
+    Account,
+    Multisig,
+};
+
+solana_program::declare_id!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+// Mint extension types, numbered as in the Token-2022 program.
+const TRANSFER_FEE_CONFIG: u16 = 1;
+const MINT_CLOSE_AUTHORITY: u16 = 3;
+const DEFAULT_ACCOUNT_STATE: u16 = 6;
+const NON_TRANSFERABLE: u16 = 9;
+const INTEREST_BEARING_CONFIG: u16 = 10;
+const PERMANENT_DELEGATE: u16 = 12;
+const TRANSFER_HOOK: u16 = 14;
+const METADATA_POINTER: u16 = 18;
+const TOKEN_METADATA: u16 = 19;
+
+/// Account type stored after the base layout of a mint with extensions.
+const ACCOUNT_TYPE_MINT: u8 = 1;
+
+/// State of new token accounts set by the DefaultAccountState extension, which freezes them.
+const ACCOUNT_STATE_FROZEN: u8 = 2;
+
+/// Token program owning `mint`, which has to own all of the token `accounts` passed along with it.
+pub fn token_program(mint: &AccountInfo, accounts: &[&AccountInfo]) -> Result<Pubkey> {
+    let program = *mint.owner;
+    if program != spl_token::id() && program != id() {
+        return Err(SolitaireError::InvalidOwner(program));
+    }
+    if accounts.iter().any(|account| *account.owner != program) {
+        return Err(WrongAccountOwner.into());
+    }
+    Ok(program)
+}
+
+/// Point an instruction built with `spl_token` at `token_program`.
+pub fn with_program(mut ix: Instruction, token_program: &Pubkey) -> Instruction {
+    ix.program_id = *token_program;
+    ix
+}
+
+/// Associated token account of `wallet` for `mint`, whose address depends on the token program.
+pub fn associated_token_address(wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &spl_associated_token_account::id(),
+    )
+    .0
+}
+
+/// Create the associated token account of `wallet` for `mint`. The associated token program
+/// takes the token program as an account, its client only builds the instruction for the original
+/// one.
+pub fn create_associated_token_account(
+    payer: &Pubkey,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let mut ix = spl_associated_token_account::instruction::create_associated_token_account(
+        payer, wallet, mint,
+    );
+    ix.accounts[1].pubkey = associated_token_address(wallet, mint, token_program);
+    ix.accounts[5].pubkey = *token_program;
+    ix
+}
+
+/// Extensions of a mint as their type and value, none for mints of the original token program.
+pub fn mint_extensions(mint: &AccountInfo) -> Result<Vec<(u16, Vec<u8>)>> {
+    let data = mint.data.borrow();
+    if *mint.owner != id() || data.len() <= Account::LEN {
+        return Ok(vec![]);
+    }
+    if data[Account::LEN] != ACCOUNT_TYPE_MINT {
+        return Err(InvalidMint.into());
+    }
+
+    let mut extensions = vec![];
+    let mut tlv = &data[Account::LEN + 1..];
+    while tlv.len() >= 4 {
+        let extension = u16::from_le_bytes([tlv[0], tlv[1]]);
+        let len = u16::from_le_bytes([tlv[2], tlv[3]]) as usize;
+        // Space reserved for extensions yet to be initialized is zeroed.
+        if extension == 0 {
+            break;
+        }
+        let value = tlv.get(4..4 + len).ok_or(InvalidMint)?;
+        extensions.push((extension, value.to_vec()));
+        tlv = &tlv[4 + len..];
+    }
+    Ok(extensions)
+}
+
+/// Accept only mints whose extensions are known to leave custody in control of its tokens. Fees
+/// withheld on transfer are accounted for by bridging what arrives, and interest and metadata only
+/// change how amounts are displayed.
+///
+/// Known extensions are rejected with a reason: non-transferable tokens could never be paid out
+/// again, a permanent delegate could move them out of custody at will, and a transfer hook could
+/// refuse to pay them out. Custody and recipient accounts frozen by default could not receive
+/// them, and a mint that can be closed could be recreated at its address with other decimals or
+/// extensions. Any other extension is rejected as well, since the program cannot tell what it
+/// does.
+pub fn verify_custody_compatible(extensions: &[(u16, Vec<u8>)]) -> Result<()> {
+    for (extension, value) in extensions {
+        match *extension {
+            TRANSFER_FEE_CONFIG | INTEREST_BEARING_CONFIG | METADATA_POINTER | TOKEN_METADATA => {}
+            DEFAULT_ACCOUNT_STATE if value.first() == Some(&ACCOUNT_STATE_FROZEN) => {
+                return Err(DefaultFrozenMint.into())
+            }
+            DEFAULT_ACCOUNT_STATE => {}
+            // The authority is zeroed if it is unset, and can then never be set again.
+            MINT_CLOSE_AUTHORITY if value.iter().any(|byte| *byte != 0) => {
+                return Err(CloseableMint.into())
+            }
+            MINT_CLOSE_AUTHORITY => {}
+            NON_TRANSFERABLE => return Err(NonTransferableMint.into()),
+            PERMANENT_DELEGATE => return Err(PermanentDelegateMint.into()),
+            TRANSFER_HOOK => return Err(TransferHookMint.into()),
+            _ => return Err(UnsupportedMintExtension.into()),
+        }
+    }
+    Ok(())
+}
+
+/// Size of a token account for a mint with `extensions`, including the account extensions those
+/// require to be present on initialization.
+pub fn account_size(extensions: &[(u16, Vec<u8>)]) -> usize {
+    let required: Vec<usize> = extensions
+        .iter()
+        .filter_map(|(extension, _)| match *extension {
+            // TransferFeeAmount, the fees withheld from the account
+            TRANSFER_FEE_CONFIG => Some(8),
+            // NonTransferableAccount
+            NON_TRANSFERABLE => Some(0),
+            // TransferHookAccount, the transferring flag
+            TRANSFER_HOOK => Some(1),
+            _ => None,
+        })
+        .collect();
+    if required.is_empty() {
+        return Account::LEN;
+    }
+
+    let size = Account::LEN + 1 + required.iter().map(|len| 4 + len).sum::<usize>();
+    // Sizes matching a multisig are padded to tell the two apart.
+    if size == Multisig::LEN {
+        size + 2
+    } else {
+        size
+    }
+}
+
+/// Balance of a token account, read from the account itself to include transfers made earlier in
+/// the same instruction.
+pub fn balance(account: &AccountInfo) -> Result<u64> {
+    let data = account.data.borrow();
+    let base = data
+        .get(..Account::LEN)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    Ok(Account::unpack_from_slice(base)?.amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TokenBridgeError;
+    use solana_program::account_info::AccountInfo;
+    use spl_token::state::Mint;
+
+    /// Mint data with the given extensions as (type, value) pairs.
+    fn mint_data(extensions: &[(u16, &[u8])]) -> Vec<u8> {
+        let mut data = vec![0u8; Mint::LEN];
+        Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Mint::default()
+        }
+        .pack_into_slice(&mut data);
+        if extensions.is_empty() {
+            return data;
+        }
+
+        data.resize(Account::LEN, 0);
+        data.push(ACCOUNT_TYPE_MINT);
+        for (extension, value) in extensions {
+            data.extend_from_slice(&extension.to_le_bytes());
+            data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+            data.extend_from_slice(value);
+        }
+        data
+    }
+
+    fn extensions_of(owner: &Pubkey, mut data: Vec<u8>) -> Result<Vec<(u16, Vec<u8>)>> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            owner,
+            false,
+            0,
+        );
+        mint_extensions(&info)
+    }
+
+    #[test]
+    fn test_mint_extensions() {
+        let data = mint_data(&[
+            (TRANSFER_FEE_CONFIG, &[0; 108]),
+            (DEFAULT_ACCOUNT_STATE, &[1]),
+        ]);
+        let expected = vec![
+            (TRANSFER_FEE_CONFIG, vec![0; 108]),
+            (DEFAULT_ACCOUNT_STATE, vec![1]),
+        ];
+        assert_eq!(extensions_of(&id(), data.clone()).unwrap(), expected);
+
+        // Mints of the original token program have no extensions.
+        assert!(extensions_of(&spl_token::id(), mint_data(&[]))
+            .unwrap()
+            .is_empty());
+        assert!(extensions_of(&id(), mint_data(&[])).unwrap().is_empty());
+
+        // Zeroed space reserved for more extensions ends the list.
+        let mut padded = data.clone();
+        padded.resize(data.len() + 16, 0);
+        assert_eq!(extensions_of(&id(), padded).unwrap(), expected);
+
+        // Truncated extension values are rejected.
+        let mut truncated = data;
+        truncated.truncate(truncated.len() - 1);
+        assert!(extensions_of(&id(), truncated).is_err());
+    }
+
+    #[test]
+    fn test_verify_custody_compatible() {
+        let rejection = |extensions: &[(u16, Vec<u8>)]| {
+            ProgramError::from(verify_custody_compatible(extensions).unwrap_err())
+        };
+
+        // Extensions that leave custody in control of its tokens are accepted, as are unset close
+        // authorities and accounts initialized by default.
+        assert!(verify_custody_compatible(&[
+            (TRANSFER_FEE_CONFIG, vec![0; 108]),
+            (MINT_CLOSE_AUTHORITY, vec![0; 32]),
+            (DEFAULT_ACCOUNT_STATE, vec![1]),
+            (INTEREST_BEARING_CONFIG, vec![0; 52]),
+            (METADATA_POINTER, vec![0; 64]),
+            (TOKEN_METADATA, vec![0; 80]),
+        ])
+        .is_ok());
+
+        // Extensions the program does not know, such as confidential transfers, are rejected.
+        assert_eq!(
+            rejection(&[(TRANSFER_FEE_CONFIG, vec![0; 108]), (4, vec![0; 65])]),
+            SolitaireError::from(TokenBridgeError::UnsupportedMintExtension).into()
+        );
+        assert_eq!(
+            rejection(&[(u16::MAX, vec![])]),
+            SolitaireError::from(TokenBridgeError::UnsupportedMintExtension).into()
+        );
+
+        assert_eq!(
+            rejection(&[
+                (TRANSFER_FEE_CONFIG, vec![0; 108]),
+                (NON_TRANSFERABLE, vec![])
+            ]),
+            SolitaireError::from(TokenBridgeError::NonTransferableMint).into()
+        );
+        assert_eq!(
+            rejection(&[(PERMANENT_DELEGATE, vec![1; 32])]),
+            SolitaireError::from(TokenBridgeError::PermanentDelegateMint).into()
+        );
+        assert_eq!(
+            rejection(&[(TRANSFER_HOOK, vec![1; 64])]),
+            SolitaireError::from(TokenBridgeError::TransferHookMint).into()
+        );
+        assert_eq!(
+            rejection(&[(DEFAULT_ACCOUNT_STATE, vec![ACCOUNT_STATE_FROZEN])]),
+            SolitaireError::from(TokenBridgeError::DefaultFrozenMint).into()
+        );
+        assert_eq!(
+            rejection(&[(MINT_CLOSE_AUTHORITY, vec![1; 32])]),
+            SolitaireError::from(TokenBridgeError::CloseableMint).into()
+        );
+    }
+
+    #[test]
+    fn test_associated_token_address() {
+        let wallet = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        assert_eq!(
+            associated_token_address(&wallet, &mint, &spl_token::id()),
+            spl_associated_token_account::get_associated_token_address(&wallet, &mint)
+        );
+        assert_ne!(
+            associated_token_address(&wallet, &mint, &id()),
+            associated_token_address(&wallet, &mint, &spl_token::id())
+        );
+
+        let payer = Pubkey::new_unique();
+        let ix = create_associated_token_account(&payer, &wallet, &mint, &id());
+        assert_eq!(
+            ix.accounts[1].pubkey,
+            associated_token_address(&wallet, &mint, &id())
+        );
+        assert_eq!(ix.accounts[5].pubkey, id());
+    }
+
+    #[test]
+    fn test_account_size() {
+        assert_eq!(account_size(&[]), Account::LEN);
+        // Mint extensions without an account counterpart do not grow the account.
+        assert_eq!(
+            account_size(&[(MINT_CLOSE_AUTHORITY, vec![0; 32])]),
+            Account::LEN
+        );
+        assert_eq!(
+            account_size(&[(TRANSFER_FEE_CONFIG, vec![0; 108])]),
+            Account::LEN + 1 + 4 + 8
+        );
+        assert_eq!(
+            account_size(&[
+                (TRANSFER_FEE_CONFIG, vec![0; 108]),
+                (TRANSFER_HOOK, vec![0; 64])
+            ]),
+            Account::LEN + 1 + 4 + 8 + 4 + 1
+        );
+    }
+}
@@ -1,18 +1,25 @@
 use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
+use bridge::types::ConsistencyLevel;
 use serde::{
     Deserialize,
     Serialize,
 };
-use solana_program::pubkey::Pubkey;
+use solana_program::{
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
 use solitaire::{
     pack_type,
     processors::seeded::{
         AccountOwner,
         Owned,
     },
+    Migrate,
+    Versioned,
 };
 use spl_token::state::{
     Account,
@@ -25,6 +32,63 @@ pub type ChainID = u16;
 #[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 pub struct Config {
     pub wormhole_bridge: Pubkey,
+
+    /// Transfers in and out are refused while set, see [`crate::api::set_paused`].
+    pub paused: bool,
+
+    /// Set by the first outflow limit, outbound transfers have to pass the governor accounts from
+    /// then on, see [`crate::api::TransferGovernor`].
+    pub governed: bool,
+}
+
+/// Config as stored in its account, at the layout it was last migrated to.
+pub type VersionedConfig = Versioned<Config>;
+
+impl Migrate for Config {
+    const VERSION: u8 = 2;
+    type Previous = ConfigV1;
+
+    fn migrate(previous: ConfigV1) -> Self {
+        Config {
+            wormhole_bridge: previous.wormhole_bridge,
+            paused: previous.paused,
+            governed: false,
+        }
+    }
+}
+
+/// Config as written before outflow limits were set.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ConfigV1 {
+    pub wormhole_bridge: Pubkey,
+    pub paused: bool,
+}
+
+impl Migrate for ConfigV1 {
+    const VERSION: u8 = 1;
+    type Previous = ConfigV0;
+
+    fn migrate(previous: ConfigV0) -> Self {
+        ConfigV1 {
+            wormhole_bridge: previous.wormhole_bridge,
+            paused: false,
+        }
+    }
+}
+
+/// Config as written before transfers could be paused.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ConfigV0 {
+    pub wormhole_bridge: Pubkey,
+}
+
+impl Migrate for ConfigV0 {
+    const VERSION: u8 = 0;
+    type Previous = Self;
+
+    fn migrate(previous: Self) -> Self {
+        previous
+    }
 }
 
 #[cfg(not(feature = "cpi"))]
@@ -68,6 +132,49 @@ pub struct WrappedMeta {
     pub chain: ChainID,
     pub token_address: Address,
     pub original_decimals: u8,
+
+    /// Sequence of the attestation the metadata was last set from, only attestations with a
+    /// higher sequence are applied. Zero for mints created before it was tracked.
+    pub last_updated_sequence: u64,
+
+    /// Unix timestamp of the Solana clock when the metadata was last set, zero for mints created
+    /// before it was tracked.
+    pub last_updated: i64,
+}
+
+/// Wrapped meta as stored in its account, at the layout it was last migrated to.
+pub type VersionedWrappedMeta = Versioned<WrappedMeta>;
+
+impl Migrate for WrappedMeta {
+    const VERSION: u8 = 1;
+    type Previous = WrappedMetaV0;
+
+    fn migrate(previous: WrappedMetaV0) -> Self {
+        WrappedMeta {
+            chain: previous.chain,
+            token_address: previous.token_address,
+            original_decimals: previous.original_decimals,
+            last_updated_sequence: 0,
+            last_updated: 0,
+        }
+    }
+}
+
+/// Wrapped meta as written before attestations were ordered.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct WrappedMetaV0 {
+    pub chain: ChainID,
+    pub token_address: Address,
+    pub original_decimals: u8,
+}
+
+impl Migrate for WrappedMetaV0 {
+    const VERSION: u8 = 0;
+    type Previous = Self;
+
+    fn migrate(previous: Self) -> Self {
+        previous
+    }
 }
 
 #[cfg(not(feature = "cpi"))]
@@ -85,5 +192,219 @@ impl Owned for WrappedMeta {
     }
 }
 
+/// Notional value sent out over the last day, in hourly buckets.
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct Outflow {
+    /// Hour since the unix epoch of the latest bucket
+    pub hour: u64,
+    pub buckets: [u64; 24],
+}
+
+impl Outflow {
+    /// Notional value sent out within the day ending at `hour`.
+    pub fn total(&self, hour: u64) -> u64 {
+        let mut outflow = *self;
+        outflow.advance(hour);
+        outflow
+            .buckets
+            .iter()
+            .fold(0, |total, bucket| total.saturating_add(*bucket))
+    }
+
+    pub fn record(&mut self, hour: u64, notional: u64) {
+        self.advance(hour);
+        let bucket = &mut self.buckets[(hour % 24) as usize];
+        *bucket = bucket.saturating_add(notional);
+    }
+
+    /// Clear the buckets of hours that left the window. Timestamps going back an hour are
+    /// counted in the latest bucket.
+    fn advance(&mut self, hour: u64) {
+        if hour <= self.hour {
+            return;
+        }
+        for stale in (self.hour + 1)..=hour.min(self.hour + 24) {
+            self.buckets[(stale % 24) as usize] = 0;
+        }
+        self.hour = hour;
+    }
+}
+
+/// Outflow limit of a mint, see [`crate::api::set_token_limit`].
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct TokenLimit {
+    /// Notional value of one whole token
+    pub price: u64,
+    /// Notional value that may leave within a day
+    pub daily_limit: u64,
+    pub outflow: Outflow,
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for TokenLimit {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for TokenLimit {
+    fn owner(&self) -> AccountOwner {
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("TOKEN_BRIDGE_ADDRESS")).unwrap())
+    }
+}
+
+/// Outflow limit towards a chain, see [`crate::api::set_chain_limit`].
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct ChainLimit {
+    /// Notional value that may leave within a day
+    pub daily_limit: u64,
+    pub outflow: Outflow,
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for ChainLimit {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for ChainLimit {
+    fn owner(&self) -> AccountOwner {
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("TOKEN_BRIDGE_ADDRESS")).unwrap())
+    }
+}
+
+/// Consistency level a transfer asks its message to be posted at, finalized unless set. It is the
+/// last field of the transfer instructions and may be left out entirely, so instruction data of
+/// clients predating it still reads as a finalized transfer.
+#[derive(Clone, Default, BorshSchema)]
+pub struct TransferConsistency(pub Option<ConsistencyLevel>);
+
+impl TransferConsistency {
+    pub fn level(&self) -> ConsistencyLevel {
+        self.0.clone().unwrap_or(ConsistencyLevel::Finalized)
+    }
+}
+
+impl From<ConsistencyLevel> for TransferConsistency {
+    fn from(level: ConsistencyLevel) -> Self {
+        TransferConsistency(Some(level))
+    }
+}
+
+impl BorshSerialize for TransferConsistency {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.0, writer)
+    }
+}
+
+impl BorshDeserialize for TransferConsistency {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        if buf.is_empty() {
+            return Ok(TransferConsistency(None));
+        }
+        BorshDeserialize::deserialize(buf).map(TransferConsistency)
+    }
+}
+
+/// Message of a transfer queued by the governor, posted by [`crate::api::release_transfer`].
+#[derive(Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+pub struct PendingTransfer {
+    pub release_time: i64,
+
+    /// Paid the rent of the account, which is refunded to it on release.
+    pub payer: Pubkey,
+
+    pub nonce: u32,
+    pub consistency_level: ConsistencyLevel,
+    pub payload: Vec<u8>,
+}
+
+impl Default for PendingTransfer {
+    fn default() -> Self {
+        PendingTransfer {
+            release_time: 0,
+            payer: Pubkey::default(),
+            nonce: 0,
+            consistency_level: ConsistencyLevel::Finalized,
+            payload: vec![],
+        }
+    }
+}
+
+#[cfg(not(feature = "cpi"))]
+impl Owned for PendingTransfer {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+#[cfg(feature = "cpi")]
+impl Owned for PendingTransfer {
+    fn owner(&self) -> AccountOwner {
+        use std::str::FromStr;
+        AccountOwner::Other(Pubkey::from_str(env!("TOKEN_BRIDGE_ADDRESS")).unwrap())
+    }
+}
+
 pack_type!(SplMint, Mint, AccountOwner::Other(spl_token::id()));
 pack_type!(SplAccount, Account, AccountOwner::Other(spl_token::id()));
+
+/// Packs a base token layout into a type accepting accounts of either token program. Only the base
+/// layout is read, Token-2022 extensions following it are left to [`crate::token_2022`], and so is
+/// checking the owner.
+macro_rules! token_pack_type {
+    ($name:ident, $embed:ty) => {
+        #[repr(transparent)]
+        pub struct $name(pub $embed);
+
+        impl BorshDeserialize for $name {
+            fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+                let base = buf
+                    .get(..<$embed as Pack>::LEN)
+                    .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::UnexpectedEof))?;
+                let acc = $name(
+                    Pack::unpack(base)
+                        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?,
+                );
+                *buf = &buf[..0];
+
+                Ok(acc)
+            }
+        }
+
+        impl BorshSerialize for $name {
+            fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                let mut data = [0u8; <$embed as Pack>::LEN];
+                Pack::pack_into_slice(&self.0, &mut data);
+                writer.write_all(&data)
+            }
+        }
+
+        impl Owned for $name {
+            fn owner(&self) -> AccountOwner {
+                AccountOwner::Any
+            }
+        }
+
+        impl std::ops::Deref for $name {
+            type Target = $embed;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl std::default::Default for $name {
+            fn default() -> Self {
+                $name(<$embed>::default())
+            }
+        }
+    };
+}
+
+token_pack_type!(TokenMint, Mint);
+token_pack_type!(TokenAccount, Account);
@@ -3,6 +3,8 @@ use crate::{
         AuthoritySigner,
         CustodySigner,
         EmitterAccount,
+        PendingTransferAccount,
+        PendingTransferDerivationData,
         WrappedDerivationData,
         WrappedMetaDerivationData,
         WrappedMint,
@@ -11,30 +13,52 @@ use crate::{
     instructions::{
         attest,
         complete_native,
+        complete_native_sol,
+        complete_native_with_payload,
         complete_wrapped,
+        complete_wrapped_with_payload,
         create_wrapped,
         register_chain,
+        release_transfer,
+        set_chain_limit,
+        set_paused,
+        set_token_limit,
         transfer_native,
+        transfer_native_sol,
         transfer_native_with_payload,
         transfer_wrapped,
         transfer_wrapped_with_payload,
         upgrade_contract,
     },
     messages::{
+        GovernancePayloadSetChainLimit,
+        GovernancePayloadSetTokenLimit,
         GovernancePayloadUpgrade,
         PayloadAssetMeta,
         PayloadGovernanceRegisterChain,
         PayloadTransfer,
+        PayloadTransferWithPayload,
     },
     types::{
+        Address,
+        ChainID,
         EndpointRegistration,
-        WrappedMeta,
+        PendingTransfer,
+        TransferConsistency,
+        VersionedWrappedMeta,
     },
     CompleteNativeData,
+    CompleteNativeSolData,
+    CompleteNativeWithPayloadData,
     CompleteWrappedData,
+    CompleteWrappedWithPayloadData,
     CreateWrappedData,
     RegisterChainData,
+    SetChainLimitData,
+    SetPausedData,
+    SetTokenLimitData,
     TransferNativeData,
+    TransferNativeSolData,
     TransferNativeWithPayloadData,
     TransferWrappedData,
     TransferWrappedWithPayloadData,
@@ -43,10 +67,12 @@ use borsh::BorshDeserialize;
 use bridge::{
     accounts::PostedVAADerivationData,
     instructions::hash_vaa,
+    types::ConsistencyLevel,
     vaa::VAA,
     DeserializePayload,
     PostVAAData,
 };
+use serde::Serialize;
 use solana_program::pubkey::Pubkey;
 use solitaire::{
     processors::seeded::Seeded,
@@ -55,6 +81,34 @@ use solitaire::{
 use std::str::FromStr;
 use wasm_bindgen::prelude::*;
 
+/// Consistency level of a transfer, `CONFIRMED` or `FINALIZED`, finalized if left out.
+fn transfer_consistency(consistency: Option<String>) -> TransferConsistency {
+    TransferConsistency(consistency.map(|consistency| match consistency.as_str() {
+        "CONFIRMED" => ConsistencyLevel::Confirmed,
+        "FINALIZED" => ConsistencyLevel::Finalized,
+        _ => panic!("invalid consistency level"),
+    }))
+}
+
+/// Account receiving a transfer sent to `to`, and the wallet owning it if `to` names a wallet whose
+/// associated token account `to_token_account` is.
+fn recipient_accounts(to: &Address, to_token_account: Option<String>) -> (Pubkey, Option<Pubkey>) {
+    let to = Pubkey::new(&to[..]);
+    match to_token_account {
+        Some(account) => (Pubkey::from_str(account.as_str()).unwrap(), Some(to)),
+        None => (to, None),
+    }
+}
+
+/// Signer redeeming a transfer with payload sent to `to`, which is `to` itself for wallets and a PDA
+/// of it for programs, see [`crate::api::RedeemerAccount`].
+fn redeemer_account(to: &Address, redeemer: Option<String>) -> Pubkey {
+    match redeemer {
+        Some(redeemer) => Pubkey::from_str(redeemer.as_str()).unwrap(),
+        None => Pubkey::new(&to[..]),
+    }
+}
+
 #[wasm_bindgen]
 pub fn attest_ix(
     program_id: String,
@@ -88,6 +142,7 @@ pub fn transfer_native_ix(
     fee: u64,
     target_address: Vec<u8>,
     target_chain: u16,
+    consistency: Option<String>,
 ) -> JsValue {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
     let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
@@ -112,6 +167,47 @@ pub fn transfer_native_ix(
             fee,
             target_address: target_addr,
             target_chain,
+            consistency_level: transfer_consistency(consistency),
+        },
+    )
+    .unwrap();
+
+    JsValue::from_serde(&ix).unwrap()
+}
+
+#[wasm_bindgen]
+pub fn transfer_native_sol_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    message: String,
+    nonce: u32,
+    amount: u64,
+    fee: u64,
+    target_address: Vec<u8>,
+    target_chain: u16,
+    consistency: Option<String>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let message = Pubkey::from_str(message.as_str()).unwrap();
+
+    let mut target_addr = [0u8; 32];
+    target_addr.copy_from_slice(target_address.as_slice());
+
+    let ix = transfer_native_sol(
+        program_id,
+        bridge_id,
+        payer,
+        message,
+        TransferNativeSolData {
+            nonce,
+            amount,
+            fee,
+            target_address: target_addr,
+            target_chain,
+            consistency_level: transfer_consistency(consistency),
         },
     )
     .unwrap();
@@ -132,6 +228,7 @@ pub fn transfer_native_with_payload_ix(
     target_address: Vec<u8>,
     target_chain: u16,
     payload: Vec<u8>,
+    consistency: Option<String>,
 ) -> JsValue {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
     let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
@@ -157,6 +254,7 @@ pub fn transfer_native_with_payload_ix(
             target_chain,
             payload,
             cpi_program_id: None,
+            consistency_level: transfer_consistency(consistency),
         },
     )
     .unwrap();
@@ -179,6 +277,7 @@ pub fn transfer_wrapped_ix(
     fee: u64,
     target_address: Vec<u8>,
     target_chain: u16,
+    consistency: Option<String>,
 ) -> JsValue {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
     let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
@@ -207,6 +306,7 @@ pub fn transfer_wrapped_ix(
             fee,
             target_address: target_addr,
             target_chain,
+            consistency_level: transfer_consistency(consistency),
         },
     )
     .unwrap();
@@ -229,6 +329,7 @@ pub fn transfer_wrapped_with_payload_ix(
     target_address: Vec<u8>,
     target_chain: u16,
     payload: Vec<u8>,
+    consistency: Option<String>,
 ) -> JsValue {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
     let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
@@ -258,6 +359,7 @@ pub fn transfer_wrapped_with_payload_ix(
             target_chain,
             payload,
             cpi_program_id: None,
+            consistency_level: transfer_consistency(consistency),
         },
     )
     .unwrap();
@@ -272,12 +374,14 @@ pub fn complete_transfer_native_ix(
     payer: String,
     vaa: Vec<u8>,
     fee_recipient: Option<String>,
+    to_token_account: Option<String>,
 ) -> JsValue {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
     let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
     let payer = Pubkey::from_str(payer.as_str()).unwrap();
     let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
     let payload = PayloadTransfer::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let (to, to_owner) = recipient_accounts(&payload.to, to_token_account);
     let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
         &PostedVAADerivationData {
             payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
@@ -302,7 +406,8 @@ pub fn complete_transfer_native_ix(
         payer,
         message_key,
         post_vaa_data,
-        Pubkey::new(&payload.to[..]),
+        to,
+        to_owner,
         if let Some(fee_r) = fee_recipient {
             Some(Pubkey::from_str(fee_r.as_str()).unwrap())
         } else {
@@ -316,6 +421,102 @@ pub fn complete_transfer_native_ix(
     JsValue::from_serde(&ix).unwrap()
 }
 
+#[wasm_bindgen]
+pub fn complete_transfer_native_with_payload_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+    to: String,
+    fee_recipient: Option<String>,
+    redeemer: Option<String>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let to = Pubkey::from_str(to.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload = PayloadTransferWithPayload::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let redeemer = redeemer_account(&payload.to, redeemer);
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let post_vaa_data = PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload,
+    };
+
+    let ix = complete_native_with_payload(
+        program_id,
+        bridge_id,
+        payer,
+        message_key,
+        post_vaa_data,
+        to,
+        redeemer,
+        fee_recipient.map(|fee_r| Pubkey::from_str(fee_r.as_str()).unwrap()),
+        Pubkey::new(&payload.token_address),
+        CompleteNativeWithPayloadData {},
+    )
+    .unwrap();
+
+    JsValue::from_serde(&ix).unwrap()
+}
+
+#[wasm_bindgen]
+pub fn complete_transfer_native_sol_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload = PayloadTransfer::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let post_vaa_data = PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload,
+    };
+
+    let ix = complete_native_sol(
+        program_id,
+        bridge_id,
+        payer,
+        message_key,
+        post_vaa_data,
+        Pubkey::new(&payload.to[..]),
+        CompleteNativeSolData {},
+    )
+    .unwrap();
+
+    JsValue::from_serde(&ix).unwrap()
+}
+
 #[wasm_bindgen]
 pub fn complete_transfer_wrapped_ix(
     program_id: String,
@@ -323,12 +524,14 @@ pub fn complete_transfer_wrapped_ix(
     payer: String,
     vaa: Vec<u8>,
     fee_recipient: Option<String>,
+    to_token_account: Option<String>,
 ) -> JsValue {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
     let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
     let payer = Pubkey::from_str(payer.as_str()).unwrap();
     let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
     let payload = PayloadTransfer::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let (to, to_owner) = recipient_accounts(&payload.to, to_token_account);
     let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
         &PostedVAADerivationData {
             payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
@@ -354,7 +557,8 @@ pub fn complete_transfer_wrapped_ix(
         message_key,
         post_vaa_data,
         payload.clone(),
-        Pubkey::new(&payload.to),
+        to,
+        to_owner,
         if let Some(fee_r) = fee_recipient {
             Some(Pubkey::from_str(fee_r.as_str()).unwrap())
         } else {
@@ -367,6 +571,58 @@ pub fn complete_transfer_wrapped_ix(
     JsValue::from_serde(&ix).unwrap()
 }
 
+#[wasm_bindgen]
+pub fn complete_transfer_wrapped_with_payload_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+    to: String,
+    fee_recipient: Option<String>,
+    redeemer: Option<String>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let to = Pubkey::from_str(to.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload = PayloadTransferWithPayload::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let redeemer = redeemer_account(&payload.to, redeemer);
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let post_vaa_data = PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload,
+    };
+
+    let ix = complete_wrapped_with_payload(
+        program_id,
+        bridge_id,
+        payer,
+        message_key,
+        post_vaa_data,
+        payload,
+        to,
+        redeemer,
+        fee_recipient.map(|fee_r| Pubkey::from_str(fee_r.as_str()).unwrap()),
+        CompleteWrappedWithPayloadData {},
+    )
+    .unwrap();
+
+    JsValue::from_serde(&ix).unwrap()
+}
+
 #[wasm_bindgen]
 pub fn create_wrapped_ix(
     program_id: String,
@@ -484,6 +740,149 @@ pub fn register_chain_ix(
     return JsValue::from_serde(&ix).unwrap();
 }
 
+#[wasm_bindgen]
+pub fn set_paused_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let post_vaa_data = PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload,
+    };
+    let ix = set_paused(
+        program_id,
+        bridge_id,
+        payer,
+        message_key,
+        post_vaa_data,
+        SetPausedData {},
+    )
+    .unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn set_token_limit_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload = GovernancePayloadSetTokenLimit::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let post_vaa_data = PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload,
+    };
+    let ix = set_token_limit(
+        program_id,
+        bridge_id,
+        payer,
+        message_key,
+        post_vaa_data,
+        payload,
+        SetTokenLimitData {},
+    )
+    .unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn set_chain_limit_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let payload = GovernancePayloadSetChainLimit::deserialize(&mut vaa.payload.as_slice()).unwrap();
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let post_vaa_data = PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload,
+    };
+    let ix = set_chain_limit(
+        program_id,
+        bridge_id,
+        payer,
+        message_key,
+        post_vaa_data,
+        payload,
+        SetChainLimitData {},
+    )
+    .unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn release_transfer_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    message: String,
+    refund: String,
+) -> JsValue {
+    let ix = release_transfer(
+        Pubkey::from_str(program_id.as_str()).unwrap(),
+        Pubkey::from_str(bridge_id.as_str()).unwrap(),
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        Pubkey::from_str(message.as_str()).unwrap(),
+        Pubkey::from_str(refund.as_str()).unwrap(),
+    )
+    .unwrap();
+    JsValue::from_serde(&ix).unwrap()
+}
+
 #[wasm_bindgen]
 pub fn emitter_address(program_id: String) -> Vec<u8> {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
@@ -538,12 +937,114 @@ pub fn wrapped_meta_address(program_id: String, mint_address: Vec<u8>) -> Vec<u8
     wrapped_meta_addr.to_bytes().to_vec()
 }
 
+#[wasm_bindgen]
+pub fn pending_transfer_address(program_id: String, message: Vec<u8>) -> Vec<u8> {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let message = Pubkey::new(message.as_slice());
+    let pending_key = PendingTransferAccount::<'_, { AccountState::Initialized }>::key(
+        &PendingTransferDerivationData { message },
+        &program_id,
+    );
+
+    pending_key.to_bytes().to_vec()
+}
+
 #[wasm_bindgen]
 pub fn parse_wrapped_meta(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&WrappedMeta::try_from_slice(data.as_slice()).unwrap()).unwrap()
+    JsValue::from_serde(
+        &VersionedWrappedMeta::try_from_slice(data.as_slice())
+            .unwrap()
+            .data,
+    )
+    .unwrap()
 }
 
 #[wasm_bindgen]
 pub fn parse_endpoint_registration(data: Vec<u8>) -> JsValue {
     JsValue::from_serde(&EndpointRegistration::try_from_slice(data.as_slice()).unwrap()).unwrap()
 }
+
+#[wasm_bindgen]
+pub fn parse_pending_transfer(data: Vec<u8>) -> JsValue {
+    JsValue::from_serde(&PendingTransfer::try_from_slice(data.as_slice()).unwrap()).unwrap()
+}
+
+/// Token bridge payload as handed to JavaScript, uint256 amounts as decimal strings.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum ParsedPayload {
+    Transfer {
+        payload_id: u8,
+        amount: String,
+        token_address: Address,
+        token_chain: ChainID,
+        to: Address,
+        to_chain: ChainID,
+        fee: String,
+    },
+    AssetMeta {
+        payload_id: u8,
+        token_address: Address,
+        token_chain: ChainID,
+        decimals: u8,
+        symbol: String,
+        name: String,
+    },
+    TransferWithPayload {
+        payload_id: u8,
+        amount: String,
+        token_address: Address,
+        token_chain: ChainID,
+        to: Address,
+        to_chain: ChainID,
+        from_address: Address,
+        payload: Vec<u8>,
+    },
+}
+
+/// Parse the payload of a token bridge VAA, a transfer (1), an attestation (2) or a transfer with
+/// payload (3), told apart by `payload_id`.
+#[wasm_bindgen]
+pub fn parse_transfer_payload(data: Vec<u8>) -> JsValue {
+    let payload = match data.first() {
+        Some(1) => {
+            let payload = PayloadTransfer::deserialize(&mut data.as_slice()).unwrap();
+            ParsedPayload::Transfer {
+                payload_id: 1,
+                amount: payload.amount.to_string(),
+                token_address: payload.token_address,
+                token_chain: payload.token_chain,
+                to: payload.to,
+                to_chain: payload.to_chain,
+                fee: payload.fee.to_string(),
+            }
+        }
+        Some(2) => {
+            let payload = PayloadAssetMeta::deserialize(&mut data.as_slice()).unwrap();
+            ParsedPayload::AssetMeta {
+                payload_id: 2,
+                token_address: payload.token_address,
+                token_chain: payload.token_chain,
+                decimals: payload.decimals,
+                symbol: payload.symbol,
+                name: payload.name,
+            }
+        }
+        Some(3) => {
+            let payload = PayloadTransferWithPayload::deserialize(&mut data.as_slice()).unwrap();
+            ParsedPayload::TransferWithPayload {
+                payload_id: 3,
+                amount: payload.amount.to_string(),
+                token_address: payload.token_address,
+                token_chain: payload.token_chain,
+                to: payload.to,
+                to_chain: payload.to_chain,
+                from_address: payload.from_address,
+                payload: payload.payload,
+            }
+        }
+        _ => panic!("unknown payload id"),
+    };
+
+    JsValue::from_serde(&payload).unwrap()
+}
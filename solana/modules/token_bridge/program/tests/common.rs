@@ -9,7 +9,10 @@ use libsecp256k1::{
 };
 use sha3::Digest;
 use solana_program::{
-    instruction::Instruction,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
     program_pack::Pack,
     pubkey::Pubkey,
     system_instruction,
@@ -41,10 +44,14 @@ use std::{
 
 use token_bridge::{
     instructions,
+    token_2022,
     types::*,
 };
 
-use solitaire::processors::seeded::Seeded;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+};
 
 pub use helpers::*;
 
@@ -71,18 +78,43 @@ mod helpers {
         types::ConsistencyLevel,
         PostVAAData,
     };
+    use solana_program::{
+        account_info::AccountInfo,
+        entrypoint::ProgramResult,
+        program::invoke,
+        program_error::ProgramError,
+    };
     use solana_program_test::processor;
     use token_bridge::{
+        accounts::{
+            CallbackAuthority,
+            CallbackAuthorityDerivationData,
+        },
+        api::{
+            ReceiveTransferData,
+            TransferBatchLeg,
+            RECEIVE_TRANSFER_DISCRIMINATOR,
+        },
         CompleteNativeData,
+        CompleteNativeSolData,
+        CompleteNativeWithCallbackData,
         CompleteNativeWithPayloadData,
         CompleteWrappedData,
+        CompleteWrappedWithCallbackData,
         CreateWrappedData,
         RegisterChainData,
+        SetChainLimitData,
+        SetPausedData,
+        SetTokenLimitData,
+        TransferBatchData,
         TransferNativeData,
+        TransferNativeSolData,
         TransferWrappedData,
     };
 
     use token_bridge::messages::{
+        GovernancePayloadSetChainLimit,
+        GovernancePayloadSetTokenLimit,
         PayloadAssetMeta,
         PayloadGovernanceRegisterChain,
         PayloadTransfer,
@@ -116,6 +148,84 @@ mod helpers {
         )
     }
 
+    /// Program receiving transfers completed with a callback, see [`callback_receiver`].
+    pub fn callback_receiver_id() -> Pubkey {
+        Pubkey::new(&[7u8; 32])
+    }
+
+    /// Program forwarding the transfers it receives to another, see [`callback_forwarder`].
+    pub fn callback_forwarder_id() -> Pubkey {
+        Pubkey::new(&[8u8; 32])
+    }
+
+    fn token_bridge_id() -> Pubkey {
+        env::var("TOKEN_BRIDGE_PROGRAM")
+            .unwrap_or_else(|_| "B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE".to_string())
+            .parse::<Pubkey>()
+            .unwrap()
+    }
+
+    /// The token bridge must have signed with the callback authority of `program_id`.
+    fn verify_callback_authority(program_id: &Pubkey, authority: &AccountInfo) -> ProgramResult {
+        let derivation_data = CallbackAuthorityDerivationData {
+            recipient_program: *program_id,
+        };
+        if !authority.is_signer
+            || *authority.key != CallbackAuthority::key(&derivation_data, &token_bridge_id())
+        {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        Ok(())
+    }
+
+    /// Recipient program accepting an invocation only if the token bridge signed it, and the
+    /// amount it is told of is what its token account holds.
+    fn callback_receiver(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        data: &[u8],
+    ) -> ProgramResult {
+        verify_callback_authority(program_id, &accounts[0])?;
+
+        let data = data
+            .strip_prefix(&RECEIVE_TRANSFER_DISCRIMINATOR[..])
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let data = ReceiveTransferData::try_from_slice(data)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+        let to = spl_token::state::Account::unpack(&accounts[1].data.borrow())?;
+        if to.amount != data.amount || to.mint != *accounts[2].key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    /// Recipient program invoking [`callback_receiver`] with the accounts and data it was invoked
+    /// with, if the receiver follows the accounts of the interface.
+    fn callback_forwarder(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        data: &[u8],
+    ) -> ProgramResult {
+        verify_callback_authority(program_id, &accounts[0])?;
+
+        if *accounts[4].key != callback_receiver_id() {
+            return Ok(());
+        }
+        let ix = Instruction {
+            program_id: callback_receiver_id(),
+            accounts: accounts[..4]
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: *account.key,
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: data.to_vec(),
+        };
+        invoke(&ix, accounts)
+    }
+
     /// Initialize the test environment, spins up a solana-test-validator in the background so that
     /// each test has a fresh environment to work within.
     pub async fn setup() -> (BanksClient, Keypair, Pubkey, Pubkey) {
@@ -124,10 +234,7 @@ mod helpers {
                 .unwrap_or_else(|_| "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o".to_string())
                 .parse::<Pubkey>()
                 .unwrap(),
-            env::var("TOKEN_BRIDGE_PROGRAM")
-                .unwrap_or_else(|_| "B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE".to_string())
-                .parse::<Pubkey>()
-                .unwrap(),
+            token_bridge_id(),
         );
 
         let mut builder = ProgramTest::new("bridge", program, processor!(bridge::solitaire));
@@ -137,6 +244,16 @@ mod helpers {
             token_program,
             processor!(token_bridge::solitaire),
         );
+        builder.add_program(
+            "callback_receiver",
+            callback_receiver_id(),
+            processor!(callback_receiver),
+        );
+        builder.add_program(
+            "callback_forwarder",
+            callback_forwarder_id(),
+            processor!(callback_forwarder),
+        );
 
         // Some instructions go over the limit when tracing is enabled but we need that for better
         // logging.  We don't really care about the limit during these tests anyway.
@@ -302,6 +419,7 @@ mod helpers {
                 fee: 0,
                 target_address: [0u8; 32],
                 target_chain: 2,
+                consistency_level: TransferConsistency::default(),
             },
         )
         .expect("Could not create Transfer Native");
@@ -331,6 +449,94 @@ mod helpers {
         .await
     }
 
+    /// Transfer as callers predating the governor do, without its trailing accounts.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn transfer_native_without_governor(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        payer: &Keypair,
+        message: &Keypair,
+        from: &Keypair,
+        from_owner: &Keypair,
+        mint: Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let mut instruction = instructions::transfer_native(
+            program,
+            bridge,
+            payer.pubkey(),
+            message.pubkey(),
+            from.pubkey(),
+            mint,
+            TransferNativeData {
+                nonce: 0,
+                amount,
+                fee: 0,
+                target_address: [0u8; 32],
+                target_chain: 2,
+                consistency_level: TransferConsistency::default(),
+            },
+        )
+        .expect("Could not create Transfer Native");
+        let governor = instruction.accounts.len() - 3;
+        instruction.accounts.truncate(governor);
+
+        execute(
+            client,
+            payer,
+            &[payer, from_owner, message],
+            &[
+                spl_token::instruction::approve(
+                    &spl_token::id(),
+                    &from.pubkey(),
+                    &token_bridge::accounts::AuthoritySigner::key(None, &program),
+                    &from_owner.pubkey(),
+                    &[],
+                    amount,
+                )
+                .unwrap(),
+                instruction,
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn transfer_native_sol(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        payer: &Keypair,
+        message: &Keypair,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::transfer_native_sol(
+            program,
+            bridge,
+            payer.pubkey(),
+            message.pubkey(),
+            TransferNativeSolData {
+                nonce: 0,
+                amount,
+                fee: 0,
+                target_address: [0u8; 32],
+                target_chain: 2,
+                consistency_level: TransferConsistency::default(),
+            },
+        )
+        .expect("Could not create Transfer Native SOL");
+
+        execute(
+            client,
+            payer,
+            &[payer, message],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn transfer_wrapped(
         client: &mut BanksClient,
@@ -359,6 +565,7 @@ mod helpers {
                 fee: 0,
                 target_address: [5u8; 32],
                 target_chain: 2,
+                consistency_level: TransferConsistency::default(),
             },
         )
         .expect("Could not create Transfer Native");
@@ -388,6 +595,67 @@ mod helpers {
         .await
     }
 
+    /// Approve and send a batch whose legs all transfer from accounts owned by `from_owner`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn transfer_batch(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        payer: &Keypair,
+        messages: &[&Keypair],
+        from_owner: &Keypair,
+        legs: Vec<(instructions::TransferBatchLegAccounts, TransferBatchLeg)>,
+    ) -> Result<(), BanksClientError> {
+        let authority_signer = token_bridge::accounts::AuthoritySigner::key(None, &program);
+        let mut instructions = vec![];
+        for (accounts, leg) in &legs {
+            let from = match accounts {
+                instructions::TransferBatchLegAccounts::Native { from, .. } => from,
+                instructions::TransferBatchLegAccounts::Wrapped { from, .. } => from,
+            };
+            instructions.push(
+                spl_token::instruction::approve(
+                    &spl_token::id(),
+                    from,
+                    &authority_signer,
+                    &from_owner.pubkey(),
+                    &[],
+                    leg.amount,
+                )
+                .unwrap(),
+            );
+        }
+
+        let (accounts, legs): (Vec<_>, Vec<_>) = legs.into_iter().unzip();
+        instructions.push(
+            instructions::transfer_batch(
+                program,
+                bridge,
+                payer.pubkey(),
+                &accounts,
+                TransferBatchData {
+                    nonce: 0,
+                    legs,
+                    target_address: [5u8; 32],
+                    target_chain: 2,
+                    consistency_level: TransferConsistency::default(),
+                },
+            )
+            .expect("Could not create Transfer Batch"),
+        );
+
+        let mut signers = vec![payer, from_owner];
+        signers.extend_from_slice(messages);
+        execute(
+            client,
+            payer,
+            &signers,
+            &instructions,
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn register_chain(
         client: &mut BanksClient,
         program: Pubkey,
@@ -422,6 +690,116 @@ mod helpers {
         .await
     }
 
+    pub async fn set_paused(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::set_paused(
+            program,
+            bridge,
+            payer.pubkey(),
+            message_acc,
+            vaa,
+            SetPausedData {},
+        )
+        .expect("Could not create Set Paused");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_token_limit(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        payload: GovernancePayloadSetTokenLimit,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::set_token_limit(
+            program,
+            bridge,
+            payer.pubkey(),
+            message_acc,
+            vaa,
+            payload,
+            SetTokenLimitData {},
+        )
+        .expect("Could not create Set Token Limit");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_chain_limit(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        payload: GovernancePayloadSetChainLimit,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::set_chain_limit(
+            program,
+            bridge,
+            payer.pubkey(),
+            message_acc,
+            vaa,
+            payload,
+            SetChainLimitData {},
+        )
+        .expect("Could not create Set Chain Limit");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn release_transfer(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        refund: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let instruction =
+            instructions::release_transfer(program, bridge, payer.pubkey(), message, refund)
+                .expect("Could not create Release Transfer");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn complete_native(
         client: &mut BanksClient,
         program: Pubkey,
@@ -439,6 +817,7 @@ mod helpers {
             vaa,
             Pubkey::new(&payload.to[..]),
             None,
+            None,
             Pubkey::new(&payload.token_address[..]),
             CompleteNativeData {},
         )
@@ -458,6 +837,36 @@ mod helpers {
         .await
     }
 
+    pub async fn complete_native_sol(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        payload: PayloadTransfer,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::complete_native_sol(
+            program,
+            bridge,
+            payer.pubkey(),
+            message_acc,
+            vaa,
+            Pubkey::new(&payload.to[..]),
+            CompleteNativeSolData {},
+        )
+        .expect("Could not create Complete Native SOL instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn complete_transfer_wrapped(
         client: &mut BanksClient,
         program: Pubkey,
@@ -478,6 +887,7 @@ mod helpers {
             payload,
             to,
             None,
+            None,
             CompleteWrappedData {},
         )
         .expect("Could not create Complete Wrapped instruction");
@@ -496,6 +906,50 @@ mod helpers {
         .await
     }
 
+    /// Complete a wrapped transfer sent to a wallet, into its associated token account.
+    pub async fn complete_transfer_wrapped_to_wallet(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        payload: PayloadTransfer,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let wallet = Pubkey::new(&payload.to[..]);
+        let mint = token_bridge::accounts::WrappedMint::<'_, { AccountState::Initialized }>::key(
+            &token_bridge::accounts::WrappedDerivationData {
+                token_chain: payload.token_chain,
+                token_address: payload.token_address,
+            },
+            &program,
+        );
+        let to = spl_associated_token_account::get_associated_token_address(&wallet, &mint);
+
+        let instruction = instructions::complete_wrapped(
+            program,
+            bridge,
+            payer.pubkey(),
+            message_acc,
+            vaa,
+            payload,
+            to,
+            Some(wallet),
+            None,
+            CompleteWrappedData {},
+        )
+        .expect("Could not create Complete Wrapped instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn complete_native_with_payload(
         client: &mut BanksClient,
@@ -536,6 +990,75 @@ mod helpers {
         .await
     }
 
+    pub async fn complete_native_with_callback(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        payload: PayloadTransferWithPayload,
+        to: Pubkey,
+        callback_accounts: Vec<AccountMeta>,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let mint = Pubkey::new(&payload.token_address[..]);
+        let instruction = instructions::complete_native_with_callback(
+            program,
+            bridge,
+            payer.pubkey(),
+            message_acc,
+            vaa,
+            payload,
+            to,
+            mint,
+            callback_accounts,
+            CompleteNativeWithCallbackData {},
+        )
+        .expect("Could not create Complete Native With Callback instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn complete_wrapped_with_callback(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        payload: PayloadTransferWithPayload,
+        to: Pubkey,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::complete_wrapped_with_callback(
+            program,
+            bridge,
+            payer.pubkey(),
+            message_acc,
+            vaa,
+            payload,
+            to,
+            vec![],
+            CompleteWrappedWithCallbackData {},
+        )
+        .expect("Could not create Complete Wrapped With Callback instruction");
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn create_wrapped(
         client: &mut BanksClient,
         program: Pubkey,
@@ -697,6 +1220,103 @@ mod helpers {
         .await
     }
 
+    /// Create a Token-2022 mint withholding `basis_points` of each transfer, up to `maximum_fee`,
+    /// along with a token account holding `amount` of it. The Token-2022 client is not a
+    /// dependency, so the instruction setting the fee is packed by hand.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_transfer_fee_token(
+        client: &mut BanksClient,
+        payer: &Keypair,
+        mint_authority: &Keypair,
+        mint: &Keypair,
+        token_acc: &Keypair,
+        token_authority: &Pubkey,
+        basis_points: u16,
+        maximum_fee: u64,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        const TRANSFER_FEE_CONFIG: u16 = 1;
+        // Base layout padded to that of an account, the account type, then the extension.
+        let mint_len = spl_token::state::Account::LEN + 1 + 4 + 108;
+        let account_len = token_2022::account_size(&[(TRANSFER_FEE_CONFIG, vec![])]);
+
+        // TransferFeeExtension, InitializeTransferFeeConfig without authorities
+        let mut data = vec![26, 0, 0, 0];
+        data.extend_from_slice(&basis_points.to_le_bytes());
+        data.extend_from_slice(&maximum_fee.to_le_bytes());
+        let initialize_fee = Instruction {
+            program_id: token_2022::id(),
+            accounts: vec![AccountMeta::new(mint.pubkey(), false)],
+            data,
+        };
+
+        execute(
+            client,
+            payer,
+            &[payer, mint, token_acc, mint_authority],
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &mint.pubkey(),
+                    Rent::default().minimum_balance(mint_len),
+                    mint_len as u64,
+                    &token_2022::id(),
+                ),
+                initialize_fee,
+                token_2022::with_program(
+                    spl_token::instruction::initialize_mint(
+                        &spl_token::id(),
+                        &mint.pubkey(),
+                        &mint_authority.pubkey(),
+                        None,
+                        0,
+                    )
+                    .unwrap(),
+                    &token_2022::id(),
+                ),
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &token_acc.pubkey(),
+                    Rent::default().minimum_balance(account_len),
+                    account_len as u64,
+                    &token_2022::id(),
+                ),
+                token_2022::with_program(
+                    spl_token::instruction::initialize_account(
+                        &spl_token::id(),
+                        &token_acc.pubkey(),
+                        &mint.pubkey(),
+                        token_authority,
+                    )
+                    .unwrap(),
+                    &token_2022::id(),
+                ),
+                token_2022::with_program(
+                    spl_token::instruction::mint_to(
+                        &spl_token::id(),
+                        &mint.pubkey(),
+                        &token_acc.pubkey(),
+                        &mint_authority.pubkey(),
+                        &[],
+                        amount,
+                    )
+                    .unwrap(),
+                    &token_2022::id(),
+                ),
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    /// Balance of a token account of either token program.
+    pub async fn get_token_balance(client: &mut BanksClient, account: Pubkey) -> u64 {
+        let account = client.get_account(account).await.unwrap().unwrap();
+        spl_token::state::Account::unpack(&account.data[..spl_token::state::Account::LEN])
+            .unwrap()
+            .amount
+    }
+
     /// Utility function for generating VAA's from message data.
     pub fn generate_vaa<T: Into<Vec<u8>>>(
         emitter: Address,
@@ -4,17 +4,24 @@ use bridge::{
         PostedVAA,
         PostedVAADerivationData,
     },
+    DeserializePayload,
     SerializePayload,
 };
 use libsecp256k1::SecretKey;
 use primitive_types::U256;
 use rand::Rng;
-use solana_program::pubkey::Pubkey;
+use solana_program::{
+    instruction::AccountMeta,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
 use solana_program_test::{
     tokio,
     BanksClient,
+    BanksClientError,
 };
 use solana_sdk::{
+    commitment_config::CommitmentLevel,
     signature::{
         Keypair,
         Signer,
@@ -32,17 +39,39 @@ use std::{
 };
 use token_bridge::{
     accounts::{
+        AuthoritySigner,
         ConfigAccount,
+        CustodyAccount,
+        CustodyAccountDerivationData,
+        PendingTransferAccount,
+        PendingTransferDerivationData,
         WrappedDerivationData,
+        WrappedMetaDerivationData,
         WrappedMint,
+        WrappedTokenMeta,
+    },
+    api::{
+        TransferBatchKind,
+        TransferBatchLeg,
     },
+    instructions,
     messages::{
+        GovernancePayloadSetChainLimit,
+        GovernancePayloadSetPaused,
+        GovernancePayloadSetTokenLimit,
         PayloadAssetMeta,
         PayloadGovernanceRegisterChain,
         PayloadTransfer,
         PayloadTransferWithPayload,
     },
-    types::Config,
+    token_2022,
+    types::{
+        PendingTransfer,
+        TransferConsistency,
+        VersionedConfig,
+        VersionedWrappedMeta,
+    },
+    TransferNativeData,
 };
 
 mod common;
@@ -202,7 +231,7 @@ async fn set_up() -> Result<Context, TransportError> {
 
     // Verify Token Bridge State
     let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &token_bridge);
-    let config: Config = common::get_account_data(&mut context.client, config_key)
+    let config: VersionedConfig = common::get_account_data(&mut context.client, config_key)
         .await
         .unwrap();
     assert_eq!(config.wormhole_bridge, bridge);
@@ -402,10 +431,75 @@ async fn register_chain(context: &mut Context) {
     .unwrap();
 }
 
+async fn set_paused(context: &mut Context, paused: bool) {
+    let Context {
+        ref payer,
+        ref mut client,
+        ref bridge,
+        ref token_bridge,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    let nonce = rand::thread_rng().gen();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let payload = GovernancePayloadSetPaused { paused };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) = common::generate_vaa(
+        emitter.pubkey().to_bytes(),
+        1,
+        message,
+        nonce,
+        rand::thread_rng().gen(),
+    );
+    let signature_set = common::verify_signatures(client, bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, *bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, bridge);
+
+    common::set_paused(client, *token_bridge, *bridge, message_key, vaa, payer)
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
-async fn transfer_native_in() {
+async fn pause() {
     let mut context = set_up().await.unwrap();
     register_chain(&mut context).await;
+
+    // Lock some tokens first so that there is custody to release once unpaused.
+    let message = &Keypair::new();
+    common::transfer_native(
+        &mut context.client,
+        context.token_bridge,
+        context.bridge,
+        &context.payer,
+        message,
+        &context.token_account,
+        &context.token_authority,
+        context.mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+
+    set_paused(&mut context, true).await;
+    let config_key =
+        ConfigAccount::<'_, { AccountState::Initialized }>::key(None, &context.token_bridge);
+    let config: VersionedConfig = common::get_account_data(&mut context.client, config_key)
+        .await
+        .unwrap();
+    assert!(config.paused);
+
     let Context {
         ref payer,
         ref mut client,
@@ -418,10 +512,8 @@ async fn transfer_native_in() {
         ..
     } = context;
 
-    // Do an initial transfer so that the bridge account has some native tokens. This also creates
-    // the custody account.
     let message = &Keypair::new();
-    common::transfer_native(
+    assert!(common::transfer_native(
         client,
         token_bridge,
         bridge,
@@ -433,9 +525,7 @@ async fn transfer_native_in() {
         100,
     )
     .await
-    .unwrap();
-
-    let nonce = rand::thread_rng().gen();
+    .is_err());
 
     let payload = PayloadTransfer {
         amount: U256::from(100u128),
@@ -447,6 +537,7 @@ async fn transfer_native_in() {
     };
     let message = payload.try_to_vec().unwrap();
 
+    let nonce = rand::thread_rng().gen();
     let (vaa, body, _) = common::generate_vaa([0u8; 32], 2, message, nonce, 1);
     let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
         .await
@@ -460,6 +551,30 @@ async fn transfer_native_in() {
     let message_key =
         PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
 
+    assert!(common::complete_native(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa.clone(),
+        payload.clone(),
+        payer,
+    )
+    .await
+    .is_err());
+
+    set_paused(&mut context, false).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ..
+    } = context;
+
     common::complete_native(
         client,
         token_bridge,
@@ -471,53 +586,68 @@ async fn transfer_native_in() {
     )
     .await
     .unwrap();
+
+    let message = &Keypair::new();
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
 }
 
-#[tokio::test]
-async fn transfer_wrapped() {
-    let mut context = set_up().await.unwrap();
-    register_chain(&mut context).await;
-    let to = create_wrapped_account(&mut context).await.unwrap();
+async fn set_token_limit(context: &mut Context, price: u64, daily_limit: u64) {
     let Context {
         ref payer,
         ref mut client,
-        bridge,
-        token_bridge,
-        ref token_authority,
+        ref bridge,
+        ref token_bridge,
         ref guardian_keys,
+        ref mint,
         ..
     } = context;
 
     let nonce = rand::thread_rng().gen();
-
-    let payload = PayloadTransfer {
-        amount: U256::from(100000000),
-        token_address: [1u8; 32],
-        token_chain: 2,
-        to: to.to_bytes(),
-        to_chain: 1,
-        fee: U256::from(0),
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let payload = GovernancePayloadSetTokenLimit {
+        token_chain: 1,
+        token_address: mint.pubkey().to_bytes(),
+        price,
+        daily_limit,
     };
     let message = payload.try_to_vec().unwrap();
 
-    let (vaa, body, _) =
-        common::generate_vaa([0u8; 32], 2, message, nonce, rand::thread_rng().gen());
-    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+    let (vaa, body, _) = common::generate_vaa(
+        emitter.pubkey().to_bytes(),
+        1,
+        message,
+        nonce,
+        rand::thread_rng().gen(),
+    );
+    let signature_set = common::verify_signatures(client, bridge, payer, body, guardian_keys, 0)
         .await
         .unwrap();
-    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+    common::post_vaa(client, *bridge, payer, signature_set, vaa.clone())
         .await
         .unwrap();
+
     let msg_derivation_data = &PostedVAADerivationData {
         payload_hash: body.to_vec(),
     };
     let message_key =
-        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, bridge);
 
-    common::complete_transfer_wrapped(
+    common::set_token_limit(
         client,
-        token_bridge,
-        bridge,
+        *token_bridge,
+        *bridge,
         message_key,
         vaa,
         payload,
@@ -525,29 +655,95 @@ async fn transfer_wrapped() {
     )
     .await
     .unwrap();
+}
 
-    // Now transfer the wrapped tokens back, which will burn them.
-    let message = &Keypair::new();
-    common::transfer_wrapped(
+async fn set_chain_limit(context: &mut Context, chain: u16, daily_limit: u64) {
+    let Context {
+        ref payer,
+        ref mut client,
+        ref bridge,
+        ref token_bridge,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    let nonce = rand::thread_rng().gen();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let payload = GovernancePayloadSetChainLimit { chain, daily_limit };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) = common::generate_vaa(
+        emitter.pubkey().to_bytes(),
+        1,
+        message,
+        nonce,
+        rand::thread_rng().gen(),
+    );
+    let signature_set = common::verify_signatures(client, bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, *bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, bridge);
+
+    common::set_chain_limit(
         client,
-        token_bridge,
-        bridge,
+        *token_bridge,
+        *bridge,
+        message_key,
+        vaa,
+        payload,
         payer,
-        message,
-        to,
-        token_authority,
-        2,
-        [1u8; 32],
-        10000000,
     )
     .await
     .unwrap();
 }
 
 #[tokio::test]
-async fn transfer_native_with_payload_in() {
+async fn governor_accounts_are_required_once_governed() {
     let mut context = set_up().await.unwrap();
-    register_chain(&mut context).await;
+    {
+        let Context {
+            ref payer,
+            ref mut client,
+            bridge,
+            token_bridge,
+            ref mint,
+            ref token_account,
+            ref token_authority,
+            ..
+        } = context;
+
+        // Without any limit, callers predating the governor keep working.
+        common::transfer_native_without_governor(
+            client,
+            token_bridge,
+            bridge,
+            payer,
+            &Keypair::new(),
+            token_account,
+            token_authority,
+            mint.pubkey(),
+            100,
+        )
+        .await
+        .unwrap();
+    }
+
+    set_token_limit(&mut context, 1, 1000).await;
+    let config_key =
+        ConfigAccount::<'_, { AccountState::Initialized }>::key(None, &context.token_bridge);
+    let config: VersionedConfig = common::get_account_data(&mut context.client, config_key)
+        .await
+        .unwrap();
+    assert!(config.governed);
+
     let Context {
         ref payer,
         ref mut client,
@@ -556,19 +752,29 @@ async fn transfer_native_with_payload_in() {
         ref mint,
         ref token_account,
         ref token_authority,
-        ref guardian_keys,
         ..
     } = context;
 
-    // Do an initial transfer so that the bridge account has some native tokens. This also creates
-    // the custody account.
-    let message = &Keypair::new();
+    // From the first limit on, leaving out the governor would bypass it.
+    assert!(common::transfer_native_without_governor(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        &Keypair::new(),
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .is_err());
     common::transfer_native(
         client,
         token_bridge,
         bridge,
         payer,
-        message,
+        &Keypair::new(),
         token_account,
         token_authority,
         mint.pubkey(),
@@ -576,45 +782,1052 @@ async fn transfer_native_with_payload_in() {
     )
     .await
     .unwrap();
+}
 
-    let nonce = rand::thread_rng().gen();
-    let from_address = Keypair::new().pubkey().to_bytes();
-    let payload: Vec<u8> = vec![1, 2, 3];
-    let payload = PayloadTransferWithPayload {
-        amount: U256::from(100u128),
-        token_address: mint.pubkey().to_bytes(),
-        token_chain: CHAIN_ID_SOLANA,
-        to: token_authority.pubkey().to_bytes(),
-        to_chain: CHAIN_ID_SOLANA,
-        from_address,
-        payload,
-    };
-    let message = payload.try_to_vec().unwrap();
-
-    let (vaa, body, _) = common::generate_vaa([0u8; 32], CHAIN_ID_ETH, message, nonce, 1);
-    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
-        .await
-        .unwrap();
-    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
-        .await
-        .unwrap();
-    let msg_derivation_data = &PostedVAADerivationData {
-        payload_hash: body.to_vec(),
-    };
-    let message_key =
-        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+#[tokio::test]
+async fn governor_refuses_unpriced_mint_to_limited_chain() {
+    let mut context = set_up().await.unwrap();
+    set_chain_limit(&mut context, 2, 1000).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ..
+    } = context;
 
-    common::complete_native_with_payload(
+    // The mint has no price, so its transfers could not be counted against the chain limit.
+    let message = &Keypair::new();
+    assert!(common::transfer_native(
         client,
         token_bridge,
         bridge,
-        message_key,
-        vaa,
-        payload,
-        token_account.pubkey(),
-        token_authority,
         payer,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
     )
     .await
-    .unwrap();
+    .is_err());
+}
+
+#[tokio::test]
+async fn governor_queues_transfer_over_limit() {
+    let mut context = set_up().await.unwrap();
+    set_token_limit(&mut context, 1, 150).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ..
+    } = context;
+
+    // Within the limit, the message is posted right away.
+    let message = &Keypair::new();
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+    assert!(client
+        .get_account(message.pubkey())
+        .await
+        .unwrap()
+        .is_some());
+
+    // Over the limit, the tokens are locked but the message is held back.
+    let message = &Keypair::new();
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+    assert!(client
+        .get_account(message.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+
+    let pending_key = PendingTransferAccount::<'_, { AccountState::Initialized }>::key(
+        &PendingTransferDerivationData {
+            message: message.pubkey(),
+        },
+        &token_bridge,
+    );
+    let pending: PendingTransfer = common::get_account_data(client, pending_key).await.unwrap();
+    assert_eq!(pending.payer, payer.pubkey());
+    let payload = PayloadTransfer::deserialize(&mut pending.payload.as_slice()).unwrap();
+    assert_eq!(payload.amount, U256::from(100u128));
+    assert_eq!(payload.token_address, mint.pubkey().to_bytes());
+
+    // The delay has not passed yet.
+    assert!(common::release_transfer(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message.pubkey(),
+        payer.pubkey()
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn transfer_native_in() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    // Do an initial transfer so that the bridge account has some native tokens. This also creates
+    // the custody account.
+    let message = &Keypair::new();
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+
+    let nonce = rand::thread_rng().gen();
+
+    let payload = PayloadTransfer {
+        amount: U256::from(100u128),
+        token_address: mint.pubkey().to_bytes(),
+        token_chain: 1,
+        to: token_account.pubkey().to_bytes(),
+        to_chain: 1,
+        fee: U256::from(0u128),
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], 2, message, nonce, 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+
+    common::complete_native(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        payer,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn transfer_native_with_transfer_fee_in() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    // A Token-2022 mint withholding 1% of each transfer.
+    let mint_authority = Keypair::new();
+    let mint = Keypair::new();
+    let token_account = Keypair::new();
+    let token_authority = Keypair::new();
+    common::create_transfer_fee_token(
+        client,
+        payer,
+        &mint_authority,
+        &mint,
+        &token_account,
+        &token_authority.pubkey(),
+        100,
+        u64::MAX,
+        1000,
+    )
+    .await
+    .unwrap();
+
+    let message = &Keypair::new();
+    let instruction = instructions::transfer_native(
+        token_bridge,
+        bridge,
+        payer.pubkey(),
+        message.pubkey(),
+        token_account.pubkey(),
+        mint.pubkey(),
+        TransferNativeData {
+            nonce: 0,
+            amount: 100,
+            fee: 0,
+            target_address: [0u8; 32],
+            target_chain: 2,
+            consistency_level: TransferConsistency::default(),
+        },
+    )
+    .unwrap();
+    common::execute(
+        client,
+        payer,
+        &[payer, &token_authority, message],
+        &[
+            token_2022::with_program(
+                spl_token::instruction::approve(
+                    &spl_token::id(),
+                    &token_account.pubkey(),
+                    &AuthoritySigner::key(None, &token_bridge),
+                    &token_authority.pubkey(),
+                    &[],
+                    100,
+                )
+                .unwrap(),
+                &token_2022::id(),
+            ),
+            instruction,
+        ],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+
+    // Only what arrived in custody is bridged.
+    let custody = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData {
+            mint: mint.pubkey(),
+        },
+        &token_bridge,
+    );
+    assert_eq!(common::get_token_balance(client, custody).await, 99);
+
+    let payload = PayloadTransfer {
+        amount: U256::from(50u128),
+        token_address: mint.pubkey().to_bytes(),
+        token_chain: 1,
+        to: token_account.pubkey().to_bytes(),
+        to_chain: 1,
+        fee: U256::from(0u128),
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], 2, message, rand::thread_rng().gen(), 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        &bridge,
+    );
+
+    common::complete_native(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        payer,
+    )
+    .await
+    .unwrap();
+
+    // The fee of paying out is withheld from the recipient.
+    assert_eq!(common::get_token_balance(client, custody).await, 49);
+    assert_eq!(
+        common::get_token_balance(client, token_account.pubkey()).await,
+        900 + 49
+    );
+}
+
+#[tokio::test]
+async fn transfer_native_sol_in() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    // Send SOL out first so that the WSOL custody account exists and holds the lamports.
+    let message = &Keypair::new();
+    common::transfer_native_sol(client, token_bridge, bridge, payer, message, 1_000_000_000)
+        .await
+        .unwrap();
+
+    let nonce = rand::thread_rng().gen();
+    let recipient = Keypair::new().pubkey();
+
+    // WSOL has 9 decimals, so amounts are sent truncated to 8.
+    let payload = PayloadTransfer {
+        amount: U256::from(100_000_000u128),
+        token_address: spl_token::native_mint::id().to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: recipient.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(0u128),
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], CHAIN_ID_ETH, message, nonce, 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+
+    common::complete_native_sol(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        payer,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        common::get_account_balance(client, recipient).await,
+        1_000_000_000
+    );
+}
+
+#[tokio::test]
+async fn transfer_wrapped() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let to = create_wrapped_account(&mut context).await.unwrap();
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref token_authority,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    let nonce = rand::thread_rng().gen();
+
+    let payload = PayloadTransfer {
+        amount: U256::from(100000000),
+        token_address: [1u8; 32],
+        token_chain: 2,
+        to: to.to_bytes(),
+        to_chain: 1,
+        fee: U256::from(0),
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) =
+        common::generate_vaa([0u8; 32], 2, message, nonce, rand::thread_rng().gen());
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+
+    common::complete_transfer_wrapped(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        payer,
+    )
+    .await
+    .unwrap();
+
+    // Now transfer the wrapped tokens back, which will burn them.
+    let message = &Keypair::new();
+    common::transfer_wrapped(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        to,
+        token_authority,
+        2,
+        [1u8; 32],
+        10000000,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn complete_wrapped_to_wallet() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let mint = create_wrapped(&mut context).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    // The VAA names a wallet without a token account, which the redeem creates.
+    let wallet = Keypair::new().pubkey();
+    let nonce = rand::thread_rng().gen();
+    let payload = PayloadTransfer {
+        amount: U256::from(100000000),
+        token_address: [1u8; 32],
+        token_chain: 2,
+        to: wallet.to_bytes(),
+        to_chain: 1,
+        fee: U256::from(0),
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) =
+        common::generate_vaa([0u8; 32], 2, message, nonce, rand::thread_rng().gen());
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+
+    common::complete_transfer_wrapped_to_wallet(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        payer,
+    )
+    .await
+    .unwrap();
+
+    let to = spl_associated_token_account::get_associated_token_address(&wallet, &mint);
+    let account = client.get_account(to).await.unwrap().unwrap();
+    let account = spl_token::state::Account::unpack(&account.data).unwrap();
+    assert_eq!(account.owner, wallet);
+    assert_eq!(account.amount, 100000000);
+}
+
+// Redeem an attestation of the token created by `create_wrapped`, emitted at `sequence`.
+async fn refresh_wrapped(
+    context: &mut Context,
+    sequence: u64,
+    name: &str,
+) -> Result<(), BanksClientError> {
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref guardian_keys,
+        ..
+    } = *context;
+
+    let payload = PayloadAssetMeta {
+        token_address: [1u8; 32],
+        token_chain: 2,
+        decimals: 7,
+        symbol: "".to_string(),
+        name: name.to_string(),
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let nonce = rand::thread_rng().gen();
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], 2, message, nonce, sequence);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+
+    common::create_wrapped(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        payer,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn refresh_wrapped_meta() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let mint = create_wrapped(&mut context).await;
+    let meta_key = WrappedTokenMeta::<'_, { AccountState::Initialized }>::key(
+        &WrappedMetaDerivationData { mint_key: mint },
+        &context.token_bridge,
+    );
+
+    // The token was created from an attestation at sequence 2, an earlier one is stale.
+    assert!(refresh_wrapped(&mut context, 1, "Stale").await.is_err());
+    assert!(refresh_wrapped(&mut context, 2, "Replayed").await.is_err());
+
+    refresh_wrapped(&mut context, 3, "Fresh").await.unwrap();
+    let meta: VersionedWrappedMeta = common::get_account_data(&mut context.client, meta_key)
+        .await
+        .unwrap();
+    assert_eq!(meta.last_updated_sequence, 3);
+    assert!(meta.last_updated > 0);
+}
+
+#[tokio::test]
+async fn transfer_batch() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let to = create_wrapped_account(&mut context).await.unwrap();
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    // Receive wrapped tokens to send back along with the native ones.
+    let nonce = rand::thread_rng().gen();
+    let payload = PayloadTransfer {
+        amount: U256::from(100000000),
+        token_address: [1u8; 32],
+        token_chain: 2,
+        to: to.to_bytes(),
+        to_chain: 1,
+        fee: U256::from(0),
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) =
+        common::generate_vaa([0u8; 32], 2, message, nonce, rand::thread_rng().gen());
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+
+    common::complete_transfer_wrapped(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        payer,
+    )
+    .await
+    .unwrap();
+
+    // Legs may not repeat a mint.
+    let native_message = &Keypair::new();
+    let other_message = &Keypair::new();
+    assert!(common::transfer_batch(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        &[native_message, other_message],
+        token_authority,
+        vec![
+            native_leg(token_account, mint, native_message, 100),
+            native_leg(token_account, mint, other_message, 100),
+        ],
+    )
+    .await
+    .is_err());
+
+    // Each leg posts its own message.
+    let wrapped_message = &Keypair::new();
+    common::transfer_batch(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        &[native_message, wrapped_message],
+        token_authority,
+        vec![
+            native_leg(token_account, mint, native_message, 100),
+            (
+                instructions::TransferBatchLegAccounts::Wrapped {
+                    from: to,
+                    from_owner: token_authority.pubkey(),
+                    token_chain: 2,
+                    token_address: [1u8; 32],
+                    message_key: wrapped_message.pubkey(),
+                },
+                TransferBatchLeg {
+                    kind: TransferBatchKind::Wrapped,
+                    amount: 10000000,
+                    fee: 0,
+                },
+            ),
+        ],
+    )
+    .await
+    .unwrap();
+
+    for message in [native_message, wrapped_message] {
+        assert!(client
+            .get_account(message.pubkey())
+            .await
+            .unwrap()
+            .is_some());
+    }
+}
+
+fn native_leg(
+    from: &Keypair,
+    mint: &Keypair,
+    message: &Keypair,
+    amount: u64,
+) -> (instructions::TransferBatchLegAccounts, TransferBatchLeg) {
+    (
+        instructions::TransferBatchLegAccounts::Native {
+            from: from.pubkey(),
+            mint: mint.pubkey(),
+            message_key: message.pubkey(),
+        },
+        TransferBatchLeg {
+            kind: TransferBatchKind::Native,
+            amount,
+            fee: 0,
+        },
+    )
+}
+
+#[tokio::test]
+async fn transfer_native_with_payload_in() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    // Do an initial transfer so that the bridge account has some native tokens. This also creates
+    // the custody account.
+    let message = &Keypair::new();
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+
+    let nonce = rand::thread_rng().gen();
+    let from_address = Keypair::new().pubkey().to_bytes();
+    let payload: Vec<u8> = vec![1, 2, 3];
+    let payload = PayloadTransferWithPayload {
+        amount: U256::from(100u128),
+        token_address: mint.pubkey().to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: token_authority.pubkey().to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        from_address,
+        payload,
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], CHAIN_ID_ETH, message, nonce, 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+
+    common::complete_native_with_payload(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        token_account.pubkey(),
+        token_authority,
+        payer,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn transfer_native_with_callback_in() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    // Do an initial transfer so that the bridge account has some native tokens. This also creates
+    // the custody account.
+    let message = &Keypair::new();
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+
+    // The tokens go to an account of the receiver's redeemer PDA.
+    let receiver = common::callback_receiver_id();
+    let (redeemer, _) = Pubkey::find_program_address(&[b"redeemer"], &receiver);
+    let to = Keypair::new();
+    common::create_token_account(client, payer, &to, &redeemer, &mint.pubkey())
+        .await
+        .unwrap();
+
+    let nonce = rand::thread_rng().gen();
+    let payload = PayloadTransferWithPayload {
+        amount: U256::from(100u128),
+        token_address: mint.pubkey().to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: receiver.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        from_address: Keypair::new().pubkey().to_bytes(),
+        payload: vec![1, 2, 3],
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], CHAIN_ID_ETH, message, nonce, 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+
+    // An account the receiver does not control is refused.
+    assert!(common::complete_native_with_callback(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa.clone(),
+        payload.clone(),
+        token_account.pubkey(),
+        vec![],
+        payer,
+    )
+    .await
+    .is_err());
+
+    common::complete_native_with_callback(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        to.pubkey(),
+        vec![],
+        payer,
+    )
+    .await
+    .unwrap();
+
+    let account = client.get_account(to.pubkey()).await.unwrap().unwrap();
+    let account = spl_token::state::Account::unpack(&account.data).unwrap();
+    assert_eq!(account.amount, 100);
+}
+
+#[tokio::test]
+async fn forwarded_callback_authority_is_refused() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint,
+        ref token_account,
+        ref token_authority,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    let message = &Keypair::new();
+    common::transfer_native(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        token_account,
+        token_authority,
+        mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+
+    let forwarder = common::callback_forwarder_id();
+    let (redeemer, _) = Pubkey::find_program_address(&[b"redeemer"], &forwarder);
+    let to = Keypair::new();
+    common::create_token_account(client, payer, &to, &redeemer, &mint.pubkey())
+        .await
+        .unwrap();
+
+    let nonce = rand::thread_rng().gen();
+    let payload = PayloadTransferWithPayload {
+        amount: U256::from(100u128),
+        token_address: mint.pubkey().to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: forwarder.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        from_address: Keypair::new().pubkey().to_bytes(),
+        payload: vec![1, 2, 3],
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], CHAIN_ID_ETH, message, nonce, 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+
+    // The receiver refuses the authority of the forwarder, even though it signed.
+    assert!(common::complete_native_with_callback(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa.clone(),
+        payload.clone(),
+        to.pubkey(),
+        vec![AccountMeta::new_readonly(
+            common::callback_receiver_id(),
+            false
+        )],
+        payer,
+    )
+    .await
+    .is_err());
+
+    // The forwarder itself accepts it.
+    common::complete_native_with_callback(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        to.pubkey(),
+        vec![],
+        payer,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn transfer_wrapped_with_callback_in() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let mint = create_wrapped(&mut context).await;
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    // The tokens are minted to an account of the receiver's redeemer PDA.
+    let receiver = common::callback_receiver_id();
+    let (redeemer, _) = Pubkey::find_program_address(&[b"redeemer"], &receiver);
+    let to = Keypair::new();
+    common::create_token_account(client, payer, &to, &redeemer, &mint)
+        .await
+        .unwrap();
+
+    let nonce = rand::thread_rng().gen();
+    let payload = PayloadTransferWithPayload {
+        amount: U256::from(100u128),
+        token_address: [1u8; 32],
+        token_chain: 2,
+        to: receiver.to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        from_address: Keypair::new().pubkey().to_bytes(),
+        payload: vec![1, 2, 3],
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], 2, message, nonce, 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+
+    common::complete_wrapped_with_callback(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        to.pubkey(),
+        payer,
+    )
+    .await
+    .unwrap();
+
+    let account = client.get_account(to.pubkey()).await.unwrap().unwrap();
+    let account = spl_token::state::Account::unpack(&account.data).unwrap();
+    assert_eq!(account.amount, 100);
 }
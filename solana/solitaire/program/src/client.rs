@@ -0,0 +1,168 @@
+//! Client side instruction building.
+//!
+//! Alongside each accounts struct `Name`, `#[derive(FromAccounts)]` generates a `NameAccounts`
+//! struct with one field per account and an `instruction` builder that lists the accounts with
+//! the flags their layers ask for. What a field holds is decided by [`ClientAccount`]: accounts
+//! derived from a constant seed and sysvars have a single valid address, so their fields are `()`
+//! and the builder computes the key from the same `Seeded` implementation the program verifies
+//! it with. These are the only accounts the builder derives.
+//!
+//! Every other account is passed as a `Pubkey`. That includes accounts derived from instruction
+//! data or from other accounts, such as guardian sets, claims or sequences: the accounts struct
+//! does not name their derivation data, so the caller computes them with `Seeded::key` as the
+//! hand-written builders in `instructions.rs` do.
+
+use solana_program::{
+    instruction::AccountMeta,
+    pubkey::Pubkey,
+    sysvar::Sysvar as SolanaSysvar,
+};
+
+use crate::{
+    idl::{
+        IdlAccount,
+        IdlMutability,
+    },
+    processors::seeded::Seeded,
+    AccountState,
+    Data,
+    DataRef,
+    Derive,
+    Info,
+    MaybeMut,
+    Mut,
+    Signer,
+    System,
+    Sysvar,
+    ZeroCopy,
+};
+
+/// How a client provides the address of an account.
+pub trait ClientAccount {
+    /// What the caller has to pass for the account.
+    type Key;
+
+    fn pubkey(key: &Self::Key, program_id: &Pubkey) -> Pubkey;
+}
+
+impl<T: ClientAccount> ClientAccount for Mut<T> {
+    type Key = T::Key;
+
+    fn pubkey(key: &Self::Key, program_id: &Pubkey) -> Pubkey {
+        T::pubkey(key, program_id)
+    }
+}
+
+impl<T: ClientAccount> ClientAccount for MaybeMut<T> {
+    type Key = T::Key;
+
+    fn pubkey(key: &Self::Key, program_id: &Pubkey) -> Pubkey {
+        T::pubkey(key, program_id)
+    }
+}
+
+impl<T: ClientAccount> ClientAccount for Signer<T> {
+    type Key = T::Key;
+
+    fn pubkey(key: &Self::Key, program_id: &Pubkey) -> Pubkey {
+        T::pubkey(key, program_id)
+    }
+}
+
+impl<T: ClientAccount> ClientAccount for System<T> {
+    type Key = T::Key;
+
+    fn pubkey(key: &Self::Key, program_id: &Pubkey) -> Pubkey {
+        T::pubkey(key, program_id)
+    }
+}
+
+/// Optional accounts are replaced by the zero key when absent.
+impl<T: ClientAccount> ClientAccount for Option<T> {
+    type Key = Option<T::Key>;
+
+    fn pubkey(key: &Self::Key, program_id: &Pubkey) -> Pubkey {
+        key.as_ref()
+            .map(|key| T::pubkey(key, program_id))
+            .unwrap_or_default()
+    }
+}
+
+impl<T, const SEED: &'static str> ClientAccount for Derive<T, SEED> {
+    type Key = ();
+
+    fn pubkey(_key: &Self::Key, program_id: &Pubkey) -> Pubkey {
+        Self::key(None, program_id)
+    }
+}
+
+impl<'b, Var: SolanaSysvar> ClientAccount for Sysvar<'b, Var> {
+    type Key = ();
+
+    fn pubkey(_key: &Self::Key, _program_id: &Pubkey) -> Pubkey {
+        Var::id()
+    }
+}
+
+impl<'b> ClientAccount for Info<'b> {
+    type Key = Pubkey;
+
+    fn pubkey(key: &Self::Key, _program_id: &Pubkey) -> Pubkey {
+        *key
+    }
+}
+
+impl<'b, T, const IS_INITIALIZED: AccountState> ClientAccount for Data<'b, T, IS_INITIALIZED>
+where
+    T: crate::Owned + Default,
+{
+    type Key = Pubkey;
+
+    fn pubkey(key: &Self::Key, _program_id: &Pubkey) -> Pubkey {
+        *key
+    }
+}
+
+impl<'b, T: ZeroCopy> ClientAccount for DataRef<'b, T> {
+    type Key = Pubkey;
+
+    fn pubkey(key: &Self::Key, _program_id: &Pubkey) -> Pubkey {
+        *key
+    }
+}
+
+/// What a client passes for an account, built back from its address. This fills in a client
+/// accounts struct from account infos alone, as cross-program invocations have them: derived
+/// accounts and sysvars are computed again, and the zero key stands for an absent optional account.
+pub trait ClientKey {
+    fn from_pubkey(pubkey: Pubkey) -> Self;
+}
+
+impl ClientKey for () {
+    fn from_pubkey(_pubkey: Pubkey) -> Self {
+    }
+}
+
+impl ClientKey for Pubkey {
+    fn from_pubkey(pubkey: Pubkey) -> Self {
+        pubkey
+    }
+}
+
+impl<K: ClientKey> ClientKey for Option<K> {
+    fn from_pubkey(pubkey: Pubkey) -> Self {
+        (pubkey != Pubkey::default()).then(|| K::from_pubkey(pubkey))
+    }
+}
+
+/// Meta of an account as described by its layers. Accounts that may be either mutable or not are
+/// passed read-only, callers wanting otherwise can flip the flag on the returned meta.
+pub fn account_meta(account: &IdlAccount, pubkey: Pubkey) -> AccountMeta {
+    // An absent optional account is the zero key, which must not be writable or signing.
+    let absent = account.optional && pubkey == Pubkey::default();
+    AccountMeta {
+        pubkey,
+        is_signer: account.signer && !absent,
+        is_writable: account.mutability == IdlMutability::Mut && !absent,
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use solana_program::{
     program_error::ProgramError,
     pubkey::Pubkey,
@@ -9,6 +11,10 @@ pub type Result<T> = std::result::Result<T, SolitaireError>;
 /// Quality of life type alias for wrapping up boxed errors.
 pub type ErrBox = Box<dyn std::error::Error>;
 
+/// Program error enums deriving `ErrorCode` must number their errors from at least this offset,
+/// codes below it are reserved for the errors Solitaire raises itself.
+pub const MIN_PROGRAM_ERROR_OFFSET: u32 = 1000;
+
 /// There are several places in Solitaire that might fail, we want descriptive errors.
 #[derive(Debug)]
 pub enum SolitaireError {
@@ -53,6 +59,77 @@ pub enum SolitaireError {
 
     /// User does not have sufficient funds for the tx
     InsufficientFunds,
+
+    /// An error defined by the program through `#[derive(ErrorCode)]`.
+    Program {
+        code: u32,
+        name: &'static str,
+    },
+}
+
+/// Names of the errors raised by Solitaire, indexed by their code. Codes are returned to clients
+/// so entries must only ever be appended.
+const SOLITAIRE_ERRORS: &[(u32, &str)] = &[
+    (100, "InvalidMutability"),
+    (101, "InvalidSigner"),
+    (102, "InvalidSysvar"),
+    (103, "InvalidDerive"),
+    (104, "InvalidOwner"),
+    (105, "NonWriteableAccount"),
+    (106, "InstructionDeserializeFailed"),
+    (107, "IoError"),
+    (108, "AmbiguousOwner"),
+    (109, "AlreadyInitialized"),
+    (110, "UnknownInstruction"),
+    (111, "InsufficientFunds"),
+];
+
+impl SolitaireError {
+    /// Code the error is returned to clients with as `ProgramError::Custom`. Errors forwarded from
+    /// Solana keep their own `ProgramError` and have none.
+    pub fn code(&self) -> Option<u32> {
+        use SolitaireError::*;
+        let code = match self {
+            InvalidMutability(..) => 100,
+            InvalidSigner(_) => 101,
+            InvalidSysvar(_) => 102,
+            InvalidDerive(..) => 103,
+            InvalidOwner(_) => 104,
+            NonWriteableAccount(_) => 105,
+            InstructionDeserializeFailed(_) => 106,
+            IoError(_) => 107,
+            AmbiguousOwner => 108,
+            AlreadyInitialized(_) => 109,
+            UnknownInstruction(_) => 110,
+            InsufficientFunds => 111,
+            Custom(code) => *code as u32,
+            Program { code, .. } => *code,
+            ProgramError(_) => return None,
+        };
+        Some(code)
+    }
+
+    /// Name of a Solitaire error from its code, program errors are decoded by the program's own
+    /// error enum with `from_code`.
+    pub fn name_of(code: u32) -> Option<&'static str> {
+        SOLITAIRE_ERRORS
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, name)| *name)
+    }
+}
+
+impl fmt::Display for SolitaireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolitaireError::Program { name, .. } => f.write_str(name)?,
+            _ => write!(f, "{:?}", self)?,
+        }
+        match self.code() {
+            Some(code) => write!(f, " (error code {})", code),
+            None => Ok(()),
+        }
+    }
 }
 
 impl From<ProgramError> for SolitaireError {
@@ -71,7 +148,7 @@ impl From<SolitaireError> for ProgramError {
     fn from(err: SolitaireError) -> ProgramError {
         match err {
             SolitaireError::ProgramError(e) => e,
-            _ => ProgramError::Custom(0),
+            err => ProgramError::Custom(err.code().unwrap_or_default()),
         }
     }
 }
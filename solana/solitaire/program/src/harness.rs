@@ -0,0 +1,640 @@
+//! In-process test harness.
+//!
+//! Runs instruction handlers natively against an in-memory map of accounts, without a BPF build
+//! or `solana-program-test`. Accounts are laid out exactly as the runtime serializes them for a
+//! program, so `realloc` and `assign` behave as they do on chain, and changes are only kept if
+//! the instruction succeeds.
+//!
+//! Cross-program invocations are intercepted and run in process against the same accounts: the
+//! system program is emulated (transfer, create account, allocate and assign), the SPL token
+//! program runs natively, and any other program can be added with [`Harness::add_program`].
+//! Signer and writable privileges of invoked instructions are checked, including program derived
+//! signatures. Like the runtime, the changes a program makes to its accounts are checked when it
+//! returns and whenever it invokes another program: only the owner of a writable account may change
+//! its data, debit it or assign it to a new owner, and the executable flag can't change. The
+//! `Rent` and `Clock` sysvars are read from the sysvar accounts in the map, both through
+//! `Sysvar::get` and as accounts.
+//!
+//! Compute units, stack and heap limits are not metered. Accounts created through the system
+//! program can be at most `MAX_PERMITTED_DATA_INCREASE` bytes, the same limit as a realloc.
+//!
+//! ```rust,ignore
+//! let mut harness = Harness::new();
+//! harness.fund(&payer, 1_000_000_000);
+//! harness.execute(
+//!     &program_id,
+//!     instruction::Initialize::execute,
+//!     &InitializeAccounts { bridge: (), payer, ... }.to_account_metas(&program_id),
+//!     &InitializeData { ... },
+//! )?;
+//! let bridge: BridgeData = harness.data(&bridge_key).unwrap();
+//! ```
+
+use std::{
+    cell::RefCell,
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    convert::TryInto,
+    sync::Once,
+};
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    bpf_loader,
+    entrypoint::{
+        deserialize,
+        ProcessInstruction,
+        ProgramResult,
+        MAX_PERMITTED_DATA_INCREASE,
+    },
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    program_error::ProgramError,
+    program_stubs::{
+        set_syscall_stubs,
+        SyscallStubs,
+    },
+    pubkey::Pubkey,
+    system_instruction::{
+        SystemError,
+        SystemInstruction,
+    },
+    system_program,
+    sysvar::{
+        self,
+        clock::Clock,
+        rent::Rent,
+        Sysvar as SolanaSysvar,
+    },
+};
+
+use crate::{
+    Result,
+    SolitaireError,
+};
+
+/// State of an account outside of an instruction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Account {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: Pubkey,
+    pub executable: bool,
+}
+
+pub struct Harness {
+    accounts: BTreeMap<Pubkey, Account>,
+    programs: HashMap<Pubkey, ProcessInstruction>,
+    logs: Vec<String>,
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Harness {
+    /// A harness knowing the system and SPL token programs, with default `Rent` and `Clock`.
+    pub fn new() -> Self {
+        let mut harness = Harness {
+            accounts: BTreeMap::new(),
+            programs: HashMap::new(),
+            logs: Vec::new(),
+        };
+        harness.add_program(system_program::id(), process_system_instruction);
+        harness.add_program(spl_token::id(), spl_token::processor::Processor::process);
+        harness.set_sysvar(&Rent::default());
+        harness.set_sysvar(&Clock::default());
+        harness
+    }
+
+    /// Run `processor` for instructions to `program_id`, whether invoked directly through
+    /// [`Harness::process`] or by another program.
+    pub fn add_program(&mut self, program_id: Pubkey, processor: ProcessInstruction) {
+        self.programs.insert(program_id, processor);
+    }
+
+    pub fn set_account(&mut self, key: Pubkey, account: Account) {
+        self.accounts.insert(key, account);
+    }
+
+    pub fn account(&self, key: &Pubkey) -> Option<&Account> {
+        self.accounts.get(key)
+    }
+
+    /// Deserialize the data of an account, `None` if it doesn't exist or doesn't deserialize.
+    pub fn data<T: BorshDeserialize>(&self, key: &Pubkey) -> Option<T> {
+        T::try_from_slice(&self.accounts.get(key)?.data).ok()
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.accounts.get(key).map_or(0, |account| account.lamports)
+    }
+
+    /// Add lamports to an account, creating it as a system account if it doesn't exist.
+    pub fn fund(&mut self, key: &Pubkey, lamports: u64) {
+        self.accounts.entry(*key).or_default().lamports += lamports;
+    }
+
+    /// Store a sysvar in its account, which is also what `Sysvar::get` returns for `Rent` and
+    /// `Clock` during instructions.
+    pub fn set_sysvar<S: SolanaSysvar>(&mut self, sysvar: &S) {
+        let data = bincode::serialize(sysvar).unwrap();
+        self.accounts.insert(
+            S::id(),
+            Account {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: sysvar::id(),
+                executable: false,
+            },
+        );
+    }
+
+    pub fn sysvar<S: SolanaSysvar>(&self) -> Option<S> {
+        bincode::deserialize(&self.accounts.get(&S::id())?.data).ok()
+    }
+
+    /// Messages logged by the last instruction, including those of invoked programs.
+    pub fn logs(&self) -> &[String] {
+        &self.logs
+    }
+
+    /// Run a single handler through the `instruction::$row::execute` function `solitaire!`
+    /// generates for it, `data` being the handler's data without the instruction discriminant.
+    pub fn execute<F, D>(
+        &mut self,
+        program_id: &Pubkey,
+        execute: F,
+        accounts: &[AccountMeta],
+        data: &D,
+    ) -> Result<()>
+    where
+        F: Fn(&Pubkey, &[AccountInfo], &[u8]) -> Result<()>,
+        D: BorshSerialize,
+    {
+        self.run(program_id, accounts, &data.try_to_vec()?, execute)
+    }
+
+    /// Run an instruction to a program added with [`Harness::add_program`].
+    pub fn process(&mut self, instruction: &Instruction) -> ProgramResult {
+        let processor = *self
+            .programs
+            .get(&instruction.program_id)
+            .ok_or(ProgramError::IncorrectProgramId)?;
+        self.run(
+            &instruction.program_id,
+            &instruction.accounts,
+            &instruction.data,
+            processor,
+        )
+    }
+
+    fn run<E, F>(
+        &mut self,
+        program_id: &Pubkey,
+        metas: &[AccountMeta],
+        data: &[u8],
+        processor: F,
+    ) -> std::result::Result<(), E>
+    where
+        E: From<SolitaireError>,
+        F: FnOnce(&Pubkey, &[AccountInfo], &[u8]) -> std::result::Result<(), E>,
+    {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(Stubs));
+        });
+
+        let (mut input, layout) = self.serialize(program_id, metas, data);
+        let frame = Frame {
+            program_id: *program_id,
+            accounts: layout
+                .iter()
+                .map(|(key, _, is_writable)| (*key, (self.load(key), *is_writable)))
+                .collect(),
+        };
+        RUNTIME.with(|runtime| {
+            *runtime.borrow_mut() = Some(Runtime {
+                programs: self.programs.clone(),
+                stack: vec![frame],
+                rent: self.sysvar().unwrap_or_default(),
+                clock: self.sysvar().unwrap_or_default(),
+                logs: Vec::new(),
+            })
+        });
+
+        // Account infos point into `input`, which outlives them.
+        let (_, infos, data) = unsafe { deserialize(input.as_mut_ptr() as *mut u8) };
+        let result = processor(program_id, &infos, data);
+        drop(infos);
+        let runtime = RUNTIME.with(|runtime| runtime.borrow_mut().take());
+        let mut frame = None;
+        if let Some(mut runtime) = runtime {
+            self.logs = runtime.logs;
+            frame = runtime.stack.pop();
+        }
+        result?;
+
+        // Like the runtime, read the results from the serialized accounts rather than the account
+        // infos, whose data slices may have been advanced by writers. Changes to read-only
+        // accounts and lamports appearing or vanishing are refused before keeping any change.
+        let input: Vec<u8> = input.iter().flat_map(|word| word.to_ne_bytes()).collect();
+        let mut changes = Vec::new();
+        let (mut before, mut after) = (0u128, 0u128);
+        for (key, at, is_writable) in layout {
+            let old = self.load(&key);
+            let new = deserialize_account(&input, at);
+            if !is_writable && new != old {
+                return Err(SolitaireError::NonWriteableAccount(key).into());
+            }
+            // Changes made before the program's last invocation were checked at that point.
+            let pre = frame
+                .as_ref()
+                .and_then(|frame| frame.accounts.get(&key))
+                .map_or(&old, |(pre, _)| pre);
+            if let Err(message) = verify_changes(program_id, &key, pre, &new, is_writable) {
+                self.logs.push(message);
+                return Err(SolitaireError::ProgramError(ProgramError::InvalidArgument).into());
+            }
+            before += old.lamports as u128;
+            after += new.lamports as u128;
+            if is_writable {
+                changes.push((key, new));
+            }
+        }
+        if before != after {
+            self.logs
+                .push("Harness: instruction changed the total of lamports".to_string());
+            return Err(SolitaireError::ProgramError(ProgramError::InvalidArgument).into());
+        }
+
+        for (key, account) in changes {
+            // Accounts left without lamports are garbage collected.
+            if account.lamports == 0 {
+                self.accounts.remove(&key);
+            } else {
+                self.accounts.insert(key, account);
+            }
+        }
+        Ok(())
+    }
+
+    /// An account as passed to instructions, missing accounts being empty system accounts unless
+    /// they are a program.
+    fn load(&self, key: &Pubkey) -> Account {
+        match self.accounts.get(key) {
+            Some(account) => account.clone(),
+            None if self.programs.contains_key(key) => Account {
+                lamports: 1,
+                data: Vec::new(),
+                owner: bpf_loader::id(),
+                executable: true,
+            },
+            None => Account::default(),
+        }
+    }
+
+    /// Serialize accounts and data the way the runtime passes them to a program, with room for
+    /// each account to grow by `MAX_PERMITTED_DATA_INCREASE`. Also returns where each distinct
+    /// account was serialized and whether it is writable.
+    fn serialize(
+        &self,
+        program_id: &Pubkey,
+        metas: &[AccountMeta],
+        data: &[u8],
+    ) -> (Vec<u64>, Vec<(Pubkey, usize, bool)>) {
+        let mut input = Vec::new();
+        let mut layout = Vec::new();
+        input.extend_from_slice(&(metas.len() as u64).to_le_bytes());
+        for (index, meta) in metas.iter().enumerate() {
+            if let Some(first) = metas[..index].iter().position(|m| m.pubkey == meta.pubkey) {
+                input.push(first as u8);
+                input.extend_from_slice(&[0; 7]);
+                continue;
+            }
+
+            // Duplicates share the privileges of all their occurrences.
+            let same = metas.iter().filter(|m| m.pubkey == meta.pubkey);
+            let is_signer = same.clone().any(|m| m.is_signer);
+            let is_writable = same.clone().any(|m| m.is_writable);
+
+            let account = self.load(&meta.pubkey);
+
+            input.push(u8::MAX);
+            input.push(is_signer as u8);
+            input.push(is_writable as u8);
+            input.push(account.executable as u8);
+            input.extend_from_slice(&[0; 4]);
+            layout.push((meta.pubkey, input.len(), is_writable));
+            input.extend_from_slice(meta.pubkey.as_ref());
+            input.extend_from_slice(account.owner.as_ref());
+            input.extend_from_slice(&account.lamports.to_le_bytes());
+            input.extend_from_slice(&(account.data.len() as u64).to_le_bytes());
+            input.extend_from_slice(&account.data);
+            input.resize(input.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+            input.resize((input.len() + 7) / 8 * 8, 0);
+            input.extend_from_slice(&0u64.to_le_bytes());
+        }
+        input.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        input.extend_from_slice(data);
+        input.extend_from_slice(program_id.as_ref());
+
+        // Copy into u64s for the alignment the runtime guarantees.
+        let mut aligned = vec![0u64; (input.len() + 7) / 8];
+        for (word, bytes) in aligned.iter_mut().zip(input.chunks(8)) {
+            let mut buf = [0u8; 8];
+            buf[..bytes.len()].copy_from_slice(bytes);
+            *word = u64::from_ne_bytes(buf);
+        }
+        (aligned, layout)
+    }
+}
+
+/// Read back an account serialized at `at` by [`Harness::serialize`].
+fn deserialize_account(input: &[u8], at: usize) -> Account {
+    let u64_at = |at: usize| u64::from_le_bytes(input[at..at + 8].try_into().unwrap());
+    let len = u64_at(at + 72) as usize;
+    Account {
+        lamports: u64_at(at + 64),
+        data: input[at + 80..at + 80 + len].to_vec(),
+        owner: Pubkey::new_from_array(input[at + 32..at + 64].try_into().unwrap()),
+        executable: input[at - 5] != 0,
+    }
+}
+
+/// The state of an account as a program sees it through its account info.
+fn account_of(info: &AccountInfo) -> Account {
+    Account {
+        lamports: info.lamports(),
+        data: info.data.borrow().to_vec(),
+        owner: *info.owner,
+        executable: info.executable,
+    }
+}
+
+/// Check the changes `program_id` made to an account against the rules the runtime enforces,
+/// returning why they are refused.
+fn verify_changes(
+    program_id: &Pubkey,
+    key: &Pubkey,
+    pre: &Account,
+    post: &Account,
+    is_writable: bool,
+) -> std::result::Result<(), String> {
+    let is_owner = *program_id == pre.owner && is_writable && !pre.executable;
+    let refuse = |what: &str| Err(format!("Harness: {} {} account {}", program_id, what, key));
+
+    if pre.owner != post.owner && (!is_owner || post.data.iter().any(|byte| *byte != 0)) {
+        return refuse("changed the owner of");
+    }
+    if post.lamports < pre.lamports && *program_id != pre.owner {
+        return refuse("debited lamports from");
+    }
+    if post.lamports != pre.lamports && (!is_writable || pre.executable) {
+        return refuse("changed the lamports of read-only");
+    }
+    if post.data != pre.data && !is_owner {
+        return refuse("modified the data of");
+    }
+    if post.executable != pre.executable {
+        return refuse("changed the executable flag of");
+    }
+    Ok(())
+}
+
+/// A program being run, with the accounts it was given and whether it may write to them, as they
+/// were when it started or last returned from an invocation.
+struct Frame {
+    program_id: Pubkey,
+    accounts: HashMap<Pubkey, (Account, bool)>,
+}
+
+/// State of the instruction being run on this thread, reached by the syscall stubs.
+struct Runtime {
+    programs: HashMap<Pubkey, ProcessInstruction>,
+
+    /// Programs being run, the last being the one currently executing.
+    stack: Vec<Frame>,
+
+    rent: Rent,
+    clock: Clock,
+    logs: Vec<String>,
+}
+
+thread_local! {
+    static RUNTIME: RefCell<Option<Runtime>> = const { RefCell::new(None) };
+}
+
+fn with_runtime<R>(f: impl FnOnce(&mut Runtime) -> R) -> Option<R> {
+    RUNTIME.with(|runtime| runtime.borrow_mut().as_mut().map(f))
+}
+
+/// Syscall stubs are global, so they serve whichever harness runs on the calling thread.
+struct Stubs;
+
+impl SyscallStubs for Stubs {
+    fn sol_log(&self, message: &str) {
+        if with_runtime(|runtime| runtime.logs.push(message.to_string())).is_none() {
+            println!("{}", message);
+        }
+    }
+
+    fn sol_log_compute_units(&self) {
+        // Compute units are not metered, so there is nothing to log.
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let (caller, processor) = with_runtime(|runtime| {
+            let processor = runtime.programs.get(&instruction.program_id).copied();
+            Some((runtime.stack.last().unwrap().program_id, processor?))
+        })
+        .flatten()
+        .ok_or(ProgramError::IncorrectProgramId)?;
+
+        let signed: Vec<Pubkey> = signers_seeds
+            .iter()
+            .filter_map(|seeds| Pubkey::create_program_address(seeds, &caller).ok())
+            .collect();
+        let accounts = instruction
+            .accounts
+            .iter()
+            .map(|meta| {
+                let mut same = account_infos.iter().filter(|info| info.key == &meta.pubkey);
+                let mut info = same
+                    .clone()
+                    .next()
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?
+                    .clone();
+                if meta.is_signer
+                    && !same.clone().any(|info| info.is_signer)
+                    && !signed.contains(&meta.pubkey)
+                {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+                if meta.is_writable && !same.any(|info| info.is_writable) {
+                    return Err(ProgramError::InvalidArgument);
+                }
+                info.is_signer = meta.is_signer;
+                info.is_writable = meta.is_writable;
+                Ok(info)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        // Check what the caller changed so far before handing its accounts over.
+        let mut callee = Frame {
+            program_id: instruction.program_id,
+            accounts: HashMap::new(),
+        };
+        for info in &accounts {
+            let account = account_of(info);
+            with_runtime(|runtime| {
+                let caller = runtime.stack.last().unwrap();
+                match caller.accounts.get(info.key) {
+                    Some((pre, is_writable)) => {
+                        verify_changes(&caller.program_id, info.key, pre, &account, *is_writable)
+                    }
+                    None => Ok(()),
+                }
+            })
+            .unwrap()
+            .map_err(log_violation)?;
+            let is_writable = matches!(callee.accounts.get(info.key), Some((_, true)));
+            callee
+                .accounts
+                .insert(*info.key, (account, is_writable || info.is_writable));
+        }
+
+        with_runtime(|runtime| runtime.stack.push(callee));
+        let result = processor(&instruction.program_id, &accounts, &instruction.data);
+        let callee = with_runtime(|runtime| runtime.stack.pop().unwrap()).unwrap();
+        result?;
+
+        // Check what the callee changed, which becomes the caller's starting point.
+        for info in &accounts {
+            let account = account_of(info);
+            let (pre, is_writable) = &callee.accounts[info.key];
+            verify_changes(&callee.program_id, info.key, pre, &account, *is_writable)
+                .map_err(log_violation)?;
+            with_runtime(|runtime| {
+                let caller = runtime.stack.last_mut().unwrap();
+                if let Some((pre, _)) = caller.accounts.get_mut(info.key) {
+                    *pre = account;
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = with_runtime(|runtime| runtime.clock.clone()).unwrap_or_default();
+        unsafe { *(var_addr as *mut Clock) = clock };
+        solana_program::entrypoint::SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let rent = with_runtime(|runtime| runtime.rent).unwrap_or_default();
+        unsafe { *(var_addr as *mut Rent) = rent };
+        solana_program::entrypoint::SUCCESS
+    }
+}
+
+/// Log why the runtime would have aborted the transaction.
+fn log_violation(message: String) -> ProgramError {
+    with_runtime(|runtime| runtime.logs.push(message));
+    ProgramError::InvalidArgument
+}
+
+/// The system program instructions Solitaire and the SPL programs rely on.
+fn process_system_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let account = |index: usize| {
+        accounts
+            .get(index)
+            .ok_or(ProgramError::NotEnoughAccountKeys)
+    };
+    let instruction: SystemInstruction =
+        bincode::deserialize(data).map_err(|_| ProgramError::InvalidInstructionData)?;
+    match instruction {
+        SystemInstruction::CreateAccount {
+            lamports,
+            space,
+            owner,
+        } => {
+            let to = account(1)?;
+            if to.lamports() != 0 {
+                return Err(ProgramError::Custom(
+                    SystemError::AccountAlreadyInUse as u32,
+                ));
+            }
+            allocate(to, space)?;
+            assign(to, &owner)?;
+            transfer(account(0)?, to, lamports)
+        }
+        SystemInstruction::Assign { owner } => assign(account(0)?, &owner),
+        SystemInstruction::Transfer { lamports } => transfer(account(0)?, account(1)?, lamports),
+        SystemInstruction::Allocate { space } => allocate(account(0)?, space),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn transfer(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    if !from.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !from.data_is_empty() || *from.owner != system_program::id() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let remaining = from
+        .lamports()
+        .checked_sub(lamports)
+        .ok_or(ProgramError::Custom(
+            SystemError::ResultWithNegativeLamports as u32,
+        ))?;
+    **from.lamports.borrow_mut() = remaining;
+    **to.lamports.borrow_mut() = to
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::InvalidArgument)?;
+    Ok(())
+}
+
+fn allocate(account: &AccountInfo, space: u64) -> ProgramResult {
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !account.data_is_empty() || *account.owner != system_program::id() {
+        return Err(ProgramError::Custom(
+            SystemError::AccountAlreadyInUse as u32,
+        ));
+    }
+    account.realloc(space as usize, true)
+}
+
+fn assign(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    if account.owner == owner {
+        return Ok(());
+    }
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    account.assign(owner);
+    Ok(())
+}
@@ -0,0 +1,197 @@
+//! Interface descriptions.
+//!
+//! The `solitaire!` macro generates an `instruction::idl()` function describing every instruction
+//! of a program: its discriminant, the Borsh layout of its data and the accounts it expects in
+//! order. Account flags are collected from the layers the account is declared with, each layer
+//! adds its own through [`Peel::describe`](crate::Peel::describe). The result serializes to JSON
+//! so that clients in other languages don't have to mirror `instructions.rs` by hand.
+//!
+//! Only the account descriptions are always built, as client instruction builders use them. The
+//! `idl` functions and their serialization need the `idl` feature, which programs built for the
+//! chain leave off.
+//!
+//! Accounts a handler only passes on to other programs, such as the system or token program, are
+//! not part of its accounts struct and so are not listed.
+
+use std::collections::BTreeMap;
+
+use borsh::schema::{
+    BorshSchema,
+    Definition,
+    Fields,
+};
+#[cfg(feature = "idl")]
+use serde::Serialize;
+
+#[cfg(feature = "idl")]
+use crate::{
+    ExecutionContext,
+    Result,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "idl", derive(Serialize))]
+pub struct Idl {
+    pub name: String,
+    pub version: String,
+    pub instructions: Vec<IdlInstruction>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "idl", derive(Serialize))]
+pub struct IdlInstruction {
+    pub name: String,
+
+    /// First byte of the instruction data, selecting the instruction.
+    pub discriminant: u8,
+
+    /// Accounts in the order they have to be passed.
+    pub accounts: Vec<IdlAccount>,
+
+    /// Layout of the Borsh encoded data following the discriminant.
+    pub data: IdlData,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "idl", derive(Serialize))]
+#[cfg_attr(feature = "idl", serde(rename_all = "snake_case"))]
+pub enum IdlMutability {
+    Readonly,
+    Mut,
+    /// Either is accepted, the instruction adapts to what the caller passed.
+    MaybeMut,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "idl", derive(Serialize))]
+pub struct IdlAccount {
+    pub name: String,
+    pub mutability: IdlMutability,
+    pub signer: bool,
+
+    /// The zero key can be passed instead of the account.
+    pub optional: bool,
+
+    /// Seed of an account derived from the program id.
+    #[cfg_attr(feature = "idl", serde(skip_serializing_if = "Option::is_none"))]
+    pub seed: Option<String>,
+
+    /// Fixed address of the account, as for sysvars.
+    #[cfg_attr(feature = "idl", serde(skip_serializing_if = "Option::is_none"))]
+    pub address: Option<String>,
+
+    /// Account state expected by the instruction, for accounts holding data.
+    #[cfg_attr(feature = "idl", serde(skip_serializing_if = "Option::is_none"))]
+    pub state: Option<String>,
+
+    #[cfg_attr(feature = "idl", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub docs: Vec<String>,
+}
+
+impl IdlAccount {
+    pub fn new(name: &str, docs: Vec<String>) -> Self {
+        IdlAccount {
+            name: name.to_string(),
+            mutability: IdlMutability::Readonly,
+            signer: false,
+            optional: false,
+            seed: None,
+            address: None,
+            state: None,
+            docs,
+        }
+    }
+}
+
+/// Borsh layout of a type, `definitions` contains every non-primitive type it refers to.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "idl", derive(Serialize))]
+pub struct IdlData {
+    #[cfg_attr(feature = "idl", serde(rename = "type"))]
+    pub ty: String,
+    pub definitions: BTreeMap<String, IdlDefinition>,
+}
+
+impl IdlData {
+    pub fn of<T: BorshSchema>() -> Self {
+        let container = T::schema_container();
+        IdlData {
+            ty: container.declaration,
+            definitions: container
+                .definitions
+                .into_iter()
+                .map(|(declaration, definition)| (declaration, definition.into()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "idl", derive(Serialize))]
+#[cfg_attr(feature = "idl", serde(rename_all = "snake_case"))]
+pub enum IdlDefinition {
+    Array { length: u32, elements: String },
+    Sequence { elements: String },
+    Tuple { elements: Vec<String> },
+    Enum { variants: Vec<IdlField> },
+    Struct { fields: Vec<IdlField> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "idl", derive(Serialize))]
+pub struct IdlField {
+    #[cfg_attr(feature = "idl", serde(skip_serializing_if = "Option::is_none"))]
+    pub name: Option<String>,
+    #[cfg_attr(feature = "idl", serde(rename = "type"))]
+    pub ty: String,
+}
+
+impl From<Definition> for IdlDefinition {
+    fn from(definition: Definition) -> Self {
+        let named = |(name, ty)| IdlField {
+            name: Some(name),
+            ty,
+        };
+        match definition {
+            Definition::Array { length, elements } => IdlDefinition::Array { length, elements },
+            Definition::Sequence { elements } => IdlDefinition::Sequence { elements },
+            Definition::Tuple { elements } => IdlDefinition::Tuple { elements },
+            Definition::Enum { variants } => IdlDefinition::Enum {
+                variants: variants.into_iter().map(named).collect(),
+            },
+            Definition::Struct { fields } => IdlDefinition::Struct {
+                fields: match fields {
+                    Fields::NamedFields(fields) => fields.into_iter().map(named).collect(),
+                    Fields::UnnamedFields(fields) => fields
+                        .into_iter()
+                        .map(|ty| IdlField { name: None, ty })
+                        .collect(),
+                    Fields::Empty => vec![],
+                },
+            },
+        }
+    }
+}
+
+/// Implemented by `#[derive(FromAccounts)]`, lists the accounts of an instruction in order.
+pub trait IdlAccounts {
+    fn accounts() -> Vec<IdlAccount>;
+}
+
+/// Describe an instruction from its handler, whose signature determines the accounts and data.
+/// Only the types matter here, so handlers tying the context to their accounts are described at
+/// the `'static` account lifetime.
+#[cfg(feature = "idl")]
+pub fn instruction<A, D, F>(name: &str, discriminant: u8, _handler: F) -> IdlInstruction
+where
+    A: IdlAccounts,
+    D: BorshSchema,
+    F: Fn(&ExecutionContext<'_, 'static>, &mut A, D) -> Result<()>,
+{
+    IdlInstruction {
+        name: name.to_string(),
+        discriminant,
+        accounts: A::accounts(),
+        data: IdlData::of::<D>(),
+    }
+}
@@ -3,11 +3,6 @@
 
 pub use rocksalt::*;
 
-// Lacking:
-//
-// - Error is a lacking as its just a basic enum, maybe use errorcode.
-// - Client generation incomplete.
-
 // We need a few Solana things in scope in order to properly abstract Solana.
 use solana_program::{
     account_info::AccountInfo,
@@ -20,11 +15,16 @@ use std::slice::Iter;
 
 pub use borsh::{
     BorshDeserialize,
+    BorshSchema,
     BorshSerialize,
 };
 
 // Expose all submodules for consumption.
+pub mod client;
 pub mod error;
+#[cfg(feature = "harness")]
+pub mod harness;
+pub mod idl;
 pub mod macros;
 pub mod processors;
 pub mod types;
@@ -17,6 +17,28 @@ macro_rules! trace_impl {
     ( $($arg:tt)* ) => {};
 }
 
+/// Expands to the items passed only if Solitaire is built with the `idl` feature, so that
+/// interface descriptions generated for a program are left out of its on-chain build.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! idl_items {
+    ( $($item:item)* ) => { $crate::idl_items_impl! { $($item)* } };
+}
+
+#[cfg(feature = "idl")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! idl_items_impl {
+    ( $($item:item)* ) => { $($item)* };
+}
+
+#[cfg(not(feature = "idl"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! idl_items_impl {
+    ( $($item:item)* ) => {};
+}
+
 /// This is our main codegen macro. It takes as input a list of enum-like variants mapping field
 /// types to function calls. The generated code produces:
 ///
@@ -24,6 +46,7 @@ macro_rules! trace_impl {
 /// - A set of functions which take as arguments the enum fields.
 /// - A Dispatcher that deserializes bytes into the enum and dispatches the function call.
 /// - A set of client calls scoped to the module `api` that can generate instructions.
+/// - An `idl` function describing all instructions with the `idl` feature, see [`idl`](crate::idl).
 #[macro_export]
 macro_rules! solitaire {
     { $($row:ident => $fn:ident),+ $(,)* } => {
@@ -60,13 +83,19 @@ macro_rules! solitaire {
                     use super::*;
 
                     #[inline(never)]
-                    pub fn execute<'a, 'b: 'a, 'c>(p: &Pubkey, a: &'c [AccountInfo<'b>], d: &[u8]) -> Result<()> {
+                    pub fn execute(p: &Pubkey, a: &[AccountInfo], d: &[u8]) -> Result<()> {
                         let ix_data = BorshDeserialize::try_from_slice(d).map_err(|e| SolitaireError::InstructionDeserializeFailed(e))?;
                         let mut accounts = FromAccounts::from(p, &mut a.iter(), &())?;
                         $fn(&ExecutionContext{program_id: p, accounts: a}, &mut accounts, ix_data)?;
                         Persist::persist(accounts.as_ref(), p)?;
                         Ok(())
                     }
+
+                    solitaire::idl_items! {
+                        pub fn idl() -> solitaire::idl::IdlInstruction {
+                            solitaire::idl::instruction(stringify!($row), Instruction::$row as u8, $fn)
+                        }
+                    }
                 }
             )*
 
@@ -93,10 +122,22 @@ macro_rules! solitaire {
                 }
             }
 
+            solitaire::idl_items! {
+                /// Generated:
+                /// Machine readable description of the instructions above, in discriminant order.
+                pub fn idl() -> solitaire::idl::Idl {
+                    solitaire::idl::Idl {
+                        name: env!("CARGO_PKG_NAME").to_string(),
+                        version: env!("CARGO_PKG_VERSION").to_string(),
+                        instructions: vec![$($row::idl(),)*],
+                    }
+                }
+            }
+
             pub fn solitaire(p: &Pubkey, a: &[AccountInfo], d: &[u8]) -> ProgramResult {
                 trace!("{} {} built with {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), solitaire::PKG_NAME_VERSION);
                 if let Err(err) = dispatch(p, a, d) {
-                    solana_program::msg!("Error: {:?}", err);
+                    solana_program::msg!("Error: {}", err);
                     return Err(err.into());
                 }
                 Ok(())
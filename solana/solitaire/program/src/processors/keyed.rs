@@ -4,12 +4,14 @@ use crate::{
     processors::seeded::Owned,
     AccountState,
     Data,
+    DataRef,
     Derive,
     Info,
     Mut,
     Signer,
     System,
     Sysvar,
+    ZeroCopy,
 };
 
 pub trait Keyed<'a, 'b: 'a> {
@@ -24,6 +26,12 @@ impl<'a, 'b: 'a, T: Owned + Default, const IS_INITIALIZED: AccountState> Keyed<'
     }
 }
 
+impl<'a, 'b: 'a, T: ZeroCopy> Keyed<'a, 'b> for DataRef<'b, T> {
+    fn info(&'a self) -> &'a Info<'b> {
+        &self.0
+    }
+}
+
 impl<'a, 'b: 'a, T> Keyed<'a, 'b> for Signer<T>
 where
     T: Keyed<'a, 'b>,
@@ -9,8 +9,13 @@ use solana_program::{
     pubkey::Pubkey,
     sysvar::Sysvar as SolanaSysvar,
 };
+use std::marker::PhantomData;
 
 use crate::{
+    idl::{
+        IdlAccount,
+        IdlMutability,
+    },
     processors::seeded::{
         AccountOwner,
         Owned,
@@ -31,6 +36,10 @@ pub trait Peel<'a, 'b: 'a> {
         Self: Sized;
 
     fn persist(&self, program_id: &Pubkey) -> Result<()>;
+
+    /// Record what this layer requires of the account in the program IDL.
+    fn describe(_account: &mut IdlAccount) {
+    }
 }
 
 /// Peel a nullable value (0-account means None)
@@ -59,6 +68,11 @@ impl<'a, 'b: 'a, T: Peel<'a, 'b>> Peel<'a, 'b> for Option<T> {
             Ok(())
         }
     }
+
+    fn describe(account: &mut IdlAccount) {
+        T::describe(account);
+        account.optional = true;
+    }
 }
 
 /// Peel a Derived Key
@@ -75,6 +89,11 @@ impl<'a, 'b: 'a, T: Peel<'a, 'b>, const SEED: &'static str> Peel<'a, 'b> for Der
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn describe(account: &mut IdlAccount) {
+        T::describe(account);
+        account.seed = Some(SEED.to_string());
+    }
 }
 
 /// Peel a Mutable key.
@@ -93,6 +112,11 @@ impl<'a, 'b: 'a, T: Peel<'a, 'b>> Peel<'a, 'b> for Mut<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn describe(account: &mut IdlAccount) {
+        T::describe(account);
+        account.mutability = IdlMutability::Mut;
+    }
 }
 
 impl<'a, 'b: 'a, T: Peel<'a, 'b>> Peel<'a, 'b> for MaybeMut<T> {
@@ -104,6 +128,11 @@ impl<'a, 'b: 'a, T: Peel<'a, 'b>> Peel<'a, 'b> for MaybeMut<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn describe(account: &mut IdlAccount) {
+        T::describe(account);
+        account.mutability = IdlMutability::MaybeMut;
+    }
 }
 
 /// Peel a Signer.
@@ -118,6 +147,11 @@ impl<'a, 'b: 'a, T: Peel<'a, 'b>> Peel<'a, 'b> for Signer<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn describe(account: &mut IdlAccount) {
+        T::describe(account);
+        account.signer = true;
+    }
 }
 
 /// Expicitly depend upon the System account.
@@ -132,6 +166,10 @@ impl<'a, 'b: 'a, T: Peel<'a, 'b>> Peel<'a, 'b> for System<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn describe(account: &mut IdlAccount) {
+        T::describe(account);
+    }
 }
 
 /// Peel a Sysvar
@@ -149,6 +187,10 @@ where
     fn persist(&self, _program_id: &Pubkey) -> Result<()> {
         Ok(())
     }
+
+    fn describe(account: &mut IdlAccount) {
+        account.address = Some(Var::id().to_string());
+    }
 }
 
 /// This is our structural recursion base case, the trait system will stop generating new nested
@@ -221,7 +263,7 @@ impl<
             };
         }
 
-        Ok(Data(Box::new(ctx.info.clone()), data))
+        Ok(Data(Box::new(ctx.info.clone()), data, Persistence::Write))
     }
 
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
@@ -235,8 +277,62 @@ impl<
             return Ok(());
         }
 
-        self.1.serialize(&mut *self.0.data.borrow_mut())?;
+        match &self.2 {
+            Persistence::Write => self.1.serialize(&mut *self.0.data.borrow_mut())?,
+            Persistence::Realloc(realloc) => {
+                let data = self.1.try_to_vec()?;
+                realloc.resize(&self.0, data.len())?;
+                self.0.data.borrow_mut().copy_from_slice(&data);
+            }
+            Persistence::Close(recipient) => close_account(&self.0, recipient)?,
+        }
 
         Ok(())
     }
+
+    fn describe(account: &mut IdlAccount) {
+        let state = match IS_INITIALIZED {
+            AccountState::Uninitialized => "uninitialized",
+            AccountState::Initialized => "initialized",
+            AccountState::MaybeInitialized => "maybe_initialized",
+        };
+        account.state = Some(state.to_string());
+    }
+}
+
+/// Zero-copy counterpart of the [`Data`] base case, only the layout of the account is checked.
+impl<'a, 'b: 'a, T: ZeroCopy> Peel<'a, 'b> for DataRef<'b, T> {
+    fn peel<I>(ctx: &mut Context<'a, 'b, I>) -> Result<Self> {
+        if ctx.immutable && ctx.info.is_writable {
+            return Err(SolitaireError::InvalidMutability(
+                *ctx.info.key,
+                ctx.info.is_writable,
+            ));
+        }
+
+        match T::default().owner() {
+            AccountOwner::This => {
+                if ctx.info.owner != ctx.this {
+                    return Err(SolitaireError::InvalidOwner(*ctx.info.owner));
+                }
+            }
+            AccountOwner::Other(v) => {
+                if *ctx.info.owner != v {
+                    return Err(SolitaireError::InvalidOwner(*ctx.info.owner));
+                }
+            }
+            AccountOwner::Any => {}
+        };
+
+        T::validate(&ctx.info.data.borrow())?;
+        Ok(DataRef(Box::new(ctx.info.clone()), PhantomData))
+    }
+
+    fn persist(&self, _program_id: &Pubkey) -> Result<()> {
+        Ok(())
+    }
+
+    fn describe(account: &mut IdlAccount) {
+        account.state = Some("initialized".to_string());
+    }
 }
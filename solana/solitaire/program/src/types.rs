@@ -1,7 +1,9 @@
 mod accounts;
 mod context;
 mod layers;
+mod versioned;
 
 pub use accounts::*;
 pub use context::*;
 pub use layers::*;
+pub use versioned::*;
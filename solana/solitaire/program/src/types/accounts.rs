@@ -11,13 +11,20 @@ use solana_program::{
         invoke,
         invoke_signed,
     },
+    program_error::ProgramError,
     pubkey::Pubkey,
+    rent::Rent,
     system_instruction,
+    system_program,
     sysvar::Sysvar as SolanaSysvar,
 };
-use std::ops::{
-    Deref,
-    DerefMut,
+use std::{
+    cell::Ref,
+    marker::PhantomData,
+    ops::{
+        Deref,
+        DerefMut,
+    },
 };
 
 use crate::{
@@ -27,6 +34,7 @@ use crate::{
     ExecutionContext,
     Keyed,
     Result,
+    SolitaireError,
 };
 
 /// A short alias for AccountInfo.
@@ -86,8 +94,66 @@ use IsSigned::*;
 pub struct Data<'r, T: Owned + Default, const IS_INITIALIZED: AccountState> (
     pub Box<Info<'r>>,
     pub T,
+    pub(crate) Persistence<'r>,
 );
 
+/// What persisting a [`Data`] account does besides serializing its data.
+pub enum Persistence<'r> {
+    /// Write the data into the account as it is sized.
+    Write,
+
+    /// Resize the account to its data first, see [`Data::realloc`].
+    Realloc(Realloc<'r>),
+
+    /// Close the account in favour of the recipient, see [`Data::close`].
+    Close(AccountInfo<'r>),
+}
+
+/// Accounts funding the resize of a [`Data`] account, see [`Data::realloc`].
+pub struct Realloc<'r> {
+    payer: AccountInfo<'r>,
+    system_program: AccountInfo<'r>,
+}
+
+/// Move all lamports of `account` to `recipient`, zero and drop its data and return the account to
+/// the system program.
+pub(crate) fn close_account(account: &AccountInfo, recipient: &AccountInfo) -> Result<()> {
+    let lamports = recipient
+        .lamports()
+        .checked_add(account.lamports())
+        .ok_or(ProgramError::InvalidArgument)?;
+    **recipient.lamports.borrow_mut() = lamports;
+    **account.lamports.borrow_mut() = 0;
+    account.data.borrow_mut().fill(0);
+    account.realloc(0, false)?;
+    account.assign(&system_program::id());
+    Ok(())
+}
+
+impl<'r> Realloc<'r> {
+    /// Resize `account` to `size` bytes, topping up its rent exemption first.
+    pub(crate) fn resize(&self, account: &AccountInfo<'r>, size: usize) -> Result<()> {
+        let rent = Rent::get()?.minimum_balance(size);
+        if account.lamports() < rent {
+            let transfer_ix = system_instruction::transfer(
+                self.payer.key,
+                account.key,
+                rent - account.lamports(),
+            );
+            invoke(
+                &transfer_ix,
+                &[
+                    self.payer.clone(),
+                    account.clone(),
+                    self.system_program.clone(),
+                ],
+            )?;
+        }
+        account.realloc(size, false)?;
+        Ok(())
+    }
+}
+
 impl<'r, T: Owned + Default, const IS_INITIALIZED: AccountState> Deref
     for Data<'r, T, IS_INITIALIZED>
 {
@@ -105,6 +171,60 @@ impl<'r, T: Owned + Default, const IS_INITIALIZED: AccountState> DerefMut
     }
 }
 
+impl<'r, T: Owned + Default, const IS_INITIALIZED: AccountState> Data<'r, T, IS_INITIALIZED> {
+    /// Fit the account to its data when it is persisted, for data whose serialized size changes
+    /// such as a growing vector. The account is resized to exactly the serialized size, as Borsh
+    /// refuses trailing bytes, so it never shrinks below what its data needs. Rent for growing is
+    /// paid by `payer`, which has to sign, through the system program that must be passed to the
+    /// instruction as well.
+    ///
+    /// Only a writable account the program owns can be resized, so an account that is still to be
+    /// created has to be created first.
+    pub fn realloc(&mut self, ctx: &ExecutionContext<'_, 'r>, payer: &Pubkey) -> Result<()> {
+        self.check_persistable(ctx)?;
+        let find = |key: &Pubkey| {
+            ctx.accounts
+                .iter()
+                .find(|account| account.key == key)
+                .cloned()
+                .ok_or(ProgramError::NotEnoughAccountKeys)
+        };
+        self.2 = Persistence::Realloc(Realloc {
+            payer: find(payer)?,
+            system_program: find(&system_program::id())?,
+        });
+        Ok(())
+    }
+
+    /// Close the account when it is persisted, moving all its lamports to `recipient`. The data is
+    /// dropped and the account handed back to the system program, so funding it again later in
+    /// the same transaction only revives an empty system account rather than the closed data.
+    /// Like a resize, this requires a writable account the program owns.
+    pub fn close(&mut self, ctx: &ExecutionContext<'_, 'r>, recipient: &Info<'r>) -> Result<()> {
+        self.check_persistable(ctx)?;
+        if recipient.key == self.0.key {
+            return Err(ProgramError::InvalidArgument.into());
+        }
+        if !recipient.is_writable {
+            return Err(SolitaireError::NonWriteableAccount(*recipient.key));
+        }
+        self.2 = Persistence::Close(recipient.clone());
+        Ok(())
+    }
+
+    /// Persisting leaves accounts the program can't write alone, refuse to schedule a resize or
+    /// close on them rather than have it silently skipped.
+    fn check_persistable(&self, ctx: &ExecutionContext<'_, 'r>) -> Result<()> {
+        if !self.0.is_writable {
+            return Err(SolitaireError::NonWriteableAccount(*self.0.key));
+        }
+        if self.0.owner != ctx.program_id {
+            return Err(SolitaireError::InvalidOwner(*self.0.owner));
+        }
+        Ok(())
+    }
+}
+
 impl<'r, T: Owned + Default> Data<'r, T, { AccountState::MaybeInitialized }> {
     /// Is the account already initialized / created
     pub fn is_initialized(&self) -> bool {
@@ -112,6 +232,23 @@ impl<'r, T: Owned + Default> Data<'r, T, { AccountState::MaybeInitialized }> {
     }
 }
 
+/// Account data that can be read in place instead of being deserialized. The layout is checked
+/// once when the account is peeled, so that accessors can read fields at fixed offsets afterwards.
+pub trait ZeroCopy: Owned + Default {
+    fn validate(data: &[u8]) -> Result<()>;
+}
+
+/// An initialized account read without copying its data, for large accounts that are only read.
+/// Unlike [`Data`], nothing is deserialized on peel nor written back on persist.
+pub struct DataRef<'r, T: ZeroCopy>(pub Box<Info<'r>>, pub(crate) PhantomData<T>);
+
+impl<'r, T: ZeroCopy> DataRef<'r, T> {
+    /// Borrow the raw account data, which passed [`ZeroCopy::validate`].
+    pub fn data(&self) -> Ref<'_, [u8]> {
+        Ref::map(self.0.data.borrow(), |data| &**data)
+    }
+}
+
 pub struct Sysvar<'b, Var: SolanaSysvar>(pub AccountInfo<'b>, pub Var);
 
 impl<'b, Var: SolanaSysvar> Deref for Sysvar<'b, Var> {
@@ -143,8 +280,11 @@ impl<const SEED: &'static str> Derive<AccountInfo<'_>, SEED> {
     }
 }
 
-impl<const SEED: &'static str, T: BorshSerialize + Owned + Default>
-    Derive<Data<'_, T, { AccountState::Uninitialized }>, SEED>
+impl<
+        const SEED: &'static str,
+        T: BorshSerialize + Owned + Default,
+        const IS_INITIALIZED: AccountState,
+    > Derive<Data<'_, T, IS_INITIALIZED>, SEED>
 {
     pub fn create(
         &self,
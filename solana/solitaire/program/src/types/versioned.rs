@@ -0,0 +1,217 @@
+//! Versioned account layouts.
+//!
+//! Borsh data has no room for change: adding a field to an account makes every existing account
+//! fail to deserialize. Accounts are also read by other programs and off-chain clients, so their
+//! layout can't be put behind a header either. Instead, a new layout only ever appends fields to
+//! the previous one, and older layouts are described through [`Migrate`]. Data wrapped in
+//! [`Versioned`] is read at the newest layout its length fits, upgraded to the current one.
+//!
+//! Layouts are told apart by length alone, so versioned data has to make up the whole account
+//! rather than be followed by other fields, and every appended field has to serialize to at least
+//! one byte. A layout is only accepted if it consumes the data exactly, which keeps layouts ending
+//! in a vector, option or enum from being read at an older layout that happens to parse a prefix.
+//!
+//! An account keeps its layout until an instruction needs one of the appended fields: the data is
+//! written back at the length it was read at, so readers of the older layout keep working. Before
+//! setting an appended field, instructions call [`Data::migrate`] to grow the account at the
+//! expense of a payer, persisting fails otherwise rather than dropping the field.
+//!
+//! Adding a field to a versioned type goes as follows:
+//!
+//! ```rust,ignore
+//! // The previous layout, renamed and kept as is.
+//! #[derive(BorshSerialize, BorshDeserialize)]
+//! pub struct ConfigV0 {
+//!     pub fee: u64,
+//! }
+//!
+//! impl Migrate for ConfigV0 {
+//!     const VERSION: u8 = 0;
+//!     type Previous = Self;
+//!     fn migrate(previous: Self) -> Self {
+//!         previous
+//!     }
+//! }
+//!
+//! #[derive(BorshSerialize, BorshDeserialize, Default)]
+//! pub struct Config {
+//!     pub fee: u64,
+//!     pub paused: bool,
+//! }
+//!
+//! impl Migrate for Config {
+//!     const VERSION: u8 = 1;
+//!     type Previous = ConfigV0;
+//!     fn migrate(previous: ConfigV0) -> Self {
+//!         Config {
+//!             fee: previous.fee,
+//!             paused: false,
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::{
+    io::{
+        Error,
+        ErrorKind::InvalidData,
+        Write,
+    },
+    ops::{
+        Deref,
+        DerefMut,
+    },
+};
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    processors::seeded::{
+        AccountOwner,
+        Owned,
+    },
+    AccountState,
+    Data,
+    ExecutionContext,
+    Result,
+};
+
+/// A layout of account data and how to upgrade to it from the layout before it.
+pub trait Migrate: BorshSerialize + BorshDeserialize {
+    /// Version of this layout, the first layout of a type being version 0.
+    const VERSION: u8;
+
+    /// The layout this one appends fields to, the first layout being its own previous layout.
+    type Previous: Migrate;
+
+    fn migrate(previous: Self::Previous) -> Self;
+
+    /// Deserialize all of `buf` at the newest layout that consumes it exactly, upgrading it
+    /// through each layout in between, along with the version it was read at.
+    fn deserialize_layout(buf: &mut &[u8]) -> std::io::Result<(u8, Self)> {
+        let mut rest = *buf;
+        match Self::deserialize(&mut rest) {
+            Ok(data) if rest.is_empty() => {
+                *buf = rest;
+                Ok((Self::VERSION, data))
+            }
+            _ if <Self::Previous as Migrate>::VERSION < Self::VERSION => {
+                let (version, previous) = Self::Previous::deserialize_layout(buf)?;
+                Ok((version, Self::migrate(previous)))
+            }
+            Ok(_) => Err(Error::new(InvalidData, "Data is longer than its layout")),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Account data read at the newest layout its length fits, see the module docs.
+pub struct Versioned<T: Migrate> {
+    /// Version the data was read at.
+    pub version: u8,
+
+    /// Serialized length of the layout the data was read at, and is written back at, for data
+    /// read at an older layout.
+    len: Option<usize>,
+
+    pub data: T,
+}
+
+impl<T: Migrate> Versioned<T> {
+    pub fn new(data: T) -> Self {
+        Versioned {
+            version: T::VERSION,
+            len: None,
+            data,
+        }
+    }
+
+    /// Whether the data was read at an older layout, and is written back at it unless migrated.
+    pub fn is_outdated(&self) -> bool {
+        self.version != T::VERSION
+    }
+
+    /// Write the data at the current layout from now on.
+    pub fn upgrade(&mut self) {
+        self.version = T::VERSION;
+        self.len = None;
+    }
+}
+
+impl<T: Migrate> BorshSerialize for Versioned<T> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let data = self.data.try_to_vec()?;
+        let len = self.len.unwrap_or(data.len());
+
+        // Appended fields only serialize to zeroes while they hold their defaults.
+        if data[len..].iter().any(|byte| *byte != 0) {
+            return Err(Error::new(
+                InvalidData,
+                format!(
+                    "Account at version {} has to be migrated to store version {}",
+                    self.version,
+                    T::VERSION
+                ),
+            ));
+        }
+        writer.write_all(&data[..len])
+    }
+}
+
+impl<T: Migrate> BorshDeserialize for Versioned<T> {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let available = buf.len();
+        let (version, data) = T::deserialize_layout(buf)?;
+        Ok(Versioned {
+            version,
+            len: (version != T::VERSION).then(|| available - buf.len()),
+            data,
+        })
+    }
+}
+
+impl<T: Migrate + Default> Default for Versioned<T> {
+    fn default() -> Self {
+        Versioned::new(T::default())
+    }
+}
+
+impl<T: Migrate + Owned> Owned for Versioned<T> {
+    fn owner(&self) -> AccountOwner {
+        self.data.owner()
+    }
+}
+
+impl<T: Migrate> Deref for Versioned<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<T: Migrate> DerefMut for Versioned<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+impl<'r, T, const IS_INITIALIZED: AccountState> Data<'r, Versioned<T>, IS_INITIALIZED>
+where
+    T: Migrate + Owned + Default,
+{
+    /// Upgrade the account to the current layout when it is persisted, resizing it at the expense
+    /// of `payer` if it was read at an older version. Only instructions setting a field an older
+    /// layout lacks need to, and current accounts are left as they are, so the system program is
+    /// only needed by instructions upgrading an account.
+    pub fn migrate(&mut self, ctx: &ExecutionContext<'_, 'r>, payer: &Pubkey) -> Result<()> {
+        if self.1.is_outdated() && !self.0.data_is_empty() {
+            self.1.upgrade();
+            self.realloc(ctx, payer)?;
+        }
+        Ok(())
+    }
+}
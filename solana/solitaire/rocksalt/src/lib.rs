@@ -1,14 +1,23 @@
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
+use quote::{
+    format_ident,
+    quote,
+};
 use syn::{
     parse_macro_input,
     parse_quote,
+    visit_mut::VisitMut,
     Data,
     DeriveInput,
     Fields,
     GenericParam,
     Generics,
+    Lifetime,
+    Lit,
+    Meta,
+    NestedMeta,
+    Type,
 };
 
 /// Generate a FromAccounts implementation for a product of accounts. Each field is constructed by
@@ -50,6 +59,8 @@ pub fn derive_from_accounts(input: TokenStream) -> TokenStream {
 
     let from_method = generate_fields(&name, &input.data);
     let persist_method = generate_persist(&input.data);
+    let idl_method = generate_idl(&input.data);
+    let client = generate_client(&name, &input.vis, &input.data);
     let expanded = quote! {
         /// Macro generated implementation of FromAccounts by Solitaire.
         impl #combined_impl_g solitaire::FromAccounts #peel_type_g for #name #type_g {
@@ -64,12 +75,130 @@ pub fn derive_from_accounts(input: TokenStream) -> TokenStream {
                 #persist_method
             }
         }
+
+        solitaire::idl_items! {
+            /// Macro generated implementation of IdlAccounts by Solitaire.
+            impl #type_impl_g solitaire::idl::IdlAccounts for #name #type_g {
+                fn accounts() -> Vec<solitaire::idl::IdlAccount> {
+                    #idl_method
+                }
+            }
+        }
+
+        #client
     };
 
     // Hand the output tokens back to the compiler
     TokenStream::from(expanded)
 }
 
+/// Generate error codes for a program's error enum. Variants are numbered in declaration order
+/// from the offset given by `#[error_code(offset = N)]`, so new errors must be appended to keep
+/// the codes clients see stable. The enum converts into a SolitaireError carrying the code and
+/// the variant name, which the dispatcher logs before failing the instruction.
+#[proc_macro_derive(ErrorCode, attributes(error_code))]
+pub fn derive_error_code(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match generate_error_code(&input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
+fn generate_error_code(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    let variants = match input.data {
+        Data::Enum(ref data) => &data.variants,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "ErrorCode can only be derived for enums",
+            ))
+        }
+    };
+
+    // Find `offset` within `#[error_code(...)]`.
+    let mut offset = None;
+    for attr in input.attrs.iter().filter(|a| a.path.is_ident("error_code")) {
+        if let Meta::List(list) = attr.parse_meta()? {
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::NameValue(meta)) if meta.path.is_ident("offset") => {
+                        match meta.lit {
+                            Lit::Int(lit) => offset = Some(lit.base10_parse::<u32>()?),
+                            lit => return Err(syn::Error::new_spanned(lit, "expected an integer")),
+                        }
+                    }
+                    other => return Err(syn::Error::new_spanned(other, "expected `offset = N`")),
+                }
+            }
+        }
+    }
+    let offset = offset.ok_or_else(|| {
+        syn::Error::new_spanned(name, "missing #[error_code(offset = N)] attribute")
+    })?;
+
+    let mut idents = Vec::new();
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "ErrorCode variants cannot carry fields",
+            ));
+        }
+        idents.push(&variant.ident);
+    }
+    let codes: Vec<u32> = (0..idents.len() as u32).map(|i| offset + i).collect();
+    let names = idents.iter().map(|ident| ident.to_string());
+
+    Ok(quote! {
+        const _: () = assert!(
+            #offset >= solitaire::error::MIN_PROGRAM_ERROR_OFFSET,
+            "error offsets below solitaire::error::MIN_PROGRAM_ERROR_OFFSET are reserved"
+        );
+
+        /// Macro generated error codes by Solitaire.
+        impl #name {
+            /// Code returned to clients as `ProgramError::Custom` for this error.
+            pub fn code(&self) -> u32 {
+                match self {
+                    #(#name::#idents => #codes,)*
+                }
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    #(#name::#idents => #names,)*
+                }
+            }
+
+            /// Decodes the code of a failed instruction back into the error.
+            pub fn from_code(code: u32) -> Option<Self> {
+                match code {
+                    #(#codes => Some(#name::#idents),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl std::fmt::Display for #name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+
+        impl From<#name> for solitaire::SolitaireError {
+            fn from(e: #name) -> solitaire::SolitaireError {
+                solitaire::SolitaireError::Program {
+                    code: e.code(),
+                    name: e.name(),
+                }
+            }
+        }
+    })
+}
+
 /// This function does the heavy lifting of generating the field parsers.
 fn generate_fields(name: &syn::Ident, data: &Data) -> TokenStream2 {
     match *data {
@@ -172,3 +301,146 @@ fn generate_persist(data: &Data) -> TokenStream2 {
         Data::Enum(_) | Data::Union(_) => unimplemented!(),
     }
 }
+
+/// Generates the IDL account list, each field type describes its own layers via Peel::describe.
+fn generate_idl(data: &Data) -> TokenStream2 {
+    match *data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => {
+                let recurse = fields.named.iter().map(|f| {
+                    let name = &f.ident;
+                    let ty = &f.ty;
+
+                    // Doc comments on the field are carried over to the IDL.
+                    let docs = f.attrs.iter().filter_map(|attr| match attr.parse_meta() {
+                        Ok(Meta::NameValue(meta)) if meta.path.is_ident("doc") => match meta.lit {
+                            Lit::Str(doc) => Some(doc.value().trim().to_string()),
+                            _ => None,
+                        },
+                        _ => None,
+                    });
+
+                    quote! {
+                        {
+                            let mut account = solitaire::idl::IdlAccount::new(
+                                stringify!(#name),
+                                vec![#(#docs.to_string()),*],
+                            );
+                            <#ty as solitaire::Peel>::describe(&mut account);
+                            account
+                        }
+                    }
+                });
+
+                quote! {
+                    vec![#(#recurse,)*]
+                }
+            }
+
+            Fields::Unnamed(_) => {
+                unimplemented!()
+            }
+
+            Fields::Unit => {
+                unimplemented!()
+            }
+        },
+
+        Data::Enum(_) | Data::Union(_) => unimplemented!(),
+    }
+}
+
+/// Rewrites every lifetime of a type to `'static`, so that account types can be named outside of
+/// the instruction context they were declared for.
+struct StaticLifetimes;
+
+impl VisitMut for StaticLifetimes {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        *lifetime = Lifetime::new("'static", lifetime.apostrophe);
+    }
+}
+
+/// Generates the client side accounts struct and its instruction builder. Fields take whatever
+/// solitaire::client::ClientAccount asks for, so accounts derived from a constant seed and sysvars
+/// are filled in here while accounts derived from data are left to the caller.
+fn generate_client(name: &syn::Ident, vis: &syn::Visibility, data: &Data) -> TokenStream2 {
+    let fields = match *data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            Fields::Unnamed(_) => unimplemented!(),
+            Fields::Unit => unimplemented!(),
+        },
+        Data::Enum(_) | Data::Union(_) => unimplemented!(),
+    };
+
+    let client_name = format_ident!("{}Accounts", name);
+    let client_doc = format!("Client side accounts of [`{}`].", name);
+
+    let types: Vec<Type> = fields
+        .iter()
+        .map(|f| {
+            let mut ty = f.ty.clone();
+            StaticLifetimes.visit_type_mut(&mut ty);
+            ty
+        })
+        .collect();
+
+    let client_fields = fields.iter().zip(&types).map(|(f, ty)| {
+        let name = &f.ident;
+        let docs = f.attrs.iter().filter(|attr| attr.path.is_ident("doc"));
+        quote! {
+            #(#docs)*
+            pub #name: <#ty as solitaire::client::ClientAccount>::Key
+        }
+    });
+
+    let metas = fields.iter().zip(&types).map(|(f, ty)| {
+        let name = &f.ident;
+        quote! {
+            {
+                let mut account = solitaire::idl::IdlAccount::new(stringify!(#name), vec![]);
+                <#ty as solitaire::Peel>::describe(&mut account);
+                solitaire::client::account_meta(
+                    &account,
+                    <#ty as solitaire::client::ClientAccount>::pubkey(&self.#name, program_id),
+                )
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #client_doc]
+        #[derive(Clone, Debug)]
+        #vis struct #client_name {
+            #(#client_fields,)*
+        }
+
+        /// Macro generated instruction builder by Solitaire.
+        impl #client_name {
+            /// Account metas in the order the program peels them.
+            pub fn to_account_metas(
+                &self,
+                program_id: &solana_program::pubkey::Pubkey,
+            ) -> Vec<solana_program::instruction::AccountMeta> {
+                vec![#(#metas,)*]
+            }
+
+            /// Builds the instruction, `data` starts with the instruction discriminant and
+            /// `remaining` lists accounts the handler only passes on to other programs.
+            pub fn instruction(
+                &self,
+                program_id: solana_program::pubkey::Pubkey,
+                data: impl solitaire::BorshSerialize,
+                remaining: &[solana_program::instruction::AccountMeta],
+            ) -> solitaire::Result<solana_program::instruction::Instruction> {
+                let mut accounts = self.to_account_metas(&program_id);
+                accounts.extend_from_slice(remaining);
+                Ok(solana_program::instruction::Instruction {
+                    program_id,
+                    accounts,
+                    data: solitaire::BorshSerialize::try_to_vec(&data)?,
+                })
+            }
+        }
+    }
+}